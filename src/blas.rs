@@ -0,0 +1,131 @@
+use core::ffi::c_int;
+
+// The library itself is linked by custos.
+extern "C" {
+    #[allow(clippy::too_many_arguments)]
+    fn cblas_sgemm(
+        order: c_int,
+        trans_a: c_int,
+        trans_b: c_int,
+        m: c_int,
+        n: c_int,
+        k: c_int,
+        alpha: f32,
+        a: *const f32,
+        lda: c_int,
+        b: *const f32,
+        ldb: c_int,
+        beta: f32,
+        c: *mut f32,
+        ldc: c_int,
+    );
+
+    #[allow(clippy::too_many_arguments)]
+    fn cblas_dgemm(
+        order: c_int,
+        trans_a: c_int,
+        trans_b: c_int,
+        m: c_int,
+        n: c_int,
+        k: c_int,
+        alpha: f64,
+        a: *const f64,
+        lda: c_int,
+        b: *const f64,
+        ldb: c_int,
+        beta: f64,
+        c: *mut f64,
+        ldc: c_int,
+    );
+}
+
+const ROW_MAJOR: c_int = 101;
+const NO_TRANS: c_int = 111;
+const TRANS: c_int = 112;
+
+/// Row major `gemm`s that [`GenericBlas`](custos::GenericBlas) does not expose: with `alpha` and `beta` and with both operands transposed.
+pub trait BlasGemm: Sized {
+    /// Computes `c = alpha * a * b + beta * c` for the `m x k` matrix `a` and the `k x n` matrix `b`.
+    /// If `beta` is zero, `c` is not read.
+    #[allow(clippy::too_many_arguments)]
+    fn gemm_acc(
+        m: usize,
+        n: usize,
+        k: usize,
+        alpha: Self,
+        a: &[Self],
+        b: &[Self],
+        beta: Self,
+        c: &mut [Self],
+    );
+
+    /// Computes `c = a.T() * b.T()` for the `k x m` matrix `a` and the `n x k` matrix `b`.
+    /// Both operands are read transposed in place.
+    fn gemm_tt(m: usize, n: usize, k: usize, a: &[Self], b: &[Self], c: &mut [Self]);
+}
+
+macro_rules! impl_blas_gemm {
+    ($t:ty, $gemm:ident) => {
+        impl BlasGemm for $t {
+            #[inline]
+            fn gemm_acc(
+                m: usize,
+                n: usize,
+                k: usize,
+                alpha: Self,
+                a: &[Self],
+                b: &[Self],
+                beta: Self,
+                c: &mut [Self],
+            ) {
+                assert!(a.len() >= m * k && b.len() >= k * n && c.len() >= m * n);
+
+                unsafe {
+                    $gemm(
+                        ROW_MAJOR,
+                        NO_TRANS,
+                        NO_TRANS,
+                        m as c_int,
+                        n as c_int,
+                        k as c_int,
+                        alpha,
+                        a.as_ptr(),
+                        k.max(1) as c_int,
+                        b.as_ptr(),
+                        n.max(1) as c_int,
+                        beta,
+                        c.as_mut_ptr(),
+                        n.max(1) as c_int,
+                    )
+                }
+            }
+
+            #[inline]
+            fn gemm_tt(m: usize, n: usize, k: usize, a: &[Self], b: &[Self], c: &mut [Self]) {
+                assert!(a.len() >= k * m && b.len() >= n * k && c.len() >= m * n);
+
+                unsafe {
+                    $gemm(
+                        ROW_MAJOR,
+                        TRANS,
+                        TRANS,
+                        m as c_int,
+                        n as c_int,
+                        k as c_int,
+                        1.,
+                        a.as_ptr(),
+                        m.max(1) as c_int,
+                        b.as_ptr(),
+                        k.max(1) as c_int,
+                        0.,
+                        c.as_mut_ptr(),
+                        n.max(1) as c_int,
+                    )
+                }
+            }
+        }
+    };
+}
+
+impl_blas_gemm!(f32, cblas_sgemm);
+impl_blas_gemm!(f64, cblas_dgemm);
@@ -1,7 +1,7 @@
 use crate::Matrix;
 use custos::{
-    cache::Cache, cuda::launch_kernel1d, prelude::CUBuffer, CDatatype, Device, Read, WriteBuf, CPU,
-    CUDA,
+    cache::Cache, cuda::launch_kernel1d, prelude::CUBuffer, CDatatype, Device, Read, Shape,
+    WriteBuf, CPU, CUDA,
 };
 
 pub fn cu_scalar_op<'a, T: CDatatype>(
@@ -79,6 +79,66 @@ pub fn cu_str_op_mut<'a, T: CDatatype>(
     Ok(())
 }
 
+/// Like [`cu_str_op`], but `op` may additionally use the scalar `p`, which is passed as a kernel argument.
+pub fn cu_str_param_op<'a, T: CDatatype>(
+    device: &'a CUDA,
+    lhs: &CUBuffer<T>,
+    p: T,
+    op: &str,
+) -> custos::Result<CUBuffer<'a, T>> {
+    let src = format!(
+        r#"extern "C" __global__ void str_param_op({datatype}* lhs, {datatype} p, {datatype}* out, int numElements)
+            {{
+                int idx = blockDim.x * blockIdx.x + threadIdx.x;
+                if (idx < numElements) {{
+                    {datatype} x = lhs[idx];
+                    out[idx] = {op};
+                }}
+            }}
+    "#,
+        datatype = T::as_c_type_str()
+    );
+
+    let out = device.retrieve::<T, ()>(lhs.len(), lhs);
+    launch_kernel1d(
+        lhs.len(),
+        device,
+        &src,
+        "str_param_op",
+        &[&lhs, &p, &out, &lhs.len()],
+    )?;
+    Ok(out)
+}
+
+pub fn cu_str_param_op_mut<'a, T: CDatatype>(
+    device: &'a CUDA,
+    lhs: &CUBuffer<T>,
+    p: T,
+    op: &str,
+) -> custos::Result<()> {
+    let src = format!(
+        r#"extern "C" __global__ void str_param_op_mut({datatype}* lhs, {datatype} p, int numElements)
+            {{
+                int idx = blockDim.x * blockIdx.x + threadIdx.x;
+                if (idx < numElements) {{
+                    {datatype} x = lhs[idx];
+                    lhs[idx] = {op};
+                }}
+            }}
+    "#,
+        datatype = T::as_c_type_str()
+    );
+
+    launch_kernel1d(
+        lhs.len(),
+        device,
+        &src,
+        "str_param_op_mut",
+        &[&lhs, &p, &lhs.len()],
+    )?;
+    Ok(())
+}
+
 pub fn cu_to_cpu_lr<'o, T, F>(
     device: &'o CUDA,
     lhs: &Matrix<T, CUDA>,
@@ -123,6 +183,18 @@ where
     Matrix::from((device, result))
 }
 
+pub fn cu_to_cpu_idx<'o, T, F>(device: &'o CUDA, x: &Matrix<T, CUDA>, f: F) -> Matrix<'o, u32, CUDA>
+where
+    T: Copy + Default,
+    F: for<'b> Fn(&'b CPU, &Matrix<T>) -> Matrix<'b, u32>,
+{
+    let cpu = custos::CPU::new();
+    let x = Matrix::from((&cpu, x.dims(), x.read()));
+
+    let result = f(&cpu, &x);
+    Matrix::from((device, result))
+}
+
 pub fn cu_to_cpu_s_mut<T: Copy + Default, F: Fn(&CPU, &mut Matrix<T>)>(
     x: &mut Matrix<T, CUDA>,
     f: F,
@@ -142,3 +214,34 @@ pub fn cu_to_cpu_scalar<T: Copy + Default, F: Fn(&CPU, Matrix<T>) -> T>(
     let x = Matrix::from((&cpu, x.dims(), x.read()));
     f(&cpu, x)
 }
+
+/// Like [`cu_to_cpu_scalar`], but `f` may return anything that does not borrow from the [`CPU`],
+/// e.g. a `Result` or several matrices that were already converted back to CUDA.
+pub fn cu_to_cpu_with<T, S, R, F>(x: &Matrix<T, CUDA, S>, f: F) -> R
+where
+    T: Copy + Default,
+    S: Shape,
+    F: FnOnce(&CPU, &Matrix<T>) -> R,
+{
+    let cpu = custos::CPU::new();
+    let x = Matrix::from((&cpu, x.dims(), x.read()));
+    f(&cpu, &x)
+}
+
+/// [`cu_to_cpu_with`] for two matrices, which may have different datatypes, e.g. values and class indices.
+pub fn cu_to_cpu_lr_with<L, R, RS, O, F>(
+    lhs: &Matrix<L, CUDA>,
+    rhs: &Matrix<R, CUDA, RS>,
+    f: F,
+) -> O
+where
+    L: Copy + Default,
+    R: Copy + Default,
+    RS: Shape,
+    F: FnOnce(&CPU, &Matrix<L>, &Matrix<R>) -> O,
+{
+    let cpu = custos::CPU::new();
+    let lhs = Matrix::from((&cpu, lhs.dims(), lhs.read()));
+    let rhs = Matrix::from((&cpu, rhs.dims(), rhs.read()));
+    f(&cpu, &lhs, &rhs)
+}
@@ -21,7 +21,10 @@ pub use cuda::*;
 #[cfg(feature = "opencl")]
 pub use opencl::*;
 pub use ops::*;
+pub use syntax::*;
 
+#[cfg(feature = "blas")]
+pub mod blas;
 pub mod matrix_multiply;
 
 pub mod custos {
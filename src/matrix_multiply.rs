@@ -4,6 +4,13 @@ pub trait MatrixMultiply where Self: Sized {
         a: &[Self], rsa: usize, csa: usize,
         b: &[Self], rsb: usize, csb: usize,
         c: &mut [Self], rsc: usize, csc: usize);
+
+    /// Computes `c = alpha * a * b + beta * c`. If `beta` is zero, `c` is not read.
+    #[cfg(feature = "matrixmultiply")]
+    fn gemm_acc(m: usize, k: usize, n: usize, alpha: Self,
+        a: &[Self], rsa: usize, csa: usize,
+        b: &[Self], rsb: usize, csb: usize, beta: Self,
+        c: &mut [Self], rsc: usize, csc: usize);
 }
 
 #[allow(unused)]
@@ -27,6 +34,18 @@ mod implements {
                 }
                 
         }
+
+        #[cfg(feature = "matrixmultiply")]
+        #[inline]
+        fn gemm_acc(m: usize, k: usize, n: usize, alpha: Self,
+            a: &[Self], rsa: usize, csa: usize,
+            b: &[Self], rsb: usize, csb: usize, beta: Self,
+            c: &mut [Self], rsc: usize, csc: usize) {
+
+                unsafe {
+                    matrixmultiply::sgemm(m, k, n, alpha, a.as_ptr(), rsa as isize, csa as isize, b.as_ptr(), rsb as isize, csb as isize, beta, c.as_mut_ptr(), rsc as isize, csc as isize);
+                }
+        }
     }
 
     #[rustfmt::skip]
@@ -44,5 +63,17 @@ mod implements {
                 matrixmultiply::dgemm(m, k, n, 1., a.as_ptr(), rsa as isize, csa as isize, b.as_ptr(), rsb as isize, csb as isize, 1., c.as_mut_ptr(), rsc as isize, csc as isize);
             }           
         }
+
+        #[cfg(feature = "matrixmultiply")]
+        #[inline]
+        fn gemm_acc(m: usize, k: usize, n: usize, alpha: Self,
+            a: &[Self], rsa: usize, csa: usize,
+            b: &[Self], rsb: usize, csb: usize, beta: Self,
+            c: &mut [Self], rsc: usize, csc: usize)
+        {
+            unsafe {
+                matrixmultiply::dgemm(m, k, n, alpha, a.as_ptr(), rsa as isize, csa as isize, b.as_ptr(), rsb as isize, csb as isize, beta, c.as_mut_ptr(), rsc as isize, csc as isize);
+            }
+        }
     }
 }
@@ -8,11 +8,11 @@ use custos::{
         AsClCvoidPtr,
     },
     prelude::CLBuffer,
-    CDatatype, Device, Error, GraphReturn, WriteBuf, CPU,
+    CDatatype, Device, Error, GraphReturn, Shape, WriteBuf, CPU,
 };
 use std::fmt::Debug;
 
-use crate::{cl_scalar_op, cl_str_op, Matrix};
+use crate::{cl_scalar_op, cl_str_op, cl_str_param_op, Matrix};
 
 #[inline]
 pub fn cl_str_op_mat<'a, T: CDatatype>(
@@ -25,6 +25,18 @@ pub fn cl_str_op_mat<'a, T: CDatatype>(
     Ok((out, x.dims()).into())
 }
 
+#[inline]
+pub fn cl_str_param_op_mat<'a, T: CDatatype>(
+    device: &'a OpenCL,
+    x: &Matrix<T, OpenCL>,
+    p: T,
+    op: &str,
+) -> Result<Matrix<'a, T, OpenCL>, Error> {
+    let out: CLBuffer<T> = device.retrieve(x.len(), x.node.idx);
+    cl_str_param_op(device, x, &out, p, op)?;
+    Ok((out, x.dims()).into())
+}
+
 pub fn cl_scalar_op_mat<'a, T: CDatatype>(
     device: &'a OpenCL,
     x: &Matrix<T, OpenCL>,
@@ -226,6 +238,54 @@ where
     Ok(())
 }
 
+/// Like [`cpu_exec_scalar`], but `f` may return anything that does not borrow from the [`CPU`],
+/// e.g. a `Result` or several matrices that were already converted back to OpenCL.
+pub fn cpu_exec_with<T, S, R, F>(device: &OpenCL, matrix: &Matrix<T, OpenCL, S>, f: F) -> R
+where
+    F: FnOnce(&CPU, &Matrix<T>) -> R,
+    T: Copy + Default,
+    S: Shape,
+{
+    let cpu = CPU::new();
+
+    if device.unified_mem() {
+        return f(&cpu, &Matrix::from((matrix.ptr.host_ptr, matrix.dims)));
+    }
+
+    // convert an OpenCL buffer to a cpu buffer
+    let cpu_buf = Matrix::from((&cpu, matrix.dims(), matrix.read()));
+    f(&cpu, &cpu_buf)
+}
+
+/// [`cpu_exec_with`] for two matrices, which may have different datatypes, e.g. values and class indices.
+pub fn cpu_exec_lhs_rhs_with<L, R, RS, O, F>(
+    device: &OpenCL,
+    lhs: &Matrix<L, OpenCL>,
+    rhs: &Matrix<R, OpenCL, RS>,
+    f: F,
+) -> O
+where
+    F: FnOnce(&CPU, &Matrix<L>, &Matrix<R>) -> O,
+    L: Copy + Default,
+    R: Copy + Default,
+    RS: Shape,
+{
+    let cpu = CPU::new();
+
+    if device.unified_mem() {
+        return f(
+            &cpu,
+            &Matrix::from((lhs.ptr.host_ptr, lhs.dims)),
+            &Matrix::from((rhs.ptr.host_ptr, rhs.dims)),
+        );
+    }
+
+    // convert the OpenCL buffers to cpu buffers
+    let cpu_lhs = Matrix::from((&cpu, lhs.dims(), lhs.read()));
+    let cpu_rhs = Matrix::from((&cpu, rhs.dims(), rhs.read()));
+    f(&cpu, &cpu_lhs, &cpu_rhs)
+}
+
 pub fn cpu_exec_scalar<T, F>(device: &OpenCL, matrix: &Matrix<T, OpenCL>, f: F) -> T
 where
     F: Fn(&CPU, &Matrix<T>) -> T,
@@ -0,0 +1,294 @@
+use crate::{Axis, Matrix};
+use custos::{Alloc, Device, MainMemory, Shape};
+
+#[cfg(feature = "cpu")]
+use custos::CPU;
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[cfg(feature = "cuda")]
+use crate::{cu_to_cpu_idx, cu_to_cpu_with};
+#[cfg(feature = "cuda")]
+use custos::CUDA;
+
+#[cfg(feature = "opencl")]
+use super::cl_to_cpu_idx;
+#[cfg(feature = "opencl")]
+use crate::cpu_exec_with;
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+use custos::CDatatype;
+#[cfg(feature = "opencl")]
+use custos::OpenCL;
+
+impl<'a, T, D: Device, IS: Shape> Matrix<'a, T, D, IS> {
+    #[inline]
+    pub fn argmax(&self) -> usize
+    where
+        D: ArgMaxOps<T, IS>,
+    {
+        self.device().argmax(self)
+    }
+
+    #[inline]
+    pub fn argmax_rows<OS: Shape>(&self) -> Matrix<'a, u32, D, OS>
+    where
+        D: ArgMaxOps<T, IS, OS>,
+    {
+        self.device().argmax_rows(self)
+    }
+
+    #[inline]
+    pub fn argmax_cols<OS: Shape>(&self) -> Matrix<'a, u32, D, OS>
+    where
+        D: ArgMaxOps<T, IS, OS>,
+    {
+        self.device().argmax_cols(self)
+    }
+
+    #[inline]
+    pub fn argmin(&self) -> usize
+    where
+        D: ArgMaxOps<T, IS>,
+    {
+        self.device().argmin(self)
+    }
+
+    #[inline]
+    pub fn argmin_rows<OS: Shape>(&self) -> Matrix<'a, u32, D, OS>
+    where
+        D: ArgMaxOps<T, IS, OS>,
+    {
+        self.device().argmin_rows(self)
+    }
+
+    #[inline]
+    pub fn argmin_cols<OS: Shape>(&self) -> Matrix<'a, u32, D, OS>
+    where
+        D: ArgMaxOps<T, IS, OS>,
+    {
+        self.device().argmin_cols(self)
+    }
+}
+
+/// Indices of the maximum or minimum values.
+/// `argmax` and `argmin` return the index into the flattened matrix.
+/// The `_rows` variants return the row index of the extreme value of every column (1 x cols),
+/// the `_cols` variants the column index of the extreme value of every row (rows x 1).
+/// If a value occurs more than once, the first index is returned.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{ArgMaxOps, Matrix};
+///
+/// let device = CPU::new();
+/// let probs = Matrix::from((&device, (2, 3), [0.1, 0.7, 0.2, 0.8, 0.15, 0.05,]));
+///
+/// let labels = device.argmax_cols(&probs);
+/// assert_eq!(labels.read(), vec![1, 0]);
+/// assert_eq!(device.argmin(&probs), 5);
+/// ```
+pub trait ArgMaxOps<T, IS: Shape = (), OS: Shape = (), D: Device = Self>: Device {
+    fn argmax(&self, x: &Matrix<T, D, IS>) -> usize;
+    fn argmax_rows(&self, x: &Matrix<T, D, IS>) -> Matrix<u32, Self, OS>;
+    fn argmax_cols(&self, x: &Matrix<T, D, IS>) -> Matrix<u32, Self, OS>;
+    fn argmin(&self, x: &Matrix<T, D, IS>) -> usize;
+    fn argmin_rows(&self, x: &Matrix<T, D, IS>) -> Matrix<u32, Self, OS>;
+    fn argmin_cols(&self, x: &Matrix<T, D, IS>) -> Matrix<u32, Self, OS>;
+}
+
+/// Returns the index of the best value, where `replace(value, best)` decides whether `value` becomes the new best.
+/// Using `>` yields the index of the first maximum, `<` the index of the first minimum.
+pub fn arg_by<T, I, F>(values: I, replace: F) -> usize
+where
+    T: Copy,
+    I: Iterator<Item = T>,
+    F: Fn(T, T) -> bool,
+{
+    let mut best_idx = 0;
+    let mut best = None;
+
+    for (idx, value) in values.enumerate() {
+        if best.map_or(true, |best| replace(value, best)) {
+            best = Some(value);
+            best_idx = idx;
+        }
+    }
+    best_idx
+}
+
+/// Writes the indices of the extreme values of a row major `rows` x `cols` slice along `axis` into `out`.
+pub fn arg_slice<T, F>(x: &[T], rows: usize, cols: usize, axis: Axis, out: &mut [u32], replace: F)
+where
+    T: Copy,
+    F: Fn(T, T) -> bool,
+{
+    match axis {
+        Axis::All => out[0] = arg_by(x.iter().copied(), replace) as u32,
+        Axis::Rows => {
+            for (col, idx) in out.iter_mut().enumerate().take(cols) {
+                *idx = arg_by((0..rows).map(|row| x[row * cols + col]), &replace) as u32;
+            }
+        }
+        Axis::Cols => {
+            for (row, idx) in out.iter_mut().enumerate().take(rows) {
+                let index = row * cols;
+                *idx = arg_by(x[index..index + cols].iter().copied(), &replace) as u32;
+            }
+        }
+    }
+}
+
+pub fn arg_op<'a, T, F, D, IS, OS, Host>(
+    device: &'a Host,
+    x: &Matrix<T, D, IS>,
+    axis: Axis,
+    replace: F,
+) -> Matrix<'a, u32, Host, OS>
+where
+    T: Copy,
+    F: Fn(T, T) -> bool,
+    D: MainMemory,
+    IS: Shape,
+    OS: Shape,
+    Host: for<'b> Alloc<'b, u32, OS> + MainMemory,
+{
+    let (rows, cols) = axis.reduced_dims(x.dims());
+
+    let mut out = device.retrieve(rows * cols, x.node.idx);
+    arg_slice(x, x.rows(), x.cols(), axis, &mut out, replace);
+    (out, rows, cols).into()
+}
+
+#[cfg(feature = "cpu")]
+impl<T: Copy + PartialOrd, D: MainMemory, IS: Shape> ArgMaxOps<T, IS, (), D> for CPU {
+    #[inline]
+    fn argmax(&self, x: &Matrix<T, D, IS>) -> usize {
+        arg_by(x.iter().copied(), |value, max| value > max)
+    }
+
+    #[inline]
+    fn argmax_rows(&self, x: &Matrix<T, D, IS>) -> Matrix<u32> {
+        arg_op(self, x, Axis::Rows, |value, max| value > max)
+    }
+
+    #[inline]
+    fn argmax_cols(&self, x: &Matrix<T, D, IS>) -> Matrix<u32> {
+        arg_op(self, x, Axis::Cols, |value, max| value > max)
+    }
+
+    #[inline]
+    fn argmin(&self, x: &Matrix<T, D, IS>) -> usize {
+        arg_by(x.iter().copied(), |value, min| value < min)
+    }
+
+    #[inline]
+    fn argmin_rows(&self, x: &Matrix<T, D, IS>) -> Matrix<u32> {
+        arg_op(self, x, Axis::Rows, |value, min| value < min)
+    }
+
+    #[inline]
+    fn argmin_cols(&self, x: &Matrix<T, D, IS>) -> Matrix<u32> {
+        arg_op(self, x, Axis::Cols, |value, min| value < min)
+    }
+}
+
+#[cfg(feature = "stack")]
+impl<T: Copy + PartialOrd, D: MainMemory, IS: Shape, OS: Shape> ArgMaxOps<T, IS, OS, D> for Stack {
+    #[inline]
+    fn argmax(&self, x: &Matrix<T, D, IS>) -> usize {
+        arg_by(x.iter().copied(), |value, max| value > max)
+    }
+
+    #[inline]
+    fn argmax_rows(&self, x: &Matrix<T, D, IS>) -> Matrix<u32, Self, OS> {
+        arg_op(self, x, Axis::Rows, |value, max| value > max)
+    }
+
+    #[inline]
+    fn argmax_cols(&self, x: &Matrix<T, D, IS>) -> Matrix<u32, Self, OS> {
+        arg_op(self, x, Axis::Cols, |value, max| value > max)
+    }
+
+    #[inline]
+    fn argmin(&self, x: &Matrix<T, D, IS>) -> usize {
+        arg_by(x.iter().copied(), |value, min| value < min)
+    }
+
+    #[inline]
+    fn argmin_rows(&self, x: &Matrix<T, D, IS>) -> Matrix<u32, Self, OS> {
+        arg_op(self, x, Axis::Rows, |value, min| value < min)
+    }
+
+    #[inline]
+    fn argmin_cols(&self, x: &Matrix<T, D, IS>) -> Matrix<u32, Self, OS> {
+        arg_op(self, x, Axis::Cols, |value, min| value < min)
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: CDatatype> ArgMaxOps<T> for OpenCL {
+    #[inline]
+    fn argmax(&self, x: &Matrix<T, Self>) -> usize {
+        cpu_exec_with(self, x, |cpu, x| cpu.argmax(x))
+    }
+
+    #[inline]
+    fn argmax_rows(&self, x: &Matrix<T, Self>) -> Matrix<u32, Self> {
+        cl_to_cpu_idx(self, x, |device, x| device.argmax_rows(x))
+    }
+
+    #[inline]
+    fn argmax_cols(&self, x: &Matrix<T, Self>) -> Matrix<u32, Self> {
+        cl_to_cpu_idx(self, x, |device, x| device.argmax_cols(x))
+    }
+
+    #[inline]
+    fn argmin(&self, x: &Matrix<T, Self>) -> usize {
+        cpu_exec_with(self, x, |cpu, x| cpu.argmin(x))
+    }
+
+    #[inline]
+    fn argmin_rows(&self, x: &Matrix<T, Self>) -> Matrix<u32, Self> {
+        cl_to_cpu_idx(self, x, |device, x| device.argmin_rows(x))
+    }
+
+    #[inline]
+    fn argmin_cols(&self, x: &Matrix<T, Self>) -> Matrix<u32, Self> {
+        cl_to_cpu_idx(self, x, |device, x| device.argmin_cols(x))
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: CDatatype> ArgMaxOps<T> for CUDA {
+    #[inline]
+    fn argmax(&self, x: &Matrix<T, CUDA>) -> usize {
+        cu_to_cpu_with(x, |cpu, x| cpu.argmax(x))
+    }
+
+    #[inline]
+    fn argmax_rows(&self, x: &Matrix<T, CUDA>) -> Matrix<u32, CUDA> {
+        cu_to_cpu_idx(self, x, |cpu, x| cpu.argmax_rows(x))
+    }
+
+    #[inline]
+    fn argmax_cols(&self, x: &Matrix<T, CUDA>) -> Matrix<u32, CUDA> {
+        cu_to_cpu_idx(self, x, |cpu, x| cpu.argmax_cols(x))
+    }
+
+    #[inline]
+    fn argmin(&self, x: &Matrix<T, CUDA>) -> usize {
+        cu_to_cpu_with(x, |cpu, x| cpu.argmin(x))
+    }
+
+    #[inline]
+    fn argmin_rows(&self, x: &Matrix<T, CUDA>) -> Matrix<u32, CUDA> {
+        cu_to_cpu_idx(self, x, |cpu, x| cpu.argmin_rows(x))
+    }
+
+    #[inline]
+    fn argmin_cols(&self, x: &Matrix<T, CUDA>) -> Matrix<u32, CUDA> {
+        cu_to_cpu_idx(self, x, |cpu, x| cpu.argmin_cols(x))
+    }
+}
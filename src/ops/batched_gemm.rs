@@ -0,0 +1,231 @@
+use custos::{impl_stack, Device, MainMemory, Shape, CPU};
+
+#[cfg(feature = "blas")]
+use custos::GenericBlas;
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+use custos::CDatatype;
+
+#[cfg(feature = "opencl")]
+use crate::cl_batched_gemm;
+#[cfg(feature = "opencl")]
+use custos::OpenCL;
+
+#[cfg(feature = "cuda")]
+use crate::cu_to_cpu_lr;
+#[cfg(feature = "cuda")]
+use custos::CUDA;
+
+use crate::Matrix;
+
+impl<'a, T, D: Device, LS: Shape> Matrix<'a, T, D, LS> {
+    /// Batched matrix multiplication. See [`BatchedGemm`].
+    #[inline]
+    pub fn batched_gemm<RS: Shape, OS: Shape>(
+        &self,
+        batch: usize,
+        rhs: &Matrix<'a, T, D, RS>,
+    ) -> Matrix<'a, T, D, OS>
+    where
+        D: BatchedGemm<T, LS, RS, OS, D>,
+    {
+        self.device().batched_gemm(batch, self, rhs)
+    }
+}
+
+/// Multiplies `batch` pairs of matrices, which are stacked row wise in one matrix.
+///
+/// `lhs` holds `batch` m x k matrices, hence it has the dimensions (`batch` * m) x k.
+/// `rhs` holds either `batch` k x n matrices, (`batch` * k) x n,
+/// or a single k x n matrix, which is then used for every lhs matrix.
+/// The output holds `batch` m x n matrices: (`batch` * m) x n.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::{CPU, Read};
+/// use custos_math::{Matrix, BatchedGemm};
+///
+/// let device = CPU::new();
+///
+/// // two 1 x 2 matrices
+/// let lhs = Matrix::from((&device, (2, 2), [1., 2., 3., 4.,]));
+/// // two 2 x 1 matrices
+/// let rhs = Matrix::from((&device, (4, 1), [1., 1., 2., 0.,]));
+///
+/// let out: Matrix = device.batched_gemm(2, &lhs, &rhs);
+/// assert_eq!(out.dims(), (2, 1));
+/// assert_eq!(out.read(), vec![3., 6.,]);
+/// ```
+pub trait BatchedGemm<T, LS: Shape = (), RS: Shape = (), OS: Shape = (), D: Device = Self>:
+    Device
+{
+    fn batched_gemm(
+        &self,
+        batch: usize,
+        lhs: &Matrix<T, D, LS>,
+        rhs: &Matrix<T, D, RS>,
+    ) -> Matrix<T, Self, OS>;
+}
+
+/// Returns m, k, n and whether the rhs matrix is broadcasted.
+pub fn batched_gemm_dims(
+    batch: usize,
+    lhs_dims: (usize, usize),
+    rhs_dims: (usize, usize),
+) -> (usize, usize, usize, bool) {
+    let (lhs_rows, k) = lhs_dims;
+    let (rhs_rows, n) = rhs_dims;
+
+    assert!(
+        batch > 0 && lhs_rows % batch == 0,
+        "lhs must consist of {batch} matrices"
+    );
+    assert!(
+        rhs_rows == batch * k || rhs_rows == k,
+        "rhs must consist of {batch} matrices or a single matrix with {k} rows"
+    );
+
+    (lhs_rows / batch, k, n, rhs_rows == k && batch != 1)
+}
+
+/// Calls `gemm` for every lhs, rhs and output slice of a batch.
+pub fn batched_gemm_slices<T, F>(
+    batch: usize,
+    (m, k, n, broadcast_rhs): (usize, usize, usize, bool),
+    lhs: &[T],
+    rhs: &[T],
+    out: &mut [T],
+    mut gemm: F,
+) where
+    F: FnMut(&[T], &[T], &mut [T]),
+{
+    for idx in 0..batch {
+        let rhs_idx = if broadcast_rhs { 0 } else { idx };
+
+        gemm(
+            &lhs[idx * m * k..(idx + 1) * m * k],
+            &rhs[rhs_idx * k * n..(rhs_idx + 1) * k * n],
+            &mut out[idx * m * n..(idx + 1) * m * n],
+        );
+    }
+}
+
+#[cfg(feature = "blas")]
+#[cfg(not(feature = "matrixmultiply"))]
+#[impl_stack]
+impl<T, D, LS, RS, OS> BatchedGemm<T, LS, RS, OS, D> for CPU
+where
+    T: GenericBlas + Default + Copy,
+    D: MainMemory,
+    LS: Shape,
+    RS: Shape,
+    OS: Shape,
+{
+    fn batched_gemm(
+        &self,
+        batch: usize,
+        lhs: &Matrix<T, D, LS>,
+        rhs: &Matrix<T, D, RS>,
+    ) -> Matrix<T, Self, OS> {
+        let dims = batched_gemm_dims(batch, lhs.dims(), rhs.dims());
+        let (m, k, n, _) = dims;
+
+        let mut out = self.retrieve(batch * m * n, (lhs.node.idx, rhs.node.idx));
+        batched_gemm_slices(batch, dims, lhs, rhs, &mut out, |lhs, rhs, out| {
+            T::gemm(m, n, k, lhs, rhs, out)
+        });
+        (out, batch * m, n).into()
+    }
+}
+
+#[cfg(feature = "matrixmultiply")]
+#[cfg(not(feature = "blas"))]
+#[impl_stack]
+impl<T, D, LS, RS, OS> BatchedGemm<T, LS, RS, OS, D> for CPU
+where
+    T: crate::matrix_multiply::MatrixMultiply + Default + Copy,
+    D: MainMemory,
+    LS: Shape,
+    RS: Shape,
+    OS: Shape,
+{
+    fn batched_gemm(
+        &self,
+        batch: usize,
+        lhs: &Matrix<T, D, LS>,
+        rhs: &Matrix<T, D, RS>,
+    ) -> Matrix<T, Self, OS> {
+        let dims = batched_gemm_dims(batch, lhs.dims(), rhs.dims());
+        let (m, k, n, _) = dims;
+
+        let mut out = self.retrieve(batch * m * n, (lhs.node.idx, rhs.node.idx));
+        batched_gemm_slices(batch, dims, lhs, rhs, &mut out, |lhs, rhs, out| {
+            T::gemm(m, k, n, lhs, k, 1, rhs, n, 1, out, n, 1)
+        });
+        (out, batch * m, n).into()
+    }
+}
+
+#[cfg(not(feature = "matrixmultiply"))]
+#[cfg(not(feature = "blas"))]
+#[impl_stack]
+impl<T, D, LS, RS, OS> BatchedGemm<T, LS, RS, OS, D> for CPU
+where
+    T: Default + Copy + core::ops::Mul<Output = T> + core::ops::AddAssign,
+    D: MainMemory,
+    LS: Shape,
+    RS: Shape,
+    OS: Shape,
+{
+    fn batched_gemm(
+        &self,
+        batch: usize,
+        lhs: &Matrix<T, D, LS>,
+        rhs: &Matrix<T, D, RS>,
+    ) -> Matrix<T, Self, OS> {
+        let dims = batched_gemm_dims(batch, lhs.dims(), rhs.dims());
+        let (m, k, n, _) = dims;
+
+        let mut out = self.retrieve(batch * m * n, (lhs.node.idx, rhs.node.idx));
+        batched_gemm_slices(batch, dims, lhs, rhs, &mut out, |lhs, rhs, out| {
+            crate::raw_ops::naive_gemm(m, k, n, lhs, rhs, out)
+        });
+        (out, batch * m, n).into()
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: CDatatype> BatchedGemm<T> for OpenCL {
+    fn batched_gemm(
+        &self,
+        batch: usize,
+        lhs: &Matrix<T, Self>,
+        rhs: &Matrix<T, Self>,
+    ) -> Matrix<T, Self> {
+        let (m, k, n, broadcast_rhs) = batched_gemm_dims(batch, lhs.dims(), rhs.dims());
+
+        let buf = cl_batched_gemm(self, batch, m, k, n, lhs, rhs, broadcast_rhs).unwrap();
+        (buf, batch * m, n).into()
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: CDatatype> BatchedGemm<T> for CUDA
+where
+    CPU: BatchedGemm<T>,
+{
+    #[inline]
+    fn batched_gemm(
+        &self,
+        batch: usize,
+        lhs: &Matrix<T, Self>,
+        rhs: &Matrix<T, Self>,
+    ) -> Matrix<T, Self> {
+        cu_to_cpu_lr(self, lhs, rhs, |cpu, lhs, rhs| {
+            cpu.batched_gemm(batch, lhs, rhs)
+        })
+    }
+}
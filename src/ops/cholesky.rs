@@ -0,0 +1,197 @@
+use custos::{impl_stack, number::Float, Buffer, Device, MainMemory, Shape, CPU};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+use custos::CDatatype;
+
+#[cfg(feature = "opencl")]
+use crate::{cpu_exec_lhs_rhs_with, cpu_exec_with};
+#[cfg(feature = "opencl")]
+use custos::OpenCL;
+
+#[cfg(feature = "cuda")]
+use crate::{cu_to_cpu_lr_with, cu_to_cpu_with};
+#[cfg(feature = "cuda")]
+use custos::CUDA;
+
+use crate::{
+    cholesky_in_place, cholesky_logdet_slice, cholesky_solve_in_place, square_dims, LinalgError,
+    Matrix,
+};
+
+impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
+    /// The lower triangular Cholesky factor of `self`. See [`CholeskyOps::cholesky`].
+    #[inline]
+    pub fn cholesky(&self) -> Result<Matrix<'a, T, D, S>, LinalgError>
+    where
+        D: CholeskyOps<T, S>,
+    {
+        self.device().cholesky(self)
+    }
+
+    /// The log-determinant of the symmetric positive-definite `self`. See [`CholeskyOps::cholesky_logdet`].
+    #[inline]
+    pub fn cholesky_logdet(&self) -> Result<T, LinalgError>
+    where
+        D: CholeskyOps<T, S>,
+    {
+        self.device().cholesky_logdet(self)
+    }
+
+    /// Solves `self * self^T * x = b`, where `self` is a Cholesky factor. See [`CholeskySolveOp`].
+    #[inline]
+    pub fn cholesky_solve<BS: Shape>(
+        &self,
+        b: &Matrix<T, D, BS>,
+    ) -> Result<Matrix<'a, T, D, BS>, LinalgError>
+    where
+        D: CholeskySolveOp<T, S, BS>,
+    {
+        self.device().cholesky_solve(self, b)
+    }
+}
+
+/// Cholesky factorisation of symmetric positive-definite matrices.
+///
+/// Only the lower triangle of the input is read, the upper triangle is assumed to mirror it.
+/// # Errors
+/// [`LinalgError::NotPositiveDefinite`] names the first failing pivot if the matrix is not positive definite.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, CholeskyOps, CholeskySolveOp, LinalgError};
+///
+/// let device = CPU::new();
+///
+/// let a = Matrix::from((&device, (2, 2), [4., 2., 2., 5.]));
+///
+/// let l = device.cholesky(&a).unwrap();
+/// assert_eq!(l.read(), vec![2., 0., 1., 2.]);
+///
+/// let b = Matrix::from((&device, (2, 1), [8., 9.]));
+/// let x = device.cholesky_solve(&l, &b).unwrap();
+/// assert_eq!(x.read(), vec![1.375, 1.25]);
+///
+/// assert!((device.cholesky_logdet(&a).unwrap() - 16f64.ln()).abs() < 1e-12);
+///
+/// let indefinite = Matrix::from((&device, (2, 2), [1., 2., 2., 1.]));
+/// assert_eq!(
+///     device.cholesky(&indefinite).unwrap_err(),
+///     LinalgError::NotPositiveDefinite { pivot: 1 }
+/// );
+/// ```
+pub trait CholeskyOps<T, S: Shape = (), D: Device = Self>: Device {
+    /// Computes the lower triangular `l` with `a = l * l^T`.
+    fn cholesky(&self, a: &Matrix<T, D, S>) -> Result<Matrix<T, Self, S>, LinalgError>;
+
+    /// The logarithm of the determinant of `a`, computed with the Cholesky factor of `a`.
+    /// Unlike `det(a).ln()`, this does not overflow for large matrices.
+    fn cholesky_logdet(&self, a: &Matrix<T, D, S>) -> Result<T, LinalgError>;
+}
+
+#[impl_stack]
+impl<T: Float, D: MainMemory, S: Shape> CholeskyOps<T, S, D> for CPU {
+    fn cholesky(&self, a: &Matrix<T, D, S>) -> Result<Matrix<T, Self, S>, LinalgError> {
+        let n = square_dims(a)?;
+
+        let mut l = self.retrieve(a.len(), a.node.idx);
+        l.copy_from_slice(a);
+        cholesky_in_place(&mut l, n)?;
+
+        Ok((l, a.dims()).into())
+    }
+
+    fn cholesky_logdet(&self, a: &Matrix<T, D, S>) -> Result<T, LinalgError> {
+        let n = square_dims(a)?;
+
+        let mut l: Buffer<T, Self, S> = self.retrieve(a.len(), a.node.idx);
+        l.copy_from_slice(a);
+        cholesky_in_place(&mut l, n)?;
+
+        Ok(cholesky_logdet_slice(&l, n))
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: Float + CDatatype> CholeskyOps<T> for OpenCL {
+    fn cholesky(&self, a: &Matrix<T, Self>) -> Result<Matrix<T, Self>, LinalgError> {
+        cpu_exec_with(self, a, |cpu, a| Ok(Matrix::from((self, cpu.cholesky(a)?))))
+    }
+
+    fn cholesky_logdet(&self, a: &Matrix<T, Self>) -> Result<T, LinalgError> {
+        cpu_exec_with(self, a, |cpu, a| cpu.cholesky_logdet(a))
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: Float + CDatatype> CholeskyOps<T> for CUDA {
+    fn cholesky(&self, a: &Matrix<T, Self>) -> Result<Matrix<T, Self>, LinalgError> {
+        cu_to_cpu_with(a, |cpu, a| Ok(Matrix::from((self, cpu.cholesky(a)?))))
+    }
+
+    fn cholesky_logdet(&self, a: &Matrix<T, Self>) -> Result<T, LinalgError> {
+        cu_to_cpu_with(a, |cpu, a| cpu.cholesky_logdet(a))
+    }
+}
+
+/// Solves `l * l^T * x = b` for every column of `b`, where `l` is the Cholesky factor returned by [`CholeskyOps::cholesky`].
+/// `BS` is the shape of the right-hand side `b` and the solution `x`.
+pub trait CholeskySolveOp<T, S: Shape = (), BS: Shape = (), D: Device = Self>: Device {
+    fn cholesky_solve(
+        &self,
+        l: &Matrix<T, D, S>,
+        b: &Matrix<T, D, BS>,
+    ) -> Result<Matrix<T, Self, BS>, LinalgError>;
+}
+
+#[impl_stack]
+impl<T: Float, D: MainMemory, S: Shape, BS: Shape> CholeskySolveOp<T, S, BS, D> for CPU {
+    fn cholesky_solve(
+        &self,
+        l: &Matrix<T, D, S>,
+        b: &Matrix<T, D, BS>,
+    ) -> Result<Matrix<T, Self, BS>, LinalgError> {
+        let n = square_dims(l)?;
+        if b.rows() != n {
+            return Err(LinalgError::DimsMismatch {
+                lhs: l.dims(),
+                rhs: b.dims(),
+            });
+        }
+
+        let mut x = self.retrieve(b.len(), (l.node.idx, b.node.idx));
+        x.copy_from_slice(b);
+        cholesky_solve_in_place(l, n, &mut x, b.cols());
+
+        Ok((x, b.dims()).into())
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: Float + CDatatype> CholeskySolveOp<T> for OpenCL {
+    fn cholesky_solve(
+        &self,
+        l: &Matrix<T, Self>,
+        b: &Matrix<T, Self>,
+    ) -> Result<Matrix<T, Self>, LinalgError> {
+        cpu_exec_lhs_rhs_with(self, l, b, |cpu, l, b| {
+            Ok(Matrix::from((self, cpu.cholesky_solve(l, b)?)))
+        })
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: Float + CDatatype> CholeskySolveOp<T> for CUDA {
+    fn cholesky_solve(
+        &self,
+        l: &Matrix<T, Self>,
+        b: &Matrix<T, Self>,
+    ) -> Result<Matrix<T, Self>, LinalgError> {
+        cu_to_cpu_lr_with(l, b, |cpu, l, b| {
+            Ok(Matrix::from((self, cpu.cholesky_solve(l, b)?)))
+        })
+    }
+}
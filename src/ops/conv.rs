@@ -0,0 +1,265 @@
+use crate::{
+    convolve_mut, correlate_grad_input_mut, correlate_grad_kernel_mut, correlate_mut, ConvConfig,
+    Matrix,
+};
+use custos::{number::Number, Device, MainMemory};
+
+#[cfg(feature = "cpu")]
+use custos::CPU;
+
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+use custos::CDatatype;
+
+#[cfg(feature = "opencl")]
+use super::cl_to_cpu_lr;
+#[cfg(feature = "opencl")]
+use custos::OpenCL;
+
+#[cfg(feature = "cuda")]
+use crate::cu_to_cpu_lr;
+#[cfg(feature = "cuda")]
+use custos::CUDA;
+
+impl<'a, T, D: ConvOps<T>> Matrix<'a, T, D> {
+    #[inline]
+    pub fn correlate_valid(&self, kernel: &Matrix<T, D>) -> Matrix<'a, T, D> {
+        self.device().correlate_valid(self, kernel)
+    }
+
+    #[inline]
+    pub fn correlate_full(&self, kernel: &Matrix<T, D>) -> Matrix<'a, T, D> {
+        self.device().correlate_full(self, kernel)
+    }
+
+    #[inline]
+    pub fn correlate(&self, kernel: &Matrix<T, D>, config: ConvConfig) -> Matrix<'a, T, D> {
+        self.device().correlate(self, kernel, config)
+    }
+
+    #[inline]
+    pub fn convolve(&self, kernel: &Matrix<T, D>, config: ConvConfig) -> Matrix<'a, T, D> {
+        self.device().convolve(self, kernel, config)
+    }
+}
+
+/// 2D cross-correlation and convolution of a matrix with a kernel.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{ConvConfig, ConvOps, Matrix};
+///
+/// let device = CPU::new();
+///
+/// let x = Matrix::from((&device, (3, 3), [1., 2., 3., 4., 5., 6., 7., 8., 9.,]));
+/// let kernel = Matrix::from((&device, (2, 2), [1., 0., 0., 1.,]));
+///
+/// let out = device.correlate_valid(&x, &kernel);
+/// assert_eq!(out.dims(), (2, 2));
+/// assert_eq!(out.read(), vec![6., 8., 12., 14.]);
+///
+/// let config = ConvConfig::new((2, 2), (1, 1), (1, 1));
+/// let out = device.correlate(&x, &kernel, config);
+/// assert_eq!(out.read(), vec![1., 3., 7., 14.]);
+/// ```
+pub trait ConvOps<T, D: Device = Self>: Device {
+    /// Cross-correlation without padding and with stride 1.
+    #[inline]
+    fn correlate_valid(&self, x: &Matrix<T, D>, kernel: &Matrix<T, D>) -> Matrix<T, Self> {
+        self.correlate(x, kernel, ConvConfig::default())
+    }
+
+    /// Cross-correlation with stride 1 and full zero padding.
+    #[inline]
+    fn correlate_full(&self, x: &Matrix<T, D>, kernel: &Matrix<T, D>) -> Matrix<T, Self> {
+        self.correlate(x, kernel, ConvConfig::full(kernel.dims()))
+    }
+
+    fn correlate(
+        &self,
+        x: &Matrix<T, D>,
+        kernel: &Matrix<T, D>,
+        config: ConvConfig,
+    ) -> Matrix<T, Self>;
+
+    /// Convolution, i.e. a cross-correlation with the kernel rotated by 180 degrees.
+    fn convolve(
+        &self,
+        x: &Matrix<T, D>,
+        kernel: &Matrix<T, D>,
+        config: ConvConfig,
+    ) -> Matrix<T, Self>;
+
+    /// Gradient of [`ConvOps::correlate`] with respect to the kernel.
+    /// `grad` is the gradient of the correlation output.
+    fn correlate_grad_kernel(
+        &self,
+        x: &Matrix<T, D>,
+        grad: &Matrix<T, D>,
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T, Self>;
+
+    /// Gradient of [`ConvOps::correlate`] with respect to the input.
+    /// `grad` is the gradient of the correlation output.
+    fn correlate_grad_input(
+        &self,
+        grad: &Matrix<T, D>,
+        kernel: &Matrix<T, D>,
+        x_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T, Self>;
+}
+
+#[cfg(feature = "cpu")]
+impl<T: Number, D: MainMemory> ConvOps<T, D> for CPU {
+    fn correlate(&self, x: &Matrix<T, D>, kernel: &Matrix<T, D>, config: ConvConfig) -> Matrix<T> {
+        let out_dims = config.out_dims(x.dims(), kernel.dims());
+
+        let mut out = self.retrieve(out_dims.0 * out_dims.1, (x.node.idx, kernel.node.idx));
+        correlate_mut(x, x.dims(), kernel, kernel.dims(), &config, &mut out);
+        (out, out_dims).into()
+    }
+
+    fn convolve(&self, x: &Matrix<T, D>, kernel: &Matrix<T, D>, config: ConvConfig) -> Matrix<T> {
+        let out_dims = config.out_dims(x.dims(), kernel.dims());
+
+        let mut out = self.retrieve(out_dims.0 * out_dims.1, (x.node.idx, kernel.node.idx));
+        convolve_mut(x, x.dims(), kernel, kernel.dims(), &config, &mut out);
+        (out, out_dims).into()
+    }
+
+    fn correlate_grad_kernel(
+        &self,
+        x: &Matrix<T, D>,
+        grad: &Matrix<T, D>,
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T> {
+        assert_eq!(grad.dims(), config.out_dims(x.dims(), kernel_dims));
+
+        let mut out = self.retrieve(kernel_dims.0 * kernel_dims.1, (x.node.idx, grad.node.idx));
+        correlate_grad_kernel_mut(x, x.dims(), grad, kernel_dims, &config, &mut out);
+        (out, kernel_dims).into()
+    }
+
+    fn correlate_grad_input(
+        &self,
+        grad: &Matrix<T, D>,
+        kernel: &Matrix<T, D>,
+        x_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T> {
+        assert_eq!(grad.dims(), config.out_dims(x_dims, kernel.dims()));
+
+        let mut out = self.retrieve(x_dims.0 * x_dims.1, (grad.node.idx, kernel.node.idx));
+        correlate_grad_input_mut(grad, kernel, kernel.dims(), x_dims, &config, &mut out);
+        (out, x_dims).into()
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: CDatatype> ConvOps<T> for OpenCL {
+    #[inline]
+    fn correlate(
+        &self,
+        x: &Matrix<T, Self>,
+        kernel: &Matrix<T, Self>,
+        config: ConvConfig,
+    ) -> Matrix<T, Self> {
+        cl_to_cpu_lr(self, x, kernel, |device, x, kernel| {
+            device.correlate(x, kernel, config)
+        })
+    }
+
+    #[inline]
+    fn convolve(
+        &self,
+        x: &Matrix<T, Self>,
+        kernel: &Matrix<T, Self>,
+        config: ConvConfig,
+    ) -> Matrix<T, Self> {
+        cl_to_cpu_lr(self, x, kernel, |device, x, kernel| {
+            device.convolve(x, kernel, config)
+        })
+    }
+
+    #[inline]
+    fn correlate_grad_kernel(
+        &self,
+        x: &Matrix<T, Self>,
+        grad: &Matrix<T, Self>,
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T, Self> {
+        cl_to_cpu_lr(self, x, grad, |device, x, grad| {
+            device.correlate_grad_kernel(x, grad, kernel_dims, config)
+        })
+    }
+
+    #[inline]
+    fn correlate_grad_input(
+        &self,
+        grad: &Matrix<T, Self>,
+        kernel: &Matrix<T, Self>,
+        x_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T, Self> {
+        cl_to_cpu_lr(self, grad, kernel, |device, grad, kernel| {
+            device.correlate_grad_input(grad, kernel, x_dims, config)
+        })
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: CDatatype> ConvOps<T> for CUDA {
+    #[inline]
+    fn correlate(
+        &self,
+        x: &Matrix<T, Self>,
+        kernel: &Matrix<T, Self>,
+        config: ConvConfig,
+    ) -> Matrix<T, Self> {
+        cu_to_cpu_lr(self, x, kernel, |device, x, kernel| {
+            device.correlate(x, kernel, config)
+        })
+    }
+
+    #[inline]
+    fn convolve(
+        &self,
+        x: &Matrix<T, Self>,
+        kernel: &Matrix<T, Self>,
+        config: ConvConfig,
+    ) -> Matrix<T, Self> {
+        cu_to_cpu_lr(self, x, kernel, |device, x, kernel| {
+            device.convolve(x, kernel, config)
+        })
+    }
+
+    #[inline]
+    fn correlate_grad_kernel(
+        &self,
+        x: &Matrix<T, Self>,
+        grad: &Matrix<T, Self>,
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T, Self> {
+        cu_to_cpu_lr(self, x, grad, |device, x, grad| {
+            device.correlate_grad_kernel(x, grad, kernel_dims, config)
+        })
+    }
+
+    #[inline]
+    fn correlate_grad_input(
+        &self,
+        grad: &Matrix<T, Self>,
+        kernel: &Matrix<T, Self>,
+        x_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T, Self> {
+        cu_to_cpu_lr(self, grad, kernel, |device, grad, kernel| {
+            device.correlate_grad_input(grad, kernel, x_dims, config)
+        })
+    }
+}
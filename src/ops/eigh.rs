@@ -0,0 +1,108 @@
+use custos::{impl_stack, number::Float, Buffer, Device, MainMemory, Shape, CPU};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+use custos::CDatatype;
+
+#[cfg(feature = "opencl")]
+use crate::cpu_exec_with;
+#[cfg(feature = "opencl")]
+use custos::OpenCL;
+
+#[cfg(feature = "cuda")]
+use crate::cu_to_cpu_with;
+#[cfg(feature = "cuda")]
+use custos::CUDA;
+
+use crate::{eigh_slice, square_dims, LinalgError, Matrix};
+
+impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
+    /// Eigenvalues and eigenvectors of the symmetric `self`. See [`EighOps`].
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    pub fn eigh<WS: Shape>(
+        &self,
+    ) -> Result<(Matrix<'a, T, D, WS>, Matrix<'a, T, D, S>), LinalgError>
+    where
+        D: EighOps<T, S, WS>,
+    {
+        self.device().eigh(self)
+    }
+}
+
+/// Eigendecomposition `a = v * diag(w) * v^T` of a symmetric `n x n` matrix with the cyclic Jacobi method.
+///
+/// Returns `(w, v)`, where `w` is a `1 x n` matrix of the eigenvalues in ascending order
+/// and the columns of the `n x n` matrix `v` are the corresponding orthonormal eigenvectors.
+/// `WS` is the shape of `w`.
+///
+/// Only the lower triangle of the input is read, the upper triangle is assumed to mirror it.
+/// # Errors
+/// [`LinalgError::NotSquare`] if the matrix is not square.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, EighOps};
+///
+/// let device = CPU::new();
+///
+/// let a = Matrix::from((&device, (2, 2), [2., 1., 1., 2.]));
+///
+/// let (w, v): (Matrix, Matrix) = device.eigh(&a).unwrap();
+/// assert_eq!(w.dims(), (1, 2));
+/// assert!((w[0] - 1.).abs() < 1e-12);
+/// assert!((w[1] - 3.).abs() < 1e-12);
+///
+/// // the eigenvector of 3 is (1, 1) / sqrt(2), up to the sign
+/// assert!((v[1].abs() - 0.5f64.sqrt()).abs() < 1e-12);
+/// assert!((v[1] - v[3]).abs() < 1e-12);
+/// ```
+pub trait EighOps<T, S: Shape = (), WS: Shape = (), D: Device = Self>: Device {
+    #[allow(clippy::type_complexity)]
+    fn eigh(
+        &self,
+        a: &Matrix<T, D, S>,
+    ) -> Result<(Matrix<T, Self, WS>, Matrix<T, Self, S>), LinalgError>;
+}
+
+#[impl_stack]
+impl<T: Float, D: MainMemory, S: Shape, WS: Shape> EighOps<T, S, WS, D> for CPU {
+    fn eigh(
+        &self,
+        a: &Matrix<T, D, S>,
+    ) -> Result<(Matrix<T, Self, WS>, Matrix<T, Self, S>), LinalgError> {
+        let n = square_dims(a)?;
+
+        let mut work: Buffer<T, Self, S> = self.retrieve(a.len(), a.node.idx);
+        work.copy_from_slice(a);
+
+        let mut w = self.retrieve(n, a.node.idx);
+        let mut v = self.retrieve(a.len(), a.node.idx);
+        eigh_slice(&mut work, n, &mut w, &mut v);
+
+        Ok(((w, 1, n).into(), (v, a.dims()).into()))
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: Float + CDatatype> EighOps<T> for OpenCL {
+    fn eigh(&self, a: &Matrix<T, Self>) -> Result<(Matrix<T, Self>, Matrix<T, Self>), LinalgError> {
+        cpu_exec_with(self, a, |cpu, a| {
+            let (w, v): (Matrix<T>, Matrix<T>) = cpu.eigh(a)?;
+            Ok((Matrix::from((self, w)), Matrix::from((self, v))))
+        })
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: Float + CDatatype> EighOps<T> for CUDA {
+    fn eigh(&self, a: &Matrix<T, Self>) -> Result<(Matrix<T, Self>, Matrix<T, Self>), LinalgError> {
+        cu_to_cpu_with(a, |cpu, a| {
+            let (w, v): (Matrix<T>, Matrix<T>) = cpu.eigh(a)?;
+            Ok((Matrix::from((self, w)), Matrix::from((self, v))))
+        })
+    }
+}
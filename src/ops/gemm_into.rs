@@ -0,0 +1,230 @@
+use custos::{Device, MainMemory, Shape, CPU};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+use custos::CDatatype;
+
+#[cfg(feature = "opencl")]
+use crate::cl_gemm_acc;
+#[cfg(feature = "opencl")]
+use custos::OpenCL;
+
+#[cfg(feature = "cuda")]
+use crate::{cu_to_cpu_lr_mut, cu_to_cpu_with};
+#[cfg(feature = "cuda")]
+use custos::CUDA;
+
+use crate::Matrix;
+
+impl<'a, T, D: Device, OS: Shape> Matrix<'a, T, D, OS> {
+    /// Computes `self = alpha * lhs * rhs + beta * self` without allocating a new output matrix.
+    /// # Example
+    #[cfg_attr(feature = "cpu", doc = "```")]
+    #[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+    /// use custos::CPU;
+    /// use custos_math::Matrix;
+    ///
+    /// let device = CPU::new();
+    ///
+    /// let a = Matrix::from((&device, (2, 3), [1., 2., 3., 4., 5., 6.,]));
+    /// let b = Matrix::from((&device, (3, 2), [6., 5., 4., 3., 2., 1.,]));
+    /// let mut c = Matrix::from((&device, (2, 2), [1., 1., 1., 1.,]));
+    ///
+    /// c.gemm_acc(2., &a, &b, 1.);
+    /// assert_eq!(c.read(), vec![41., 29., 113., 83.,]);
+    /// ```
+    #[inline]
+    pub fn gemm_acc<LS: Shape, RS: Shape>(
+        &mut self,
+        alpha: T,
+        lhs: &Matrix<'a, T, D, LS>,
+        rhs: &Matrix<'a, T, D, RS>,
+        beta: T,
+    ) where
+        D: GemmInto<T, LS, RS, OS, D>,
+    {
+        self.device().gemm_acc(alpha, lhs, rhs, beta, self)
+    }
+}
+
+/// General matrix multiplication into an existing output matrix: `out = alpha * lhs * rhs + beta * out`.
+/// If `beta` is zero, the previous values of `out` are ignored.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::{CPU, Read};
+/// use custos_math::{Matrix, GemmInto};
+///
+/// let device = CPU::new();
+///
+/// let a = Matrix::from((&device, (2, 3), [1., 2., 3., 4., 5., 6.,]));
+/// let b = Matrix::from((&device, (3, 2), [6., 5., 4., 3., 2., 1.,]));
+/// let mut grad = Matrix::from((&device, (2, 2), [1., 1., 1., 1.,]));
+///
+/// // accumulate
+/// device.gemm_acc(1., &a, &b, 1., &mut grad);
+/// assert_eq!(grad.read(), vec![21., 15., 57., 42.,]);
+///
+/// // overwrite
+/// device.gemm_acc(1., &a, &b, 0., &mut grad);
+/// assert_eq!(grad.read(), vec![20., 14., 56., 41.,]);
+/// ```
+pub trait GemmInto<T, LS: Shape = (), RS: Shape = (), OS: Shape = (), D: Device = Self>:
+    Device
+{
+    fn gemm_acc(
+        &self,
+        alpha: T,
+        lhs: &Matrix<T, D, LS>,
+        rhs: &Matrix<T, D, RS>,
+        beta: T,
+        out: &mut Matrix<T, Self, OS>,
+    );
+}
+
+#[cfg(feature = "blas")]
+#[cfg(not(feature = "matrixmultiply"))]
+#[custos::impl_stack]
+impl<T, D, LS, RS, OS> GemmInto<T, LS, RS, OS, D> for CPU
+where
+    T: crate::blas::BlasGemm,
+    D: MainMemory,
+    LS: Shape,
+    RS: Shape,
+    OS: Shape,
+{
+    #[inline]
+    fn gemm_acc(
+        &self,
+        alpha: T,
+        lhs: &Matrix<T, D, LS>,
+        rhs: &Matrix<T, D, RS>,
+        beta: T,
+        out: &mut Matrix<T, Self, OS>,
+    ) {
+        let (m, k) = lhs.dims();
+        let n = rhs.cols();
+
+        assert!(k == rhs.rows());
+        assert!(out.dims() == (m, n));
+
+        T::gemm_acc(m, n, k, alpha, lhs, rhs, beta, out);
+    }
+}
+
+#[cfg(feature = "matrixmultiply")]
+#[cfg(not(feature = "blas"))]
+#[custos::impl_stack]
+impl<T, D, LS, RS, OS> GemmInto<T, LS, RS, OS, D> for CPU
+where
+    T: crate::matrix_multiply::MatrixMultiply + Default + Copy,
+    D: MainMemory,
+    LS: Shape,
+    RS: Shape,
+    OS: Shape,
+{
+    #[inline]
+    fn gemm_acc(
+        &self,
+        alpha: T,
+        lhs: &Matrix<T, D, LS>,
+        rhs: &Matrix<T, D, RS>,
+        beta: T,
+        out: &mut Matrix<T, Self, OS>,
+    ) {
+        let (m, k) = lhs.dims();
+        let n = rhs.cols();
+
+        assert!(k == rhs.rows());
+        assert!(out.dims() == (m, n));
+
+        T::gemm_acc(m, k, n, alpha, lhs, k, 1, rhs, n, 1, beta, out, n, 1);
+    }
+}
+
+#[cfg(not(feature = "matrixmultiply"))]
+#[cfg(not(feature = "blas"))]
+#[custos::impl_stack]
+impl<T, D, LS, RS, OS> GemmInto<T, LS, RS, OS, D> for CPU
+where
+    T: Default
+        + Copy
+        + PartialEq
+        + core::ops::Mul<Output = T>
+        + core::ops::Add<Output = T>
+        + core::ops::AddAssign,
+    D: MainMemory,
+    LS: Shape,
+    RS: Shape,
+    OS: Shape,
+{
+    #[inline]
+    fn gemm_acc(
+        &self,
+        alpha: T,
+        lhs: &Matrix<T, D, LS>,
+        rhs: &Matrix<T, D, RS>,
+        beta: T,
+        out: &mut Matrix<T, Self, OS>,
+    ) {
+        let (m, k) = lhs.dims();
+        let n = rhs.cols();
+
+        assert!(k == rhs.rows());
+        assert!(out.dims() == (m, n));
+
+        crate::raw_ops::naive_gemm_acc(m, k, n, alpha, lhs, rhs, beta, out);
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: CDatatype> GemmInto<T> for OpenCL {
+    fn gemm_acc(
+        &self,
+        alpha: T,
+        lhs: &Matrix<T, Self>,
+        rhs: &Matrix<T, Self>,
+        beta: T,
+        out: &mut Matrix<T, Self>,
+    ) {
+        assert!(lhs.cols() == rhs.rows());
+        assert!(out.dims() == (lhs.rows(), rhs.cols()));
+
+        cl_gemm_acc(
+            self,
+            lhs.rows(),
+            lhs.cols(),
+            rhs.cols(),
+            alpha,
+            lhs,
+            rhs,
+            beta,
+            out,
+        )
+        .unwrap();
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: CDatatype> GemmInto<T> for CUDA
+where
+    CPU: GemmInto<T>,
+{
+    #[inline]
+    fn gemm_acc(
+        &self,
+        alpha: T,
+        lhs: &Matrix<T, Self>,
+        rhs: &Matrix<T, Self>,
+        beta: T,
+        out: &mut Matrix<T, Self>,
+    ) {
+        cu_to_cpu_with(rhs, |_, rhs| {
+            cu_to_cpu_lr_mut(self, out, lhs, |cpu, out, lhs| {
+                cpu.gemm_acc(alpha, lhs, rhs, beta, out)
+            })
+        });
+    }
+}
@@ -0,0 +1,271 @@
+use custos::{impl_stack, Device, MainMemory, Shape, CPU};
+
+#[cfg(feature = "blas")]
+use custos::GenericBlas;
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+use custos::CDatatype;
+
+#[cfg(feature = "opencl")]
+use crate::cl_gemm_trans;
+#[cfg(feature = "opencl")]
+use custos::OpenCL;
+
+#[cfg(feature = "cuda")]
+use crate::cu_to_cpu_lr;
+#[cfg(feature = "cuda")]
+use custos::CUDA;
+
+use crate::Matrix;
+
+impl<'a, T, D: Device, LS: Shape> Matrix<'a, T, D, LS> {
+    /// Matrix multiplication with the transposed lhs matrix: `self.T() * rhs`.
+    #[inline]
+    pub fn gemm_tn<RS: Shape, OS: Shape>(&self, rhs: &Matrix<'a, T, D, RS>) -> Matrix<'a, T, D, OS>
+    where
+        D: GemmTrans<T, LS, RS, OS, D>,
+    {
+        self.device().gemm_tn(self, rhs)
+    }
+
+    /// Matrix multiplication with the transposed rhs matrix: `self * rhs.T()`.
+    #[inline]
+    pub fn gemm_nt<RS: Shape, OS: Shape>(&self, rhs: &Matrix<'a, T, D, RS>) -> Matrix<'a, T, D, OS>
+    where
+        D: GemmTrans<T, LS, RS, OS, D>,
+    {
+        self.device().gemm_nt(self, rhs)
+    }
+
+    /// Matrix multiplication of both transposed matrices: `self.T() * rhs.T()`.
+    #[inline]
+    pub fn gemm_tt<RS: Shape, OS: Shape>(&self, rhs: &Matrix<'a, T, D, RS>) -> Matrix<'a, T, D, OS>
+    where
+        D: GemmTrans<T, LS, RS, OS, D>,
+    {
+        self.device().gemm_tt(self, rhs)
+    }
+}
+
+/// Matrix multiplication with transposed operands.
+/// The operands are read in place, hence, unlike `lhs.T().gemm(&rhs)`, no transposed copy is allocated.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::{CPU, Read};
+/// use custos_math::{Matrix, GemmTrans};
+///
+/// let device = CPU::new();
+///
+/// let x = Matrix::from((&device, (3, 2), [1., 4., 2., 5., 3., 6.,]));
+/// let grad = Matrix::from((&device, (3, 2), [6., 5., 4., 3., 2., 1.,]));
+///
+/// // x.T() * grad
+/// let c: Matrix = device.gemm_tn(&x, &grad);
+/// assert_eq!(c.read(), vec![20., 14., 56., 41.,]);
+/// ```
+pub trait GemmTrans<T, LS: Shape = (), RS: Shape = (), OS: Shape = (), D: Device = Self>:
+    Device
+{
+    /// `lhs.T() * rhs`
+    fn gemm_tn(&self, lhs: &Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>) -> Matrix<T, Self, OS>;
+    /// `lhs * rhs.T()`
+    fn gemm_nt(&self, lhs: &Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>) -> Matrix<T, Self, OS>;
+    /// `lhs.T() * rhs.T()`
+    fn gemm_tt(&self, lhs: &Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>) -> Matrix<T, Self, OS>;
+}
+
+#[cfg(feature = "blas")]
+#[cfg(not(feature = "matrixmultiply"))]
+#[impl_stack]
+impl<T, D, LS, RS, OS> GemmTrans<T, LS, RS, OS, D> for CPU
+where
+    T: GenericBlas + crate::blas::BlasGemm + Default + Copy,
+    D: MainMemory,
+    LS: Shape,
+    RS: Shape,
+    OS: Shape,
+{
+    #[inline]
+    fn gemm_tn(&self, lhs: &Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>) -> Matrix<T, Self, OS> {
+        let (k, m) = lhs.dims();
+        let n = rhs.cols();
+
+        debug_assert!(k == rhs.rows());
+
+        let mut out = self.retrieve(m * n, (lhs.node.idx, rhs.node.idx));
+        T::Tgemm(m, n, k, lhs, rhs, &mut out);
+        (out, m, n).into()
+    }
+
+    #[inline]
+    fn gemm_nt(&self, lhs: &Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>) -> Matrix<T, Self, OS> {
+        let (m, k) = lhs.dims();
+        let n = rhs.rows();
+
+        debug_assert!(k == rhs.cols());
+
+        let mut out = self.retrieve(m * n, (lhs.node.idx, rhs.node.idx));
+        T::gemmT(m, n, k, lhs, rhs, &mut out);
+        (out, m, n).into()
+    }
+
+    #[inline]
+    fn gemm_tt(&self, lhs: &Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>) -> Matrix<T, Self, OS> {
+        let (k, m) = lhs.dims();
+        let n = rhs.rows();
+
+        debug_assert!(k == rhs.cols());
+
+        let mut out = self.retrieve(m * n, (lhs.node.idx, rhs.node.idx));
+        T::gemm_tt(m, n, k, lhs, rhs, &mut out);
+        (out, m, n).into()
+    }
+}
+
+#[cfg(feature = "matrixmultiply")]
+#[cfg(not(feature = "blas"))]
+#[impl_stack]
+impl<T, D, LS, RS, OS> GemmTrans<T, LS, RS, OS, D> for CPU
+where
+    T: crate::matrix_multiply::MatrixMultiply + Default + Copy,
+    D: MainMemory,
+    LS: Shape,
+    RS: Shape,
+    OS: Shape,
+{
+    #[inline]
+    fn gemm_tn(&self, lhs: &Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>) -> Matrix<T, Self, OS> {
+        let (k, m) = lhs.dims();
+        let n = rhs.cols();
+
+        debug_assert!(k == rhs.rows());
+
+        let mut out = self.retrieve(m * n, (lhs.node.idx, rhs.node.idx));
+        T::gemm(m, k, n, lhs, 1, m, rhs, n, 1, &mut out, n, 1);
+        (out, m, n).into()
+    }
+
+    #[inline]
+    fn gemm_nt(&self, lhs: &Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>) -> Matrix<T, Self, OS> {
+        let (m, k) = lhs.dims();
+        let n = rhs.rows();
+
+        debug_assert!(k == rhs.cols());
+
+        let mut out = self.retrieve(m * n, (lhs.node.idx, rhs.node.idx));
+        T::gemm(m, k, n, lhs, k, 1, rhs, 1, k, &mut out, n, 1);
+        (out, m, n).into()
+    }
+
+    #[inline]
+    fn gemm_tt(&self, lhs: &Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>) -> Matrix<T, Self, OS> {
+        let (k, m) = lhs.dims();
+        let n = rhs.rows();
+
+        debug_assert!(k == rhs.cols());
+
+        let mut out = self.retrieve(m * n, (lhs.node.idx, rhs.node.idx));
+        T::gemm(m, k, n, lhs, 1, m, rhs, 1, k, &mut out, n, 1);
+        (out, m, n).into()
+    }
+}
+
+#[cfg(not(feature = "matrixmultiply"))]
+#[cfg(not(feature = "blas"))]
+#[impl_stack]
+impl<T, D, LS, RS, OS> GemmTrans<T, LS, RS, OS, D> for CPU
+where
+    T: Default + Copy + core::ops::Mul<Output = T> + core::ops::AddAssign,
+    D: MainMemory,
+    LS: Shape,
+    RS: Shape,
+    OS: Shape,
+{
+    #[inline]
+    fn gemm_tn(&self, lhs: &Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>) -> Matrix<T, Self, OS> {
+        let (k, m) = lhs.dims();
+        let n = rhs.cols();
+
+        debug_assert!(k == rhs.rows());
+
+        let mut out = self.retrieve(m * n, (lhs.node.idx, rhs.node.idx));
+        crate::raw_ops::naive_gemm_trans(true, false, m, k, n, lhs, rhs, &mut out);
+        (out, m, n).into()
+    }
+
+    #[inline]
+    fn gemm_nt(&self, lhs: &Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>) -> Matrix<T, Self, OS> {
+        let (m, k) = lhs.dims();
+        let n = rhs.rows();
+
+        debug_assert!(k == rhs.cols());
+
+        let mut out = self.retrieve(m * n, (lhs.node.idx, rhs.node.idx));
+        crate::raw_ops::naive_gemm_trans(false, true, m, k, n, lhs, rhs, &mut out);
+        (out, m, n).into()
+    }
+
+    #[inline]
+    fn gemm_tt(&self, lhs: &Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>) -> Matrix<T, Self, OS> {
+        let (k, m) = lhs.dims();
+        let n = rhs.rows();
+
+        debug_assert!(k == rhs.cols());
+
+        let mut out = self.retrieve(m * n, (lhs.node.idx, rhs.node.idx));
+        crate::raw_ops::naive_gemm_trans(true, true, m, k, n, lhs, rhs, &mut out);
+        (out, m, n).into()
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: CDatatype> GemmTrans<T> for OpenCL {
+    fn gemm_tn(&self, lhs: &Matrix<T, Self>, rhs: &Matrix<T, Self>) -> Matrix<T, Self> {
+        assert!(lhs.rows() == rhs.rows());
+        let (k, m) = lhs.dims();
+
+        let buf = cl_gemm_trans(self, true, false, m, k, rhs.cols(), lhs, rhs).unwrap();
+        (buf, m, rhs.cols()).into()
+    }
+
+    fn gemm_nt(&self, lhs: &Matrix<T, Self>, rhs: &Matrix<T, Self>) -> Matrix<T, Self> {
+        assert!(lhs.cols() == rhs.cols());
+        let (m, k) = lhs.dims();
+
+        let buf = cl_gemm_trans(self, false, true, m, k, rhs.rows(), lhs, rhs).unwrap();
+        (buf, m, rhs.rows()).into()
+    }
+
+    fn gemm_tt(&self, lhs: &Matrix<T, Self>, rhs: &Matrix<T, Self>) -> Matrix<T, Self> {
+        assert!(lhs.rows() == rhs.cols());
+        let (k, m) = lhs.dims();
+
+        let buf = cl_gemm_trans(self, true, true, m, k, rhs.rows(), lhs, rhs).unwrap();
+        (buf, m, rhs.rows()).into()
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: CDatatype> GemmTrans<T> for CUDA
+where
+    CPU: GemmTrans<T>,
+{
+    #[inline]
+    fn gemm_tn(&self, lhs: &Matrix<T, Self>, rhs: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cu_to_cpu_lr(self, lhs, rhs, |cpu, lhs, rhs| cpu.gemm_tn(lhs, rhs))
+    }
+
+    #[inline]
+    fn gemm_nt(&self, lhs: &Matrix<T, Self>, rhs: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cu_to_cpu_lr(self, lhs, rhs, |cpu, lhs, rhs| cpu.gemm_nt(lhs, rhs))
+    }
+
+    #[inline]
+    fn gemm_tt(&self, lhs: &Matrix<T, Self>, rhs: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cu_to_cpu_lr(self, lhs, rhs, |cpu, lhs, rhs| cpu.gemm_tt(lhs, rhs))
+    }
+}
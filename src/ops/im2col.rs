@@ -0,0 +1,206 @@
+use crate::{col2im_mut, im2col_mut, ConvConfig, Matrix};
+use custos::{number::Number, Device, MainMemory};
+
+#[cfg(feature = "cpu")]
+use custos::CPU;
+
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+use custos::CDatatype;
+
+#[cfg(feature = "opencl")]
+use crate::{cl_col2im, cl_im2col};
+#[cfg(feature = "opencl")]
+use custos::OpenCL;
+
+#[cfg(feature = "cuda")]
+use crate::cu_to_cpu_s;
+#[cfg(feature = "cuda")]
+use custos::CUDA;
+
+impl<'a, T, D: Im2ColOps<T>> Matrix<'a, T, D> {
+    #[inline]
+    pub fn im2col(
+        &self,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<'a, T, D> {
+        self.device().im2col(self, img_dims, kernel_dims, config)
+    }
+
+    #[inline]
+    pub fn col2im(
+        &self,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<'a, T, D> {
+        self.device().col2im(self, img_dims, kernel_dims, config)
+    }
+}
+
+/// Lowers a convolution to a matrix multiplication.
+///
+/// The input of `im2col` stores one channel per row: `channels` x (`img_dims.0` * `img_dims.1`).
+/// Every kernel sized patch becomes one column of the output, which therefore has the dimensions
+/// (`channels` * `kernel_dims.0` * `kernel_dims.1`) x (`out_rows` * `out_cols`),
+/// with (`out_rows`, `out_cols`) = `config.out_dims(img_dims, kernel_dims)`.
+///
+/// Multiplying `out_channels` x (`channels` * kernel size) kernels with these columns
+/// yields the `out_channels` x (`out_rows` * `out_cols`) cross-correlation.
+/// `col2im` sums columns back into the input layout and is used for the gradient of the input.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{ConvConfig, Im2ColOps, Matrix};
+///
+/// let device = CPU::new();
+///
+/// let x = Matrix::from((&device, (1, 9), [1., 2., 3., 4., 5., 6., 7., 8., 9.,]));
+/// let kernel = Matrix::from((&device, (1, 4), [1., 0., 0., 1.,]));
+///
+/// let cols = device.im2col(&x, (3, 3), (2, 2), ConvConfig::default());
+/// assert_eq!(cols.dims(), (4, 4));
+///
+/// let out = kernel.gemm(&cols);
+/// assert_eq!(out.read(), vec![6., 8., 12., 14.]);
+/// ```
+pub trait Im2ColOps<T, D: Device = Self>: Device {
+    fn im2col(
+        &self,
+        x: &Matrix<T, D>,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T, Self>;
+
+    fn col2im(
+        &self,
+        cols: &Matrix<T, D>,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T, Self>;
+}
+
+fn im2col_dims(
+    x_dims: (usize, usize),
+    img_dims: (usize, usize),
+    kernel_dims: (usize, usize),
+    config: &ConvConfig,
+) -> (usize, usize) {
+    assert_eq!(
+        x_dims.1,
+        img_dims.0 * img_dims.1,
+        "Every row must hold one channel of the image."
+    );
+    let (out_rows, out_cols) = config.out_dims(img_dims, kernel_dims);
+    (
+        x_dims.0 * kernel_dims.0 * kernel_dims.1,
+        out_rows * out_cols,
+    )
+}
+
+fn col2im_dims(
+    cols_dims: (usize, usize),
+    img_dims: (usize, usize),
+    kernel_dims: (usize, usize),
+    config: &ConvConfig,
+) -> (usize, usize) {
+    let (out_rows, out_cols) = config.out_dims(img_dims, kernel_dims);
+    let kernel_len = kernel_dims.0 * kernel_dims.1;
+
+    assert!(
+        cols_dims.0 % kernel_len == 0 && cols_dims.1 == out_rows * out_cols,
+        "The columns do not match the image and kernel dimensions."
+    );
+    (cols_dims.0 / kernel_len, img_dims.0 * img_dims.1)
+}
+
+#[cfg(feature = "cpu")]
+impl<T: Number, D: MainMemory> Im2ColOps<T, D> for CPU {
+    fn im2col(
+        &self,
+        x: &Matrix<T, D>,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T> {
+        let dims = im2col_dims(x.dims(), img_dims, kernel_dims, &config);
+
+        let mut out = self.retrieve(dims.0 * dims.1, x.node.idx);
+        im2col_mut(x, x.rows(), img_dims, kernel_dims, &config, &mut out);
+        (out, dims).into()
+    }
+
+    fn col2im(
+        &self,
+        cols: &Matrix<T, D>,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T> {
+        let dims = col2im_dims(cols.dims(), img_dims, kernel_dims, &config);
+
+        let mut out = self.retrieve(dims.0 * dims.1, cols.node.idx);
+        col2im_mut(cols, dims.0, img_dims, kernel_dims, &config, &mut out);
+        (out, dims).into()
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: CDatatype> Im2ColOps<T> for OpenCL {
+    fn im2col(
+        &self,
+        x: &Matrix<T, Self>,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T, Self> {
+        let dims = im2col_dims(x.dims(), img_dims, kernel_dims, &config);
+        let out = cl_im2col(self, x, x.rows(), img_dims, kernel_dims, &config).unwrap();
+        (out, dims).into()
+    }
+
+    fn col2im(
+        &self,
+        cols: &Matrix<T, Self>,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T, Self> {
+        let dims = col2im_dims(cols.dims(), img_dims, kernel_dims, &config);
+        let out = cl_col2im(self, cols, dims.0, img_dims, kernel_dims, &config).unwrap();
+        (out, dims).into()
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: CDatatype> Im2ColOps<T> for CUDA {
+    #[inline]
+    fn im2col(
+        &self,
+        x: &Matrix<T, Self>,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T, Self> {
+        cu_to_cpu_s(self, x, |cpu, x| {
+            cpu.im2col(x, img_dims, kernel_dims, config)
+        })
+    }
+
+    #[inline]
+    fn col2im(
+        &self,
+        cols: &Matrix<T, Self>,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T, Self> {
+        cu_to_cpu_s(self, cols, |cpu, cols| {
+            cpu.col2im(cols, img_dims, kernel_dims, config)
+        })
+    }
+}
@@ -0,0 +1,360 @@
+use core::fmt::Display;
+
+use custos::{impl_stack, number::Float, Buffer, Device, MainMemory, Shape, CPU};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+use custos::CDatatype;
+
+#[cfg(feature = "opencl")]
+use crate::{cpu_exec_lhs_rhs_with, cpu_exec_with};
+#[cfg(feature = "opencl")]
+use custos::OpenCL;
+
+#[cfg(feature = "cuda")]
+use crate::{cu_to_cpu_lr_with, cu_to_cpu_with};
+#[cfg(feature = "cuda")]
+use custos::CUDA;
+
+use crate::{identity_slice, lu_in_place, lu_solve_in_place, pivot_tolerance, swap_rows, Matrix};
+
+/// Errors of the linear algebra operations, e.g. [`LinalgOps`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinalgError {
+    /// The operation requires a square matrix.
+    NotSquare { rows: usize, cols: usize },
+    /// The rows of the right-hand side do not match the rows of the matrix.
+    DimsMismatch {
+        lhs: (usize, usize),
+        rhs: (usize, usize),
+    },
+    /// The matrix is singular. `pivot` is the column of the elimination step that found no usable pivot.
+    Singular { pivot: usize },
+    /// The matrix is not (numerically) positive definite. `pivot` is the first diagonal element of the Cholesky factor that is not positive.
+    NotPositiveDefinite { pivot: usize },
+}
+
+impl Display for LinalgError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LinalgError::NotSquare { rows, cols } => {
+                write!(f, "Expected a square matrix, got a {rows}x{cols} matrix.")
+            }
+            LinalgError::DimsMismatch { lhs, rhs } => write!(
+                f,
+                "A {}x{} right-hand side does not fit a {}x{} matrix.",
+                rhs.0, rhs.1, lhs.0, lhs.1
+            ),
+            LinalgError::Singular { pivot } => {
+                write!(f, "The matrix is singular (zero pivot in column {pivot}).")
+            }
+            LinalgError::NotPositiveDefinite { pivot } => write!(
+                f,
+                "The matrix is not positive definite (non-positive pivot in column {pivot})."
+            ),
+        }
+    }
+}
+
+#[cfg(not(feature = "no-std"))]
+impl std::error::Error for LinalgError {}
+
+/// Returns the side length of `a` or [`LinalgError::NotSquare`].
+pub(crate) fn square_dims<T, D: Device, S: Shape>(
+    a: &Matrix<T, D, S>,
+) -> Result<usize, LinalgError> {
+    let (rows, cols) = a.dims();
+    if rows != cols {
+        return Err(LinalgError::NotSquare { rows, cols });
+    }
+    Ok(rows)
+}
+
+impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
+    /// LU decomposition with partial pivoting. See [`LinalgOps::lu`].
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    pub fn lu(
+        &self,
+    ) -> Result<
+        (
+            Matrix<'a, T, D, S>,
+            Matrix<'a, T, D, S>,
+            Matrix<'a, T, D, S>,
+        ),
+        LinalgError,
+    >
+    where
+        D: LinalgOps<T, S>,
+    {
+        self.device().lu(self)
+    }
+
+    /// Solves `self * x = b`. See [`SolveOp`].
+    #[inline]
+    pub fn solve<BS: Shape>(
+        &self,
+        b: &Matrix<T, D, BS>,
+    ) -> Result<Matrix<'a, T, D, BS>, LinalgError>
+    where
+        D: SolveOp<T, S, BS>,
+    {
+        self.device().solve(self, b)
+    }
+
+    /// The inverse of `self`. See [`LinalgOps::inv`].
+    #[inline]
+    pub fn inv(&self) -> Result<Matrix<'a, T, D, S>, LinalgError>
+    where
+        D: LinalgOps<T, S>,
+    {
+        self.device().inv(self)
+    }
+
+    /// The determinant of `self`. See [`LinalgOps::det`].
+    #[inline]
+    pub fn det(&self) -> Result<T, LinalgError>
+    where
+        D: LinalgOps<T, S>,
+    {
+        self.device().det(self)
+    }
+}
+
+/// Dense linear algebra based on the LU decomposition with partial pivoting.
+///
+/// A pivot is treated as zero if it is at most `n * eps` times the largest absolute value of its column
+/// (see [`lu_in_place`](crate::lu_in_place)). Such matrices are reported as [`LinalgError::Singular`].
+/// `det` does not use this tolerance and returns the product of the pivots instead.
+/// Linear systems are solved with [`SolveOp`].
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, LinalgOps, SolveOp};
+///
+/// let device = CPU::new();
+///
+/// let a = Matrix::from((&device, (2, 2), [4., 3., 6., 3.]));
+/// let b = Matrix::from((&device, (2, 1), [10., 12.]));
+///
+/// let x = device.solve(&a, &b).unwrap();
+/// assert_eq!(x.read(), vec![1., 2.]);
+///
+/// assert!((device.det(&a).unwrap() + 6.).abs() < 1e-12);
+///
+/// let singular = Matrix::from((&device, (2, 2), [1., 2., 2., 4.]));
+/// assert!(device.inv(&singular).is_err());
+/// ```
+pub trait LinalgOps<T, S: Shape = (), D: Device = Self>: Device {
+    /// Decomposes `a` into `(p, l, u)` with `a = p * l * u`.
+    /// `p` is a permutation matrix, `l` is unit lower triangular and `u` is upper triangular.
+    #[allow(clippy::type_complexity)]
+    fn lu(
+        &self,
+        a: &Matrix<T, D, S>,
+    ) -> Result<(Matrix<T, Self, S>, Matrix<T, Self, S>, Matrix<T, Self, S>), LinalgError>;
+
+    /// The inverse of `a`.
+    fn inv(&self, a: &Matrix<T, D, S>) -> Result<Matrix<T, Self, S>, LinalgError>;
+
+    /// The determinant of `a`, which is the product of the pivots. Returns 0 if a pivot is exactly zero.
+    fn det(&self, a: &Matrix<T, D, S>) -> Result<T, LinalgError>;
+}
+
+#[impl_stack]
+impl<T: Float, D: MainMemory, S: Shape> LinalgOps<T, S, D> for CPU {
+    fn lu(
+        &self,
+        a: &Matrix<T, D, S>,
+    ) -> Result<(Matrix<T, Self, S>, Matrix<T, Self, S>, Matrix<T, Self, S>), LinalgError> {
+        let n = square_dims(a)?;
+
+        let mut p = self.retrieve(a.len(), a.node.idx);
+        identity_slice(&mut p, n);
+
+        let mut u = self.retrieve(a.len(), a.node.idx);
+        u.copy_from_slice(a);
+
+        // a row swap of p * a is a column swap of p
+        lu_in_place(&mut u, n, pivot_tolerance(n), |k, pivot| {
+            for row in 0..n {
+                p.swap(row * n + k, row * n + pivot);
+            }
+        })?;
+
+        let mut l = self.retrieve(a.len(), a.node.idx);
+        identity_slice(&mut l, n);
+        for row in 1..n {
+            for col in 0..row {
+                l[row * n + col] = u[row * n + col];
+                u[row * n + col] = T::default();
+            }
+        }
+
+        Ok((
+            (p, a.dims()).into(),
+            (l, a.dims()).into(),
+            (u, a.dims()).into(),
+        ))
+    }
+
+    fn inv(&self, a: &Matrix<T, D, S>) -> Result<Matrix<T, Self, S>, LinalgError> {
+        let n = square_dims(a)?;
+
+        let mut lu: Buffer<T, Self, S> = self.retrieve(a.len(), a.node.idx);
+        lu.copy_from_slice(a);
+
+        let mut inv = self.retrieve(a.len(), a.node.idx);
+        identity_slice(&mut inv, n);
+
+        lu_in_place(&mut lu, n, pivot_tolerance(n), |k, pivot| {
+            swap_rows(&mut inv, n, k, pivot)
+        })?;
+        lu_solve_in_place(&lu, n, &mut inv, n);
+
+        Ok((inv, a.dims()).into())
+    }
+
+    fn det(&self, a: &Matrix<T, D, S>) -> Result<T, LinalgError> {
+        let n = square_dims(a)?;
+
+        let mut lu: Buffer<T, Self, S> = self.retrieve(a.len(), a.node.idx);
+        lu.copy_from_slice(a);
+
+        // the determinant is the product of the pivots, therefore only exactly zero pivots stop the factorisation
+        let mut negate = false;
+        match lu_in_place(&mut lu, n, T::default(), |_, _| negate = !negate) {
+            Ok(()) => (),
+            // the remaining column is zero
+            Err(LinalgError::Singular { pivot })
+                if (pivot..n).all(|row| lu[row * n + pivot] == T::default()) =>
+            {
+                return Ok(T::default())
+            }
+            Err(err) => return Err(err),
+        }
+
+        let det = (0..n).fold(T::one(), |det, k| det * lu[k * n + k]);
+        Ok(if negate { T::default() - det } else { det })
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: Float + CDatatype> LinalgOps<T> for OpenCL {
+    fn lu(
+        &self,
+        a: &Matrix<T, Self>,
+    ) -> Result<(Matrix<T, Self>, Matrix<T, Self>, Matrix<T, Self>), LinalgError> {
+        cpu_exec_with(self, a, |cpu, a| {
+            let (p, l, u) = cpu.lu(a)?;
+            Ok((
+                Matrix::from((self, p)),
+                Matrix::from((self, l)),
+                Matrix::from((self, u)),
+            ))
+        })
+    }
+
+    fn inv(&self, a: &Matrix<T, Self>) -> Result<Matrix<T, Self>, LinalgError> {
+        cpu_exec_with(self, a, |cpu, a| Ok(Matrix::from((self, cpu.inv(a)?))))
+    }
+
+    fn det(&self, a: &Matrix<T, Self>) -> Result<T, LinalgError> {
+        cpu_exec_with(self, a, |cpu, a| cpu.det(a))
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: Float + CDatatype> LinalgOps<T> for CUDA {
+    fn lu(
+        &self,
+        a: &Matrix<T, Self>,
+    ) -> Result<(Matrix<T, Self>, Matrix<T, Self>, Matrix<T, Self>), LinalgError> {
+        cu_to_cpu_with(a, |cpu, a| {
+            let (p, l, u) = cpu.lu(a)?;
+            Ok((
+                Matrix::from((self, p)),
+                Matrix::from((self, l)),
+                Matrix::from((self, u)),
+            ))
+        })
+    }
+
+    fn inv(&self, a: &Matrix<T, Self>) -> Result<Matrix<T, Self>, LinalgError> {
+        cu_to_cpu_with(a, |cpu, a| Ok(Matrix::from((self, cpu.inv(a)?))))
+    }
+
+    fn det(&self, a: &Matrix<T, Self>) -> Result<T, LinalgError> {
+        cu_to_cpu_with(a, |cpu, a| cpu.det(a))
+    }
+}
+
+/// Solves the linear system `a * x = b` for every column of `b` with the LU decomposition of [`LinalgOps`].
+/// `BS` is the shape of the right-hand side `b` and the solution `x`.
+/// # Errors
+/// [`LinalgError::Singular`] if `a` is singular, [`LinalgError::DimsMismatch`] if `b` does not have as many rows as `a`.
+pub trait SolveOp<T, S: Shape = (), BS: Shape = (), D: Device = Self>: Device {
+    fn solve(
+        &self,
+        a: &Matrix<T, D, S>,
+        b: &Matrix<T, D, BS>,
+    ) -> Result<Matrix<T, Self, BS>, LinalgError>;
+}
+
+#[impl_stack]
+impl<T: Float, D: MainMemory, S: Shape, BS: Shape> SolveOp<T, S, BS, D> for CPU {
+    fn solve(
+        &self,
+        a: &Matrix<T, D, S>,
+        b: &Matrix<T, D, BS>,
+    ) -> Result<Matrix<T, Self, BS>, LinalgError> {
+        let n = square_dims(a)?;
+        if b.rows() != n {
+            return Err(LinalgError::DimsMismatch {
+                lhs: a.dims(),
+                rhs: b.dims(),
+            });
+        }
+
+        let mut lu: Buffer<T, Self, S> = self.retrieve(a.len(), a.node.idx);
+        lu.copy_from_slice(a);
+
+        let mut x = self.retrieve(b.len(), (a.node.idx, b.node.idx));
+        x.copy_from_slice(b);
+
+        let nrhs = b.cols();
+        lu_in_place(&mut lu, n, pivot_tolerance(n), |k, pivot| {
+            swap_rows(&mut x, nrhs, k, pivot)
+        })?;
+        lu_solve_in_place(&lu, n, &mut x, nrhs);
+
+        Ok((x, b.dims()).into())
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: Float + CDatatype> SolveOp<T> for OpenCL {
+    fn solve(
+        &self,
+        a: &Matrix<T, Self>,
+        b: &Matrix<T, Self>,
+    ) -> Result<Matrix<T, Self>, LinalgError> {
+        cpu_exec_lhs_rhs_with(self, a, b, |cpu, a, b| {
+            Ok(Matrix::from((self, cpu.solve(a, b)?)))
+        })
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: Float + CDatatype> SolveOp<T> for CUDA {
+    fn solve(
+        &self,
+        a: &Matrix<T, Self>,
+        b: &Matrix<T, Self>,
+    ) -> Result<Matrix<T, Self>, LinalgError> {
+        cu_to_cpu_lr_with(a, b, |cpu, a, b| Ok(Matrix::from((self, cpu.solve(a, b)?))))
+    }
+}
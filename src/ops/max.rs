@@ -1,5 +1,10 @@
 use crate::Matrix;
-use custos::{number::Number, CDatatype, Device, MainMemory, CPU};
+use custos::{number::Number, CDatatype, Device, MainMemory, Shape, CPU};
+
+#[cfg(feature = "stack")]
+use crate::{reduce_iter, reduce_op, Axis, Reduce};
+#[cfg(feature = "stack")]
+use custos::Stack;
 
 #[cfg(feature = "cpu")]
 use custos::cache::Cache;
@@ -31,16 +36,16 @@ impl<'a, T, D: MaxOps<T>> Matrix<'a, T, D> {
     }
 }
 
-pub trait MaxOps<T, D: Device = Self>: Device {
-    fn max(&self, x: &Matrix<T, D>) -> T;
-    fn max_rows(&self, x: &Matrix<T, D>) -> Matrix<T, Self>;
-    fn max_cols(&self, x: &Matrix<T, D>) -> Matrix<T, Self>;
+pub trait MaxOps<T, IS: Shape = (), OS: Shape = (), D: Device = Self>: Device {
+    fn max(&self, x: &Matrix<T, D, IS>) -> T;
+    fn max_rows(&self, x: &Matrix<T, D, IS>) -> Matrix<T, Self, OS>;
+    fn max_cols(&self, x: &Matrix<T, D, IS>) -> Matrix<T, Self, OS>;
 }
 
 // TODO: refactor this into own methods
 #[cfg(feature = "cpu")]
-impl<T: Copy + PartialOrd, D: MainMemory> MaxOps<T, D> for CPU {
-    fn max(&self, x: &Matrix<T, D>) -> T {
+impl<T: Copy + PartialOrd, D: MainMemory, IS: Shape> MaxOps<T, IS, (), D> for CPU {
+    fn max(&self, x: &Matrix<T, D, IS>) -> T {
         let mut max = x[0];
 
         for value in x.iter() {
@@ -51,7 +56,7 @@ impl<T: Copy + PartialOrd, D: MainMemory> MaxOps<T, D> for CPU {
         max
     }
 
-    fn max_rows(&self, x: &Matrix<T, D>) -> Matrix<T> {
+    fn max_rows(&self, x: &Matrix<T, D, IS>) -> Matrix<T> {
         let mut out = Cache::get(self, x.cols(), x.node.idx);
 
         let data = x.as_slice();
@@ -72,7 +77,7 @@ impl<T: Copy + PartialOrd, D: MainMemory> MaxOps<T, D> for CPU {
         (out, 1, x.cols()).into()
     }
 
-    fn max_cols(&self, x: &Matrix<T, D>) -> Matrix<T> {
+    fn max_cols(&self, x: &Matrix<T, D, IS>) -> Matrix<T> {
         let data = x.as_slice();
         let mut y = Cache::get(self, x.rows(), x.node.idx);
 
@@ -95,6 +100,24 @@ impl<T: Copy + PartialOrd, D: MainMemory> MaxOps<T, D> for CPU {
     }
 }
 
+#[cfg(feature = "stack")]
+impl<T: Number, D: MainMemory, IS: Shape, OS: Shape> MaxOps<T, IS, OS, D> for Stack {
+    #[inline]
+    fn max(&self, x: &Matrix<T, D, IS>) -> T {
+        reduce_iter(x.iter().copied(), Reduce::Max)
+    }
+
+    #[inline]
+    fn max_rows(&self, x: &Matrix<T, D, IS>) -> Matrix<T, Self, OS> {
+        reduce_op(self, x, Axis::Rows, Reduce::Max)
+    }
+
+    #[inline]
+    fn max_cols(&self, x: &Matrix<T, D, IS>) -> Matrix<T, Self, OS> {
+        reduce_op(self, x, Axis::Cols, Reduce::Max)
+    }
+}
+
 #[cfg(feature = "opencl")]
 impl<T: CDatatype> MaxOps<T> for OpenCL {
     fn max(&self, x: &Matrix<T, Self>) -> T {
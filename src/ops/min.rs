@@ -0,0 +1,139 @@
+use crate::{reduce_iter, reduce_op, Axis, Matrix, Reduce};
+use custos::{number::Number, Device, MainMemory, Shape};
+
+#[cfg(feature = "cpu")]
+use custos::CPU;
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[cfg(feature = "cuda")]
+use crate::{cu_to_cpu_s, cu_to_cpu_scalar};
+#[cfg(feature = "cuda")]
+use custos::CUDA;
+
+#[cfg(feature = "opencl")]
+use super::{cl_to_cpu_s, cl_to_cpu_scalar};
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+use custos::CDatatype;
+#[cfg(feature = "opencl")]
+use custos::OpenCL;
+
+impl<'a, T, D: Device, IS: Shape> Matrix<'a, T, D, IS> {
+    #[inline]
+    pub fn min(&self) -> T
+    where
+        D: MinOps<T, IS>,
+    {
+        self.device().min(self)
+    }
+
+    #[inline]
+    pub fn min_rows<OS: Shape>(&self) -> Matrix<'a, T, D, OS>
+    where
+        D: MinOps<T, IS, OS>,
+    {
+        self.device().min_rows(self)
+    }
+
+    #[inline]
+    pub fn min_cols<OS: Shape>(&self) -> Matrix<'a, T, D, OS>
+    where
+        D: MinOps<T, IS, OS>,
+    {
+        self.device().min_cols(self)
+    }
+}
+
+/// Minimum of all values, of every column (`min_rows`, rows x cols -> 1 x cols)
+/// or of every row (`min_cols`, rows x cols -> rows x 1).
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, MinOps};
+///
+/// let device = CPU::new();
+/// let x = Matrix::from((&device, (2, 3), [3., -1., 2., 4., 5., -6.,]));
+///
+/// assert_eq!(device.min(&x), -6.);
+/// assert_eq!(device.min_rows(&x).read(), vec![3., -1., -6.]);
+/// assert_eq!(device.min_cols(&x).read(), vec![-1., -6.]);
+/// ```
+pub trait MinOps<T, IS: Shape = (), OS: Shape = (), D: Device = Self>: Device {
+    fn min(&self, x: &Matrix<T, D, IS>) -> T;
+    fn min_rows(&self, x: &Matrix<T, D, IS>) -> Matrix<T, Self, OS>;
+    fn min_cols(&self, x: &Matrix<T, D, IS>) -> Matrix<T, Self, OS>;
+}
+
+#[cfg(feature = "cpu")]
+impl<T: Number, D: MainMemory, IS: Shape> MinOps<T, IS, (), D> for CPU {
+    #[inline]
+    fn min(&self, x: &Matrix<T, D, IS>) -> T {
+        reduce_iter(x.iter().copied(), Reduce::Min)
+    }
+
+    #[inline]
+    fn min_rows(&self, x: &Matrix<T, D, IS>) -> Matrix<T> {
+        reduce_op(self, x, Axis::Rows, Reduce::Min)
+    }
+
+    #[inline]
+    fn min_cols(&self, x: &Matrix<T, D, IS>) -> Matrix<T> {
+        reduce_op(self, x, Axis::Cols, Reduce::Min)
+    }
+}
+
+#[cfg(feature = "stack")]
+impl<T: Number, D: MainMemory, IS: Shape, OS: Shape> MinOps<T, IS, OS, D> for Stack {
+    #[inline]
+    fn min(&self, x: &Matrix<T, D, IS>) -> T {
+        reduce_iter(x.iter().copied(), Reduce::Min)
+    }
+
+    #[inline]
+    fn min_rows(&self, x: &Matrix<T, D, IS>) -> Matrix<T, Self, OS> {
+        reduce_op(self, x, Axis::Rows, Reduce::Min)
+    }
+
+    #[inline]
+    fn min_cols(&self, x: &Matrix<T, D, IS>) -> Matrix<T, Self, OS> {
+        reduce_op(self, x, Axis::Cols, Reduce::Min)
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: CDatatype> MinOps<T> for OpenCL {
+    #[inline]
+    fn min(&self, x: &Matrix<T, Self>) -> T {
+        cl_to_cpu_scalar(self, x, |device, x| device.min(x))
+    }
+
+    #[inline]
+    fn min_rows(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cl_to_cpu_s(self, x, |device, x| device.min_rows(x))
+    }
+
+    #[inline]
+    fn min_cols(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cl_to_cpu_s(self, x, |device, x| device.min_cols(x))
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: CDatatype> MinOps<T> for CUDA {
+    #[inline]
+    fn min(&self, x: &Matrix<T, CUDA>) -> T {
+        cu_to_cpu_scalar(x, |cpu, x| cpu.min(&x))
+    }
+
+    #[inline]
+    fn min_rows(&self, x: &Matrix<T, CUDA>) -> Matrix<T, CUDA> {
+        cu_to_cpu_s(self, x, |cpu, x| cpu.min_rows(x))
+    }
+
+    #[inline]
+    fn min_cols(&self, x: &Matrix<T, CUDA>) -> Matrix<T, CUDA> {
+        cu_to_cpu_s(self, x, |cpu, x| cpu.min_cols(x))
+    }
+}
@@ -1,36 +1,66 @@
 pub mod nn;
 
+mod argmax;
 mod arithmetic;
 mod assign;
+mod batched_gemm;
+mod cholesky;
 mod clip;
 mod col_op;
+mod conv;
 mod diagflat;
+mod eigh;
 mod fns;
 mod gemm;
+mod gemm_into;
+mod gemm_trans;
+mod im2col;
+mod linalg;
 mod max;
+mod min;
+mod norm;
+mod pool;
+mod qr;
+mod reduce;
 mod row_op;
 mod scalar;
 mod scalar_assign;
 mod slice;
 mod sum;
+mod svd;
 mod transpose;
 
 #[cfg(feature = "fastrand")]
 mod random;
 
+pub use argmax::*;
 pub use arithmetic::*;
 pub use assign::*;
+pub use batched_gemm::*;
+pub use cholesky::*;
 pub use clip::*;
 pub use col_op::*;
+pub use conv::*;
 pub use diagflat::*;
+pub use eigh::*;
 pub use fns::*;
 pub use gemm::*;
+pub use gemm_into::*;
+pub use gemm_trans::*;
+pub use im2col::*;
+pub use linalg::*;
 pub use max::*;
+pub use min::*;
+pub use norm::*;
+pub use pool::*;
+pub use qr::*;
+pub use reduce::*;
 pub use row_op::*;
 pub use scalar::*;
 pub use scalar_assign::*;
 pub use slice::*;
 pub use sum::*;
+pub use svd::*;
 pub use transpose::*;
 
 #[cfg(feature = "fastrand")]
@@ -85,3 +115,18 @@ fn cl_to_cpu_scalar<T: Default + Copy, F: Fn(&CPU, &Matrix<T>) -> T>(
     use crate::opencl::cpu_exec_scalar;
     cpu_exec_scalar(device, x, f)
 }
+
+#[cfg(feature = "opencl")]
+///OpenCL
+fn cl_to_cpu_idx<'o, T, F>(
+    device: &'o OpenCL,
+    x: &Matrix<T, OpenCL>,
+    f: F,
+) -> Matrix<'o, u32, OpenCL>
+where
+    T: Copy + Default,
+    F: for<'b> Fn(&'b CPU, &Matrix<T>) -> Matrix<'b, u32>,
+{
+    use crate::opencl::cpu_exec_with;
+    cpu_exec_with(device, x, |cpu, x| Matrix::from((device, f(cpu, x))))
+}
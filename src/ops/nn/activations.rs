@@ -8,12 +8,15 @@ use custos::CPU;
 use custos::Stack;
 
 #[cfg(feature = "opencl")]
-use crate::{cl_str_op_mut, opencl::cl_str_op_mat};
+use crate::{
+    cl_str_op_mut, cl_str_param_op_mut,
+    opencl::{cl_str_op_mat, cl_str_param_op_mat},
+};
 #[cfg(feature = "opencl")]
 use custos::OpenCL;
 
 #[cfg(feature = "cuda")]
-use crate::{cu_str_op, cu_str_op_mut};
+use crate::{cu_str_op, cu_str_op_mut, cu_str_param_op, cu_str_param_op_mut};
 #[cfg(feature = "cuda")]
 use custos::CUDA;
 
@@ -58,6 +61,141 @@ impl<'a, T, D: ActivationOps<T, S>, S: Shape> Matrix<'a, T, D, S> {
     pub fn sigmoid_grad(&self) -> Matrix<'a, T, D, S> {
         self.device().sigmoid_grad(self)
     }
+
+    #[inline]
+    pub fn leaky_relu(&self, alpha: T) -> Matrix<'a, T, D, S> {
+        self.device().leaky_relu(self, alpha)
+    }
+
+    #[inline]
+    pub fn leaky_relu_mut(&mut self, alpha: T) {
+        self.device().leaky_relu_mut(self, alpha)
+    }
+
+    #[inline]
+    pub fn leaky_relu_grad(&self, alpha: T) -> Matrix<'a, T, D, S> {
+        self.device().leaky_relu_grad(self, alpha)
+    }
+
+    #[inline]
+    pub fn elu(&self, alpha: T) -> Matrix<'a, T, D, S> {
+        self.device().elu(self, alpha)
+    }
+
+    #[inline]
+    pub fn elu_mut(&mut self, alpha: T) {
+        self.device().elu_mut(self, alpha)
+    }
+
+    #[inline]
+    pub fn elu_grad(&self, alpha: T) -> Matrix<'a, T, D, S> {
+        self.device().elu_grad(self, alpha)
+    }
+
+    #[inline]
+    pub fn selu(&self) -> Matrix<'a, T, D, S> {
+        self.device().selu(self)
+    }
+
+    #[inline]
+    pub fn selu_mut(&mut self) {
+        self.device().selu_mut(self)
+    }
+
+    #[inline]
+    pub fn selu_grad(&self) -> Matrix<'a, T, D, S> {
+        self.device().selu_grad(self)
+    }
+
+    #[inline]
+    pub fn gelu(&self) -> Matrix<'a, T, D, S> {
+        self.device().gelu(self)
+    }
+
+    #[inline]
+    pub fn gelu_mut(&mut self) {
+        self.device().gelu_mut(self)
+    }
+
+    #[inline]
+    pub fn gelu_grad(&self) -> Matrix<'a, T, D, S> {
+        self.device().gelu_grad(self)
+    }
+
+    #[inline]
+    pub fn gelu_tanh(&self) -> Matrix<'a, T, D, S> {
+        self.device().gelu_tanh(self)
+    }
+
+    #[inline]
+    pub fn gelu_tanh_mut(&mut self) {
+        self.device().gelu_tanh_mut(self)
+    }
+
+    #[inline]
+    pub fn gelu_tanh_grad(&self) -> Matrix<'a, T, D, S> {
+        self.device().gelu_tanh_grad(self)
+    }
+
+    #[inline]
+    pub fn silu(&self) -> Matrix<'a, T, D, S> {
+        self.device().silu(self)
+    }
+
+    #[inline]
+    pub fn silu_mut(&mut self) {
+        self.device().silu_mut(self)
+    }
+
+    #[inline]
+    pub fn silu_grad(&self) -> Matrix<'a, T, D, S> {
+        self.device().silu_grad(self)
+    }
+
+    #[inline]
+    pub fn softplus(&self) -> Matrix<'a, T, D, S> {
+        self.device().softplus(self)
+    }
+
+    #[inline]
+    pub fn softplus_mut(&mut self) {
+        self.device().softplus_mut(self)
+    }
+
+    #[inline]
+    pub fn softplus_grad(&self) -> Matrix<'a, T, D, S> {
+        self.device().softplus_grad(self)
+    }
+
+    #[inline]
+    pub fn mish(&self) -> Matrix<'a, T, D, S> {
+        self.device().mish(self)
+    }
+
+    #[inline]
+    pub fn mish_mut(&mut self) {
+        self.device().mish_mut(self)
+    }
+
+    #[inline]
+    pub fn mish_grad(&self) -> Matrix<'a, T, D, S> {
+        self.device().mish_grad(self)
+    }
+
+    #[inline]
+    pub fn hard_sigmoid(&self) -> Matrix<'a, T, D, S> {
+        self.device().hard_sigmoid(self)
+    }
+
+    #[inline]
+    pub fn hard_sigmoid_mut(&mut self) {
+        self.device().hard_sigmoid_mut(self)
+    }
+
+    #[inline]
+    pub fn hard_sigmoid_grad(&self) -> Matrix<'a, T, D, S> {
+        self.device().hard_sigmoid_grad(self)
+    }
 }
 
 pub trait ActivationOps<T, S: Shape = (), D: Device = Self>: Device {
@@ -71,8 +209,90 @@ pub trait ActivationOps<T, S: Shape = (), D: Device = Self>: Device {
     fn relu_grad(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
     /// inplace
     fn relu_grad_mut(&self, x: &mut Matrix<T, D, S>);
+
+    // The `_grad` functions below expect the input of the activation, not its output.
+    /// `x` if `x >= 0`, otherwise `alpha * x`
+    fn leaky_relu(&self, x: &Matrix<T, D, S>, alpha: T) -> Matrix<T, Self, S>;
+    /// inplace
+    fn leaky_relu_mut(&self, x: &mut Matrix<T, D, S>, alpha: T);
+    fn leaky_relu_grad(&self, x: &Matrix<T, D, S>, alpha: T) -> Matrix<T, Self, S>;
+    /// `x` if `x >= 0`, otherwise `alpha * (exp(x) - 1)`
+    fn elu(&self, x: &Matrix<T, D, S>, alpha: T) -> Matrix<T, Self, S>;
+    /// inplace
+    fn elu_mut(&self, x: &mut Matrix<T, D, S>, alpha: T);
+    fn elu_grad(&self, x: &Matrix<T, D, S>, alpha: T) -> Matrix<T, Self, S>;
+    /// Scaled elu with the fixed `alpha` and `scale` of the self-normalizing networks paper
+    fn selu(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
+    /// inplace
+    fn selu_mut(&self, x: &mut Matrix<T, D, S>);
+    fn selu_grad(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
+    /// `0.5 * x * (1 + erf(x / sqrt(2)))`
+    fn gelu(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
+    /// inplace
+    fn gelu_mut(&self, x: &mut Matrix<T, D, S>);
+    fn gelu_grad(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
+    /// Tanh approximation of gelu: `0.5 * x * (1 + tanh(sqrt(2 / pi) * (x + 0.044715 * x^3)))`
+    fn gelu_tanh(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
+    /// inplace
+    fn gelu_tanh_mut(&self, x: &mut Matrix<T, D, S>);
+    fn gelu_tanh_grad(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
+    /// `x * sigmoid(x)`, also known as swish
+    fn silu(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
+    /// inplace
+    fn silu_mut(&self, x: &mut Matrix<T, D, S>);
+    fn silu_grad(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
+    /// `ln(1 + exp(x))`
+    fn softplus(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
+    /// inplace
+    fn softplus_mut(&self, x: &mut Matrix<T, D, S>);
+    fn softplus_grad(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
+    /// `x * tanh(softplus(x))`
+    fn mish(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
+    /// inplace
+    fn mish_mut(&self, x: &mut Matrix<T, D, S>);
+    fn mish_grad(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
+    /// `clamp(x / 6 + 0.5, 0, 1)`
+    fn hard_sigmoid(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
+    /// inplace
+    fn hard_sigmoid_mut(&self, x: &mut Matrix<T, D, S>);
+    fn hard_sigmoid_grad(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
 }
 
+// Kernel sources of the OpenCL and CUDA activations. `x` is the input value.
+#[cfg(any(feature = "opencl", feature = "cuda"))]
+const SELU: &str = "1.0507009873554805 * (x >= 0 ? x : 1.6732632423543772 * (exp(x) - 1.0))";
+#[cfg(any(feature = "opencl", feature = "cuda"))]
+const SELU_GRAD: &str = "1.0507009873554805 * (x >= 0 ? 1.0 : 1.6732632423543772 * exp(x))";
+#[cfg(any(feature = "opencl", feature = "cuda"))]
+const GELU: &str = "0.5 * x * (1.0 + erf(x * 0.7071067811865476))";
+#[cfg(any(feature = "opencl", feature = "cuda"))]
+const GELU_GRAD: &str =
+    "0.5 * (1.0 + erf(x * 0.7071067811865476)) + x * exp(-0.5 * x * x) * 0.3989422804014327";
+#[cfg(any(feature = "opencl", feature = "cuda"))]
+const GELU_TANH: &str = "0.5 * x * (1.0 + tanh(0.7978845608028654 * (x + 0.044715 * x * x * x)))";
+#[cfg(any(feature = "opencl", feature = "cuda"))]
+const GELU_TANH_GRAD: &str = "0.5 * (1.0 + tanh(0.7978845608028654 * (x + 0.044715 * x * x * x)))
+    + 0.5 * x * (1.0 - tanh(0.7978845608028654 * (x + 0.044715 * x * x * x)) * tanh(0.7978845608028654 * (x + 0.044715 * x * x * x)))
+    * 0.7978845608028654 * (1.0 + 0.134145 * x * x)";
+#[cfg(any(feature = "opencl", feature = "cuda"))]
+const SILU: &str = "x / (1.0 + exp(-x))";
+#[cfg(any(feature = "opencl", feature = "cuda"))]
+const SILU_GRAD: &str = "(1.0 + x * (1.0 - 1.0 / (1.0 + exp(-x)))) / (1.0 + exp(-x))";
+#[cfg(any(feature = "opencl", feature = "cuda"))]
+const SOFTPLUS: &str = "(x > 0 ? x : 0) + log1p(exp(-fabs(x)))";
+#[cfg(any(feature = "opencl", feature = "cuda"))]
+const SOFTPLUS_GRAD: &str = "1.0 / (1.0 + exp(-x))";
+#[cfg(any(feature = "opencl", feature = "cuda"))]
+const MISH: &str = "x * tanh((x > 0 ? x : 0) + log1p(exp(-fabs(x))))";
+#[cfg(any(feature = "opencl", feature = "cuda"))]
+const MISH_GRAD: &str = "tanh((x > 0 ? x : 0) + log1p(exp(-fabs(x))))
+    + x * (1.0 - tanh((x > 0 ? x : 0) + log1p(exp(-fabs(x)))) * tanh((x > 0 ? x : 0) + log1p(exp(-fabs(x)))))
+    / (1.0 + exp(-x))";
+#[cfg(any(feature = "opencl", feature = "cuda"))]
+const HARD_SIGMOID: &str = "(x <= -3 ? 0.0 : (x >= 3 ? 1.0 : x / 6.0 + 0.5))";
+#[cfg(any(feature = "opencl", feature = "cuda"))]
+const HARD_SIGMOID_GRAD: &str = "(x > -3 && x < 3 ? 1.0 / 6.0 : 0.0)";
+
 #[cfg(feature = "opencl")]
 impl<T: CDatatype + Float> ActivationOps<T> for OpenCL {
     #[inline]
@@ -113,6 +333,141 @@ impl<T: CDatatype + Float> ActivationOps<T> for OpenCL {
     fn relu_grad_mut(&self, x: &mut Matrix<T, Self, ()>) {
         cl_str_op_mut(self, x, "(x >= 0)").unwrap()
     }
+
+    #[inline]
+    fn leaky_relu(&self, x: &Matrix<T, Self>, alpha: T) -> Matrix<T, Self> {
+        cl_str_param_op_mat(self, x, alpha, "(x >= 0 ? x : p * x)").unwrap()
+    }
+
+    #[inline]
+    fn leaky_relu_mut(&self, x: &mut Matrix<T, Self, ()>, alpha: T) {
+        cl_str_param_op_mut(self, x, alpha, "(x >= 0 ? x : p * x)").unwrap();
+    }
+
+    #[inline]
+    fn leaky_relu_grad(&self, x: &Matrix<T, Self>, alpha: T) -> Matrix<T, Self> {
+        cl_str_param_op_mat(self, x, alpha, "(x >= 0 ? 1 : p)").unwrap()
+    }
+
+    #[inline]
+    fn elu(&self, x: &Matrix<T, Self>, alpha: T) -> Matrix<T, Self> {
+        cl_str_param_op_mat(self, x, alpha, "(x >= 0 ? x : p * (exp(x) - 1.0))").unwrap()
+    }
+
+    #[inline]
+    fn elu_mut(&self, x: &mut Matrix<T, Self, ()>, alpha: T) {
+        cl_str_param_op_mut(self, x, alpha, "(x >= 0 ? x : p * (exp(x) - 1.0))").unwrap();
+    }
+
+    #[inline]
+    fn elu_grad(&self, x: &Matrix<T, Self>, alpha: T) -> Matrix<T, Self> {
+        cl_str_param_op_mat(self, x, alpha, "(x >= 0 ? 1.0 : p * exp(x))").unwrap()
+    }
+
+    #[inline]
+    fn selu(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cl_str_op_mat(self, x, SELU).unwrap()
+    }
+
+    #[inline]
+    fn selu_mut(&self, x: &mut Matrix<T, Self, ()>) {
+        cl_str_op_mut(self, x, SELU).unwrap();
+    }
+
+    #[inline]
+    fn selu_grad(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cl_str_op_mat(self, x, SELU_GRAD).unwrap()
+    }
+
+    #[inline]
+    fn gelu(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cl_str_op_mat(self, x, GELU).unwrap()
+    }
+
+    #[inline]
+    fn gelu_mut(&self, x: &mut Matrix<T, Self, ()>) {
+        cl_str_op_mut(self, x, GELU).unwrap();
+    }
+
+    #[inline]
+    fn gelu_grad(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cl_str_op_mat(self, x, GELU_GRAD).unwrap()
+    }
+
+    #[inline]
+    fn gelu_tanh(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cl_str_op_mat(self, x, GELU_TANH).unwrap()
+    }
+
+    #[inline]
+    fn gelu_tanh_mut(&self, x: &mut Matrix<T, Self, ()>) {
+        cl_str_op_mut(self, x, GELU_TANH).unwrap();
+    }
+
+    #[inline]
+    fn gelu_tanh_grad(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cl_str_op_mat(self, x, GELU_TANH_GRAD).unwrap()
+    }
+
+    #[inline]
+    fn silu(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cl_str_op_mat(self, x, SILU).unwrap()
+    }
+
+    #[inline]
+    fn silu_mut(&self, x: &mut Matrix<T, Self, ()>) {
+        cl_str_op_mut(self, x, SILU).unwrap();
+    }
+
+    #[inline]
+    fn silu_grad(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cl_str_op_mat(self, x, SILU_GRAD).unwrap()
+    }
+
+    #[inline]
+    fn softplus(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cl_str_op_mat(self, x, SOFTPLUS).unwrap()
+    }
+
+    #[inline]
+    fn softplus_mut(&self, x: &mut Matrix<T, Self, ()>) {
+        cl_str_op_mut(self, x, SOFTPLUS).unwrap();
+    }
+
+    #[inline]
+    fn softplus_grad(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cl_str_op_mat(self, x, SOFTPLUS_GRAD).unwrap()
+    }
+
+    #[inline]
+    fn mish(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cl_str_op_mat(self, x, MISH).unwrap()
+    }
+
+    #[inline]
+    fn mish_mut(&self, x: &mut Matrix<T, Self, ()>) {
+        cl_str_op_mut(self, x, MISH).unwrap();
+    }
+
+    #[inline]
+    fn mish_grad(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cl_str_op_mat(self, x, MISH_GRAD).unwrap()
+    }
+
+    #[inline]
+    fn hard_sigmoid(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cl_str_op_mat(self, x, HARD_SIGMOID).unwrap()
+    }
+
+    #[inline]
+    fn hard_sigmoid_mut(&self, x: &mut Matrix<T, Self, ()>) {
+        cl_str_op_mut(self, x, HARD_SIGMOID).unwrap();
+    }
+
+    #[inline]
+    fn hard_sigmoid_grad(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cl_str_op_mat(self, x, HARD_SIGMOID_GRAD).unwrap()
+    }
 }
 
 #[impl_stack]
@@ -155,6 +510,141 @@ impl<T: Float, D: MainMemory, S: Shape> ActivationOps<T, S, D> for CPU {
     fn relu_grad_mut(&self, x: &mut Matrix<T, D, S>) {
         each_op_slice_mut(x, |x| T::from_usize((x >= T::default()) as usize))
     }
+
+    #[inline]
+    fn leaky_relu(&self, x: &Matrix<T, D, S>, alpha: T) -> Matrix<T, Self, S> {
+        each_op(self, x, |x| leaky_relu(x, alpha))
+    }
+
+    #[inline]
+    fn leaky_relu_mut(&self, x: &mut Matrix<T, D, S>, alpha: T) {
+        each_op_slice_mut(x, |x| leaky_relu(x, alpha))
+    }
+
+    #[inline]
+    fn leaky_relu_grad(&self, x: &Matrix<T, D, S>, alpha: T) -> Matrix<T, Self, S> {
+        each_op(self, x, |x| leaky_relu_grad(x, alpha))
+    }
+
+    #[inline]
+    fn elu(&self, x: &Matrix<T, D, S>, alpha: T) -> Matrix<T, Self, S> {
+        each_op(self, x, |x| elu(x, alpha))
+    }
+
+    #[inline]
+    fn elu_mut(&self, x: &mut Matrix<T, D, S>, alpha: T) {
+        each_op_slice_mut(x, |x| elu(x, alpha))
+    }
+
+    #[inline]
+    fn elu_grad(&self, x: &Matrix<T, D, S>, alpha: T) -> Matrix<T, Self, S> {
+        each_op(self, x, |x| elu_grad(x, alpha))
+    }
+
+    #[inline]
+    fn selu(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S> {
+        each_op(self, x, |x| selu(x))
+    }
+
+    #[inline]
+    fn selu_mut(&self, x: &mut Matrix<T, D, S>) {
+        each_op_slice_mut(x, |x| selu(x))
+    }
+
+    #[inline]
+    fn selu_grad(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S> {
+        each_op(self, x, |x| selu_grad(x))
+    }
+
+    #[inline]
+    fn gelu(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S> {
+        each_op(self, x, |x| gelu(x))
+    }
+
+    #[inline]
+    fn gelu_mut(&self, x: &mut Matrix<T, D, S>) {
+        each_op_slice_mut(x, |x| gelu(x))
+    }
+
+    #[inline]
+    fn gelu_grad(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S> {
+        each_op(self, x, |x| gelu_grad(x))
+    }
+
+    #[inline]
+    fn gelu_tanh(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S> {
+        each_op(self, x, |x| gelu_tanh(x))
+    }
+
+    #[inline]
+    fn gelu_tanh_mut(&self, x: &mut Matrix<T, D, S>) {
+        each_op_slice_mut(x, |x| gelu_tanh(x))
+    }
+
+    #[inline]
+    fn gelu_tanh_grad(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S> {
+        each_op(self, x, |x| gelu_tanh_grad(x))
+    }
+
+    #[inline]
+    fn silu(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S> {
+        each_op(self, x, |x| silu(x))
+    }
+
+    #[inline]
+    fn silu_mut(&self, x: &mut Matrix<T, D, S>) {
+        each_op_slice_mut(x, |x| silu(x))
+    }
+
+    #[inline]
+    fn silu_grad(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S> {
+        each_op(self, x, |x| silu_grad(x))
+    }
+
+    #[inline]
+    fn softplus(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S> {
+        each_op(self, x, |x| softplus(x))
+    }
+
+    #[inline]
+    fn softplus_mut(&self, x: &mut Matrix<T, D, S>) {
+        each_op_slice_mut(x, |x| softplus(x))
+    }
+
+    #[inline]
+    fn softplus_grad(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S> {
+        each_op(self, x, |x| softplus_grad(x))
+    }
+
+    #[inline]
+    fn mish(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S> {
+        each_op(self, x, |x| mish(x))
+    }
+
+    #[inline]
+    fn mish_mut(&self, x: &mut Matrix<T, D, S>) {
+        each_op_slice_mut(x, |x| mish(x))
+    }
+
+    #[inline]
+    fn mish_grad(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S> {
+        each_op(self, x, |x| mish_grad(x))
+    }
+
+    #[inline]
+    fn hard_sigmoid(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S> {
+        each_op(self, x, |x| hard_sigmoid(x))
+    }
+
+    #[inline]
+    fn hard_sigmoid_mut(&self, x: &mut Matrix<T, D, S>) {
+        each_op_slice_mut(x, |x| hard_sigmoid(x))
+    }
+
+    #[inline]
+    fn hard_sigmoid_grad(&self, x: &Matrix<T, D, S>) -> Matrix<T, Self, S> {
+        each_op(self, x, |x| hard_sigmoid_grad(x))
+    }
 }
 
 #[cfg(feature = "cuda")]
@@ -203,4 +693,314 @@ impl<T: CDatatype> ActivationOps<T> for CUDA {
     fn relu_grad_mut(&self, x: &mut Matrix<T, Self, ()>) {
         cu_str_op_mut(self, x, "(x >= 0)").unwrap()
     }
+
+    #[inline]
+    fn leaky_relu(&self, x: &Matrix<T, Self>, alpha: T) -> Matrix<T, Self> {
+        let out = cu_str_param_op(self, x, alpha, "(x >= 0 ? x : p * x)").unwrap();
+        (out, x.dims()).into()
+    }
+
+    #[inline]
+    fn leaky_relu_mut(&self, x: &mut Matrix<T, Self, ()>, alpha: T) {
+        cu_str_param_op_mut(self, x, alpha, "(x >= 0 ? x : p * x)").unwrap();
+    }
+
+    #[inline]
+    fn leaky_relu_grad(&self, x: &Matrix<T, Self>, alpha: T) -> Matrix<T, Self> {
+        let out = cu_str_param_op(self, x, alpha, "(x >= 0 ? 1 : p)").unwrap();
+        (out, x.dims()).into()
+    }
+
+    #[inline]
+    fn elu(&self, x: &Matrix<T, Self>, alpha: T) -> Matrix<T, Self> {
+        let out = cu_str_param_op(self, x, alpha, "(x >= 0 ? x : p * (exp(x) - 1.0))").unwrap();
+        (out, x.dims()).into()
+    }
+
+    #[inline]
+    fn elu_mut(&self, x: &mut Matrix<T, Self, ()>, alpha: T) {
+        cu_str_param_op_mut(self, x, alpha, "(x >= 0 ? x : p * (exp(x) - 1.0))").unwrap();
+    }
+
+    #[inline]
+    fn elu_grad(&self, x: &Matrix<T, Self>, alpha: T) -> Matrix<T, Self> {
+        let out = cu_str_param_op(self, x, alpha, "(x >= 0 ? 1.0 : p * exp(x))").unwrap();
+        (out, x.dims()).into()
+    }
+
+    #[inline]
+    fn selu(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        let out = cu_str_op(self, x, SELU).unwrap();
+        (out, x.dims()).into()
+    }
+
+    #[inline]
+    fn selu_mut(&self, x: &mut Matrix<T, Self, ()>) {
+        cu_str_op_mut(self, x, SELU).unwrap();
+    }
+
+    #[inline]
+    fn selu_grad(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        let out = cu_str_op(self, x, SELU_GRAD).unwrap();
+        (out, x.dims()).into()
+    }
+
+    #[inline]
+    fn gelu(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        let out = cu_str_op(self, x, GELU).unwrap();
+        (out, x.dims()).into()
+    }
+
+    #[inline]
+    fn gelu_mut(&self, x: &mut Matrix<T, Self, ()>) {
+        cu_str_op_mut(self, x, GELU).unwrap();
+    }
+
+    #[inline]
+    fn gelu_grad(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        let out = cu_str_op(self, x, GELU_GRAD).unwrap();
+        (out, x.dims()).into()
+    }
+
+    #[inline]
+    fn gelu_tanh(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        let out = cu_str_op(self, x, GELU_TANH).unwrap();
+        (out, x.dims()).into()
+    }
+
+    #[inline]
+    fn gelu_tanh_mut(&self, x: &mut Matrix<T, Self, ()>) {
+        cu_str_op_mut(self, x, GELU_TANH).unwrap();
+    }
+
+    #[inline]
+    fn gelu_tanh_grad(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        let out = cu_str_op(self, x, GELU_TANH_GRAD).unwrap();
+        (out, x.dims()).into()
+    }
+
+    #[inline]
+    fn silu(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        let out = cu_str_op(self, x, SILU).unwrap();
+        (out, x.dims()).into()
+    }
+
+    #[inline]
+    fn silu_mut(&self, x: &mut Matrix<T, Self, ()>) {
+        cu_str_op_mut(self, x, SILU).unwrap();
+    }
+
+    #[inline]
+    fn silu_grad(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        let out = cu_str_op(self, x, SILU_GRAD).unwrap();
+        (out, x.dims()).into()
+    }
+
+    #[inline]
+    fn softplus(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        let out = cu_str_op(self, x, SOFTPLUS).unwrap();
+        (out, x.dims()).into()
+    }
+
+    #[inline]
+    fn softplus_mut(&self, x: &mut Matrix<T, Self, ()>) {
+        cu_str_op_mut(self, x, SOFTPLUS).unwrap();
+    }
+
+    #[inline]
+    fn softplus_grad(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        let out = cu_str_op(self, x, SOFTPLUS_GRAD).unwrap();
+        (out, x.dims()).into()
+    }
+
+    #[inline]
+    fn mish(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        let out = cu_str_op(self, x, MISH).unwrap();
+        (out, x.dims()).into()
+    }
+
+    #[inline]
+    fn mish_mut(&self, x: &mut Matrix<T, Self, ()>) {
+        cu_str_op_mut(self, x, MISH).unwrap();
+    }
+
+    #[inline]
+    fn mish_grad(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        let out = cu_str_op(self, x, MISH_GRAD).unwrap();
+        (out, x.dims()).into()
+    }
+
+    #[inline]
+    fn hard_sigmoid(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        let out = cu_str_op(self, x, HARD_SIGMOID).unwrap();
+        (out, x.dims()).into()
+    }
+
+    #[inline]
+    fn hard_sigmoid_mut(&self, x: &mut Matrix<T, Self, ()>) {
+        cu_str_op_mut(self, x, HARD_SIGMOID).unwrap();
+    }
+
+    #[inline]
+    fn hard_sigmoid_grad(&self, x: &Matrix<T, Self>) -> Matrix<T, Self> {
+        let out = cu_str_op(self, x, HARD_SIGMOID_GRAD).unwrap();
+        (out, x.dims()).into()
+    }
+}
+
+const SELU_ALPHA: f64 = 1.6732632423543772;
+const SELU_SCALE: f64 = 1.0507009873554805;
+const SQRT_2_OVER_PI: f64 = 0.7978845608028654;
+const GELU_COEF: f64 = 0.044715;
+
+fn sigmoid<T: Float>(x: T) -> T {
+    T::one() / (T::one() + (-x).exp())
+}
+
+/// Approximation of the error function with a fractional error below 1.2e-7 (Numerical Recipes, `erfcc`).
+fn erf<T: Float>(x: T) -> T {
+    let coefs = [
+        -1.26551223,
+        1.00002368,
+        0.37409196,
+        0.09678418,
+        -0.18628806,
+        0.27886807,
+        -1.13520398,
+        1.48851587,
+        -0.82215223,
+        0.17087277,
+    ];
+
+    let z = x.abs();
+    let t = T::one() / (T::one() + T::as_generic(0.5) * z);
+
+    let poly = coefs
+        .iter()
+        .rev()
+        .fold(T::zero(), |acc, coef| T::as_generic(*coef) + t * acc);
+    let erfc = t * (-z * z + poly).exp();
+
+    if x >= T::zero() {
+        T::one() - erfc
+    } else {
+        erfc - T::one()
+    }
+}
+
+fn leaky_relu<T: Float>(x: T, alpha: T) -> T {
+    if x >= T::zero() {
+        x
+    } else {
+        alpha * x
+    }
+}
+
+fn leaky_relu_grad<T: Float>(x: T, alpha: T) -> T {
+    if x >= T::zero() {
+        T::one()
+    } else {
+        alpha
+    }
+}
+
+fn elu<T: Float>(x: T, alpha: T) -> T {
+    if x >= T::zero() {
+        x
+    } else {
+        alpha * (x.exp() - T::one())
+    }
+}
+
+fn elu_grad<T: Float>(x: T, alpha: T) -> T {
+    if x >= T::zero() {
+        T::one()
+    } else {
+        alpha * x.exp()
+    }
+}
+
+fn selu<T: Float>(x: T) -> T {
+    T::as_generic(SELU_SCALE) * elu(x, T::as_generic(SELU_ALPHA))
+}
+
+fn selu_grad<T: Float>(x: T) -> T {
+    T::as_generic(SELU_SCALE) * elu_grad(x, T::as_generic(SELU_ALPHA))
+}
+
+fn gelu<T: Float>(x: T) -> T {
+    T::as_generic(0.5) * x * (T::one() + erf(x * T::as_generic(core::f64::consts::FRAC_1_SQRT_2)))
+}
+
+fn gelu_grad<T: Float>(x: T) -> T {
+    let cdf =
+        T::as_generic(0.5) * (T::one() + erf(x * T::as_generic(core::f64::consts::FRAC_1_SQRT_2)));
+    let pdf = (T::as_generic(-0.5) * x * x).exp()
+        * T::as_generic(core::f64::consts::FRAC_2_SQRT_PI * core::f64::consts::FRAC_1_SQRT_2 * 0.5);
+    cdf + x * pdf
+}
+
+fn gelu_tanh<T: Float>(x: T) -> T {
+    let inner = T::as_generic(SQRT_2_OVER_PI) * (x + T::as_generic(GELU_COEF) * x * x * x);
+    T::as_generic(0.5) * x * (T::one() + inner.tanh())
+}
+
+fn gelu_tanh_grad<T: Float>(x: T) -> T {
+    let inner = T::as_generic(SQRT_2_OVER_PI) * (x + T::as_generic(GELU_COEF) * x * x * x);
+    let tanh = inner.tanh();
+    let inner_grad =
+        T::as_generic(SQRT_2_OVER_PI) * (T::one() + T::as_generic(3. * GELU_COEF) * x * x);
+
+    T::as_generic(0.5) * (T::one() + tanh)
+        + T::as_generic(0.5) * x * (T::one() - tanh * tanh) * inner_grad
+}
+
+fn silu<T: Float>(x: T) -> T {
+    x * sigmoid(x)
+}
+
+fn silu_grad<T: Float>(x: T) -> T {
+    let sigmoid = sigmoid(x);
+    sigmoid * (T::one() + x * (T::one() - sigmoid))
+}
+
+/// `max(x, 0) + ln(1 + exp(-|x|))`, which does not overflow for large `x`.
+fn softplus<T: Float>(x: T) -> T {
+    let max = if x > T::zero() { x } else { T::zero() };
+    max + (T::one() + (-x.abs()).exp()).ln()
+}
+
+fn softplus_grad<T: Float>(x: T) -> T {
+    sigmoid(x)
+}
+
+fn mish<T: Float>(x: T) -> T {
+    x * softplus(x).tanh()
+}
+
+fn mish_grad<T: Float>(x: T) -> T {
+    let tanh = softplus(x).tanh();
+    tanh + x * (T::one() - tanh * tanh) * sigmoid(x)
+}
+
+fn hard_sigmoid<T: Float>(x: T) -> T {
+    let three = T::as_generic(3.);
+
+    if x <= -three {
+        T::zero()
+    } else if x >= three {
+        T::one()
+    } else {
+        x / T::as_generic(6.) + T::as_generic(0.5)
+    }
+}
+
+fn hard_sigmoid_grad<T: Float>(x: T) -> T {
+    let three = T::as_generic(3.);
+
+    if x > -three && x < three {
+        T::one() / T::as_generic(6.)
+    } else {
+        T::zero()
+    }
 }
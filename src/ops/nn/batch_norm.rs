@@ -0,0 +1,223 @@
+use crate::{AdditionalOps, BaseOps, FnsOps, Matrix, RowOp, ScalarAssign, SumOverOps};
+use custos::{number::Float, Device, Shape};
+
+impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
+    /// Returns the output, the batch mean and the inverse standard deviation. See [`BatchNormOps`].
+    #[inline]
+    pub fn batch_norm_train<RS: Shape>(
+        &self,
+        gamma: &Matrix<T, D, RS>,
+        beta: &Matrix<T, D, RS>,
+        eps: T,
+    ) -> (
+        Matrix<'a, T, D, S>,
+        Matrix<'a, T, D, RS>,
+        Matrix<'a, T, D, RS>,
+    )
+    where
+        D: BatchNormOps<T, S, RS>,
+    {
+        self.device().batch_norm_train(self, gamma, beta, eps)
+    }
+
+    #[inline]
+    pub fn batch_norm_infer<RS: Shape>(
+        &self,
+        gamma: &Matrix<T, D, RS>,
+        beta: &Matrix<T, D, RS>,
+        running_mean: &Matrix<T, D, RS>,
+        running_var: &Matrix<T, D, RS>,
+        eps: T,
+    ) -> Matrix<'a, T, D, S>
+    where
+        D: BatchNormOps<T, S, RS>,
+    {
+        self.device()
+            .batch_norm_infer(self, gamma, beta, running_mean, running_var, eps)
+    }
+
+    /// `self` are the gradients of the batch norm output.
+    /// Returns the gradients with respect to `x`, `gamma` and `beta`.
+    #[inline]
+    pub fn batch_norm_grad<RS: Shape>(
+        &self,
+        x: &Matrix<T, D, S>,
+        gamma: &Matrix<T, D, RS>,
+        mean: &Matrix<T, D, RS>,
+        inv_std: &Matrix<T, D, RS>,
+    ) -> (
+        Matrix<'a, T, D, S>,
+        Matrix<'a, T, D, RS>,
+        Matrix<'a, T, D, RS>,
+    )
+    where
+        D: BatchNormOps<T, S, RS>,
+    {
+        self.device().batch_norm_grad(x, self, gamma, mean, inv_std)
+    }
+}
+
+/// Batch normalisation over the rows of `x`, hence every column is a feature.
+/// `gamma`, `beta` and all statistics are row vectors with `x.cols()` columns.
+///
+/// `batch_norm_train` normalises with the (biased) batch statistics and returns the output,
+/// the batch mean and the inverse standard deviation `1 / sqrt(var + eps)`, which are needed by `batch_norm_grad`.
+/// `batch_norm_infer` normalises with running statistics instead.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, nn::BatchNormOps};
+///
+/// let device = CPU::new();
+///
+/// let x = Matrix::from((&device, (2, 2), [1., 10., 3., 14.]));
+/// let gamma = Matrix::from((&device, (1, 2), [1., 2.]));
+/// let beta = Matrix::from((&device, (1, 2), [0., 1.]));
+///
+/// let (out, mean, inv_std) = device.batch_norm_train(&x, &gamma, &beta, 0.);
+/// assert_eq!(mean.read(), vec![2., 12.]);
+/// assert_eq!(inv_std.read(), vec![1., 0.5]);
+/// assert_eq!(out.read(), vec![-1., -1., 1., 3.]);
+///
+/// let running_var = Matrix::from((&device, (1, 2), [1., 4.]));
+/// let infer = device.batch_norm_infer(&x, &gamma, &beta, &mean, &running_var, 0.);
+/// assert_eq!(infer.read(), out.read());
+/// ```
+pub trait BatchNormOps<T, IS: Shape = (), RS: Shape = (), D: Device = Self>: Device {
+    #[allow(clippy::type_complexity)]
+    fn batch_norm_train(
+        &self,
+        x: &Matrix<T, D, IS>,
+        gamma: &Matrix<T, D, RS>,
+        beta: &Matrix<T, D, RS>,
+        eps: T,
+    ) -> (
+        Matrix<T, Self, IS>,
+        Matrix<T, Self, RS>,
+        Matrix<T, Self, RS>,
+    );
+
+    fn batch_norm_infer(
+        &self,
+        x: &Matrix<T, D, IS>,
+        gamma: &Matrix<T, D, RS>,
+        beta: &Matrix<T, D, RS>,
+        running_mean: &Matrix<T, D, RS>,
+        running_var: &Matrix<T, D, RS>,
+        eps: T,
+    ) -> Matrix<T, Self, IS>;
+
+    /// Returns `(dx, dgamma, dbeta)`. `mean` and `inv_std` are the statistics returned by `batch_norm_train`.
+    #[allow(clippy::type_complexity)]
+    fn batch_norm_grad(
+        &self,
+        x: &Matrix<T, D, IS>,
+        grads: &Matrix<T, D, IS>,
+        gamma: &Matrix<T, D, RS>,
+        mean: &Matrix<T, D, RS>,
+        inv_std: &Matrix<T, D, RS>,
+    ) -> (
+        Matrix<T, Self, IS>,
+        Matrix<T, Self, RS>,
+        Matrix<T, Self, RS>,
+    );
+}
+
+impl<T, D, IS: Shape, RS: Shape> BatchNormOps<T, IS, RS> for D
+where
+    T: Float,
+    D: SumOverOps<T, IS, RS>
+        + RowOp<T, IS, RS>
+        + BaseOps<T, IS>
+        + ScalarAssign<T, RS>
+        + AdditionalOps<T, RS>
+        + FnsOps<T, RS>,
+{
+    fn batch_norm_train(
+        &self,
+        x: &Matrix<T, D, IS>,
+        gamma: &Matrix<T, D, RS>,
+        beta: &Matrix<T, D, RS>,
+        eps: T,
+    ) -> (
+        Matrix<T, Self, IS>,
+        Matrix<T, Self, RS>,
+        Matrix<T, Self, RS>,
+    ) {
+        let n = T::from_usize(x.rows());
+
+        let mut mean = self.sum_rows(x);
+        self.divs_assign(&mut mean, n);
+
+        let centered = self.sub_row(x, &mean);
+
+        let mut var = self.sum_rows(&(&centered * &centered));
+        self.divs_assign(&mut var, n);
+        self.adds_assign(&mut var, eps);
+
+        let inv_std = self.powf(&var, T::as_generic(-0.5));
+
+        let mut out = self.mul_row(&centered, &inv_std);
+        self.mul_row_mut(&mut out, gamma);
+        self.add_row_mut(&mut out, beta);
+
+        (out, mean, inv_std)
+    }
+
+    fn batch_norm_infer(
+        &self,
+        x: &Matrix<T, D, IS>,
+        gamma: &Matrix<T, D, RS>,
+        beta: &Matrix<T, D, RS>,
+        running_mean: &Matrix<T, D, RS>,
+        running_var: &Matrix<T, D, RS>,
+        eps: T,
+    ) -> Matrix<T, Self, IS> {
+        let inv_std = self.powf(&self.adds(running_var, eps), T::as_generic(-0.5));
+
+        let mut out = self.sub_row(x, running_mean);
+        self.mul_row_mut(&mut out, &inv_std);
+        self.mul_row_mut(&mut out, gamma);
+        self.add_row_mut(&mut out, beta);
+        out
+    }
+
+    fn batch_norm_grad(
+        &self,
+        x: &Matrix<T, D, IS>,
+        grads: &Matrix<T, D, IS>,
+        gamma: &Matrix<T, D, RS>,
+        mean: &Matrix<T, D, RS>,
+        inv_std: &Matrix<T, D, RS>,
+    ) -> (
+        Matrix<T, Self, IS>,
+        Matrix<T, Self, RS>,
+        Matrix<T, Self, RS>,
+    ) {
+        let n = T::from_usize(x.rows());
+
+        let mut x_hat = self.sub_row(x, mean);
+        self.mul_row_mut(&mut x_hat, inv_std);
+
+        let dbeta = self.sum_rows(grads);
+        let dgamma = self.sum_rows(&self.mul(grads, &x_hat));
+
+        // dx = inv_std * (dx_hat - mean(dx_hat) - x_hat * mean(dx_hat * x_hat))
+        let dx_hat = self.mul_row(grads, gamma);
+
+        let mut dx_hat_mean = self.sum_rows(&dx_hat);
+        self.divs_assign(&mut dx_hat_mean, n);
+
+        let mut dx_hat_x_hat_mean = self.sum_rows(&(&dx_hat * &x_hat));
+        self.divs_assign(&mut dx_hat_x_hat_mean, n);
+
+        let mut correction = self.mul_row(&x_hat, &dx_hat_x_hat_mean);
+        self.add_row_mut(&mut correction, &dx_hat_mean);
+
+        let mut dx = &dx_hat - &correction;
+        self.mul_row_mut(&mut dx, inv_std);
+
+        (dx, dgamma, dbeta)
+    }
+}
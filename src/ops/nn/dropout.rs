@@ -0,0 +1,161 @@
+use crate::Matrix;
+use custos::{impl_stack, number::Float, Device, MainMemory, Shape, CPU};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[cfg(any(feature = "opencl", feature = "cuda"))]
+use custos::CDatatype;
+
+#[cfg(feature = "opencl")]
+use crate::{cl_tew, opencl::cl_write};
+#[cfg(feature = "opencl")]
+use custos::{prelude::CLBuffer, OpenCL};
+
+#[cfg(feature = "cuda")]
+use crate::cu_ew;
+#[cfg(feature = "cuda")]
+use custos::{cuda::api::cu_write, prelude::CUBuffer, CUDA};
+
+impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S>
+where
+    D: DropoutOps<T, S>,
+{
+    /// Returns the output and the mask. See [`DropoutOps`].
+    #[inline]
+    pub fn dropout(&self, p: T, seed: u64) -> (Matrix<'a, T, D, S>, Matrix<'a, T, D, S>) {
+        self.device().dropout(self, p, seed)
+    }
+
+    /// `self` are the gradients of the dropout output.
+    #[inline]
+    pub fn dropout_grad(&self, mask: &Matrix<T, D, S>) -> Matrix<'a, T, D, S> {
+        self.device().dropout_grad(self, mask)
+    }
+}
+
+/// Inverted dropout: every value is set to zero with probability `p`,
+/// the remaining values are scaled by `1 / (1 - p)`, hence the expected value stays the same.
+///
+/// `dropout` returns the output and the mask, which contains `0` for dropped values and `1 / (1 - p)` for kept values.
+/// The mask is generated on the host, therefore the same `seed` yields the same mask on every device.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, nn::DropoutOps};
+///
+/// let device = CPU::new();
+///
+/// let x = Matrix::from((&device, (2, 3), [1., 2., 3., 4., 5., 6.,]));
+///
+/// let (out, mask) = device.dropout(&x, 0.5, 42);
+/// let (_, same_mask) = device.dropout(&x, 0.5, 42);
+/// assert_eq!(mask.read(), same_mask.read());
+///
+/// for ((out, x), mask) in out.iter().zip(x.iter()).zip(mask.iter()) {
+///     assert!(*mask == 0. || *mask == 2.);
+///     assert_eq!(*out, x * mask);
+/// }
+///
+/// let grads = Matrix::from((&device, (2, 3), [1.; 6]));
+/// assert_eq!(device.dropout_grad(&grads, &mask).read(), mask.read());
+/// ```
+pub trait DropoutOps<T, S: Shape = (), D: Device = Self>: Device {
+    fn dropout(
+        &self,
+        x: &Matrix<T, D, S>,
+        p: T,
+        seed: u64,
+    ) -> (Matrix<T, Self, S>, Matrix<T, Self, S>);
+    fn dropout_grad(&self, grads: &Matrix<T, D, S>, mask: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
+}
+
+/// Fills `mask` with `0` (probability `p`) or `1 / (1 - p)`. The values only depend on `seed`.
+pub fn dropout_mask_slice<T: Float>(mask: &mut [T], p: T, seed: u64) {
+    assert!(
+        p >= T::zero() && p < T::one(),
+        "the dropout probability must be in [0, 1)"
+    );
+
+    let rng = fastrand::Rng::with_seed(seed);
+    let scale = T::one() / (T::one() - p);
+
+    for value in mask {
+        *value = if T::as_generic(rng.f64()) < p {
+            T::zero()
+        } else {
+            scale
+        };
+    }
+}
+
+#[impl_stack]
+impl<T: Float, D: MainMemory, S: Shape> DropoutOps<T, S, D> for CPU {
+    fn dropout(
+        &self,
+        x: &Matrix<T, D, S>,
+        p: T,
+        seed: u64,
+    ) -> (Matrix<T, Self, S>, Matrix<T, Self, S>) {
+        let mut mask = self.retrieve(x.len(), x.node.idx);
+        dropout_mask_slice(&mut mask, p, seed);
+
+        let mut out = self.retrieve(x.len(), (x.node.idx, mask.node.idx));
+        for ((out, x), mask) in out.iter_mut().zip(x.iter()).zip(mask.iter()) {
+            *out = *x * *mask;
+        }
+
+        ((out, x.dims()).into(), (mask, x.dims()).into())
+    }
+
+    fn dropout_grad(&self, grads: &Matrix<T, D, S>, mask: &Matrix<T, D, S>) -> Matrix<T, Self, S> {
+        assert_eq!(grads.dims(), mask.dims());
+
+        let mut out = self.retrieve(grads.len(), (grads.node.idx, mask.node.idx));
+        for ((out, grad), mask) in out.iter_mut().zip(grads.iter()).zip(mask.iter()) {
+            *out = *grad * *mask;
+        }
+        (out, grads.dims()).into()
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: Float + CDatatype> DropoutOps<T> for OpenCL {
+    fn dropout(&self, x: &Matrix<T, Self>, p: T, seed: u64) -> (Matrix<T, Self>, Matrix<T, Self>) {
+        let mut data = vec![T::default(); x.len()];
+        dropout_mask_slice(&mut data, p, seed);
+
+        let mut mask: CLBuffer<T> = self.retrieve(x.len(), x.node.idx);
+        cl_write(self, &mut mask, &data);
+
+        let out = cl_tew(self, x, &mask, "*").unwrap();
+        ((out, x.dims()).into(), (mask, x.dims()).into())
+    }
+
+    #[inline]
+    fn dropout_grad(&self, grads: &Matrix<T, Self>, mask: &Matrix<T, Self>) -> Matrix<T, Self> {
+        let out = cl_tew(self, grads, mask, "*").unwrap();
+        (out, grads.dims()).into()
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: Float + CDatatype> DropoutOps<T> for CUDA {
+    fn dropout(&self, x: &Matrix<T, Self>, p: T, seed: u64) -> (Matrix<T, Self>, Matrix<T, Self>) {
+        let mut data = vec![T::default(); x.len()];
+        dropout_mask_slice(&mut data, p, seed);
+
+        let mask: CUBuffer<T> = self.retrieve(x.len(), x.node.idx);
+        cu_write(mask.ptr.ptr, &data).unwrap();
+
+        let out = cu_ew(self, x, &mask, "*").unwrap();
+        ((out, x.dims()).into(), (mask, x.dims()).into())
+    }
+
+    #[inline]
+    fn dropout_grad(&self, grads: &Matrix<T, Self>, mask: &Matrix<T, Self>) -> Matrix<T, Self> {
+        let out = cu_ew(self, grads, mask, "*").unwrap();
+        (out, grads.dims()).into()
+    }
+}
@@ -0,0 +1,582 @@
+use crate::Matrix;
+use custos::{impl_stack, number::Float, Device, MainMemory, Shape, CPU};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[cfg(feature = "opencl")]
+use custos::{opencl::enqueue_kernel, prelude::CLBuffer, CDatatype, OpenCL};
+
+impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
+    /// See [`LayerNormOps::layer_norm`].
+    #[inline]
+    pub fn layer_norm<RS: Shape>(
+        &self,
+        gamma: &Matrix<T, D, RS>,
+        beta: &Matrix<T, D, RS>,
+        eps: T,
+    ) -> Matrix<'a, T, D, S>
+    where
+        D: LayerNormOps<T, S, RS>,
+    {
+        self.device().layer_norm(self, gamma, beta, eps)
+    }
+
+    /// `self` are the gradients of the layer norm output.
+    /// Returns the gradients with respect to `x`, `gamma` and `beta`.
+    #[inline]
+    pub fn layer_norm_grad<RS: Shape>(
+        &self,
+        x: &Matrix<T, D, S>,
+        gamma: &Matrix<T, D, RS>,
+        eps: T,
+    ) -> (
+        Matrix<'a, T, D, S>,
+        Matrix<'a, T, D, RS>,
+        Matrix<'a, T, D, RS>,
+    )
+    where
+        D: LayerNormOps<T, S, RS>,
+    {
+        self.device().layer_norm_grad(x, self, gamma, eps)
+    }
+
+    /// See [`LayerNormOps::rms_norm`].
+    #[inline]
+    pub fn rms_norm<RS: Shape>(&self, gamma: &Matrix<T, D, RS>, eps: T) -> Matrix<'a, T, D, S>
+    where
+        D: LayerNormOps<T, S, RS>,
+    {
+        self.device().rms_norm(self, gamma, eps)
+    }
+
+    /// `self` are the gradients of the rms norm output.
+    /// Returns the gradients with respect to `x` and `gamma`.
+    #[inline]
+    pub fn rms_norm_grad<RS: Shape>(
+        &self,
+        x: &Matrix<T, D, S>,
+        gamma: &Matrix<T, D, RS>,
+        eps: T,
+    ) -> (Matrix<'a, T, D, S>, Matrix<'a, T, D, RS>)
+    where
+        D: LayerNormOps<T, S, RS>,
+    {
+        self.device().rms_norm_grad(x, self, gamma, eps)
+    }
+}
+
+/// Row wise normalisation, hence every row is a sample and every column a feature.
+/// `gamma` and `beta` are row vectors with `x.cols()` columns.
+///
+/// The statistics of a row are computed in the same pass as its output.
+/// As they are cheap to recompute, the backward passes take `x` instead of saved statistics.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, nn::LayerNormOps};
+///
+/// let device = CPU::new();
+///
+/// let x = Matrix::from((&device, (2, 2), [2., -2., -4., 4.]));
+/// let gamma = Matrix::from((&device, (1, 2), [1., 2.]));
+/// let beta = Matrix::from((&device, (1, 2), [0., 1.]));
+///
+/// let out = device.layer_norm(&x, &gamma, &beta, 0.);
+/// assert_eq!(out.read(), vec![1., -1., -1., 3.]);
+///
+/// let out = device.rms_norm(&x, &gamma, 0.);
+/// assert_eq!(out.read(), vec![1., -2., -1., 2.]);
+/// ```
+pub trait LayerNormOps<T, S: Shape = (), RS: Shape = (), D: Device = Self>: Device {
+    /// `(x - mean) / sqrt(var + eps) * gamma + beta`, where `mean` and the (biased) `var` are computed per row.
+    fn layer_norm(
+        &self,
+        x: &Matrix<T, D, S>,
+        gamma: &Matrix<T, D, RS>,
+        beta: &Matrix<T, D, RS>,
+        eps: T,
+    ) -> Matrix<T, Self, S>;
+
+    /// Returns `(dx, dgamma, dbeta)`.
+    #[allow(clippy::type_complexity)]
+    fn layer_norm_grad(
+        &self,
+        x: &Matrix<T, D, S>,
+        grads: &Matrix<T, D, S>,
+        gamma: &Matrix<T, D, RS>,
+        eps: T,
+    ) -> (Matrix<T, Self, S>, Matrix<T, Self, RS>, Matrix<T, Self, RS>);
+
+    /// `x / sqrt(mean(x^2) + eps) * gamma`, where the mean is computed per row.
+    fn rms_norm(&self, x: &Matrix<T, D, S>, gamma: &Matrix<T, D, RS>, eps: T)
+        -> Matrix<T, Self, S>;
+
+    /// Returns `(dx, dgamma)`.
+    fn rms_norm_grad(
+        &self,
+        x: &Matrix<T, D, S>,
+        grads: &Matrix<T, D, S>,
+        gamma: &Matrix<T, D, RS>,
+        eps: T,
+    ) -> (Matrix<T, Self, S>, Matrix<T, Self, RS>);
+}
+
+/// Returns the mean (zero if `center` is false) and `1 / sqrt(var + eps)` of a row.
+/// If `center` is false, `var` is the mean of the squared values.
+fn row_stats<T: Float>(x: &[T], eps: T, center: bool) -> (T, T) {
+    let cols = T::from_usize(x.len());
+
+    let mean = if center {
+        x.iter().fold(T::zero(), |sum, x| sum + *x) / cols
+    } else {
+        T::zero()
+    };
+
+    let var = x
+        .iter()
+        .fold(T::zero(), |sum, x| sum + (*x - mean) * (*x - mean))
+        / cols;
+
+    (mean, T::one() / (var + eps).sqrt())
+}
+
+fn norm_slice<T: Float>(
+    x: &[T],
+    cols: usize,
+    gamma: &[T],
+    beta: Option<&[T]>,
+    eps: T,
+    out: &mut [T],
+) {
+    assert_eq!(gamma.len(), cols);
+
+    for (x, out) in x.chunks(cols).zip(out.chunks_mut(cols)) {
+        let (mean, inv_std) = row_stats(x, eps, beta.is_some());
+
+        for (col, (out, x)) in out.iter_mut().zip(x).enumerate() {
+            *out = (*x - mean) * inv_std * gamma[col];
+
+            if let Some(beta) = beta {
+                *out += beta[col];
+            }
+        }
+    }
+}
+
+/// `dx = inv_std * (dx_hat - mean(dx_hat) - x_hat * mean(dx_hat * x_hat))` with `dx_hat = grads * gamma`.
+/// The `mean(dx_hat)` term is omitted if `dbeta` is `None` (rms norm).
+#[allow(clippy::too_many_arguments)]
+fn norm_grad_slice<T: Float>(
+    x: &[T],
+    grads: &[T],
+    cols: usize,
+    gamma: &[T],
+    eps: T,
+    dx: &mut [T],
+    dgamma: &mut [T],
+    mut dbeta: Option<&mut [T]>,
+) {
+    assert_eq!(gamma.len(), cols);
+
+    let center = dbeta.is_some();
+    let n = T::from_usize(cols);
+
+    dgamma.fill(T::zero());
+    if let Some(dbeta) = &mut dbeta {
+        dbeta.fill(T::zero());
+    }
+
+    let rows = x.chunks(cols).zip(grads.chunks(cols));
+
+    for ((x, grads), dx) in rows.zip(dx.chunks_mut(cols)) {
+        let (mean, inv_std) = row_stats(x, eps, center);
+
+        let mut dx_hat_sum = T::zero();
+        let mut dx_hat_x_hat_sum = T::zero();
+
+        for (col, (x, grad)) in x.iter().zip(grads).enumerate() {
+            let x_hat = (*x - mean) * inv_std;
+            let dx_hat = *grad * gamma[col];
+
+            dx_hat_sum += dx_hat;
+            dx_hat_x_hat_sum += dx_hat * x_hat;
+
+            dgamma[col] += *grad * x_hat;
+            if let Some(dbeta) = &mut dbeta {
+                dbeta[col] += *grad;
+            }
+        }
+
+        let dx_hat_mean = if center { dx_hat_sum / n } else { T::zero() };
+        let dx_hat_x_hat_mean = dx_hat_x_hat_sum / n;
+
+        for (col, ((dx, x), grad)) in dx.iter_mut().zip(x).zip(grads).enumerate() {
+            let x_hat = (*x - mean) * inv_std;
+            let dx_hat = *grad * gamma[col];
+            *dx = inv_std * (dx_hat - dx_hat_mean - x_hat * dx_hat_x_hat_mean);
+        }
+    }
+}
+
+/// Row wise layer normalisation of a slice with `cols` columns.
+pub fn layer_norm_slice<T: Float>(
+    x: &[T],
+    cols: usize,
+    gamma: &[T],
+    beta: &[T],
+    eps: T,
+    out: &mut [T],
+) {
+    norm_slice(x, cols, gamma, Some(beta), eps, out)
+}
+
+/// Row wise rms normalisation of a slice with `cols` columns.
+pub fn rms_norm_slice<T: Float>(x: &[T], cols: usize, gamma: &[T], eps: T, out: &mut [T]) {
+    norm_slice(x, cols, gamma, None, eps, out)
+}
+
+/// Computes `dx`, `dgamma` and `dbeta` of a row wise layer normalisation. `dgamma` and `dbeta` are overwritten.
+#[allow(clippy::too_many_arguments)]
+pub fn layer_norm_grad_slice<T: Float>(
+    x: &[T],
+    grads: &[T],
+    cols: usize,
+    gamma: &[T],
+    eps: T,
+    dx: &mut [T],
+    dgamma: &mut [T],
+    dbeta: &mut [T],
+) {
+    norm_grad_slice(x, grads, cols, gamma, eps, dx, dgamma, Some(dbeta))
+}
+
+/// Computes `dx` and `dgamma` of a row wise rms normalisation. `dgamma` is overwritten.
+pub fn rms_norm_grad_slice<T: Float>(
+    x: &[T],
+    grads: &[T],
+    cols: usize,
+    gamma: &[T],
+    eps: T,
+    dx: &mut [T],
+    dgamma: &mut [T],
+) {
+    norm_grad_slice(x, grads, cols, gamma, eps, dx, dgamma, None)
+}
+
+#[impl_stack]
+impl<T: Float, D: MainMemory, S: Shape, RS: Shape> LayerNormOps<T, S, RS, D> for CPU {
+    fn layer_norm(
+        &self,
+        x: &Matrix<T, D, S>,
+        gamma: &Matrix<T, D, RS>,
+        beta: &Matrix<T, D, RS>,
+        eps: T,
+    ) -> Matrix<T, Self, S> {
+        let mut out = self.retrieve(x.len(), [x.node.idx, gamma.node.idx, beta.node.idx]);
+        layer_norm_slice(x, x.cols(), gamma, beta, eps, &mut out);
+        (out, x.dims()).into()
+    }
+
+    fn layer_norm_grad(
+        &self,
+        x: &Matrix<T, D, S>,
+        grads: &Matrix<T, D, S>,
+        gamma: &Matrix<T, D, RS>,
+        eps: T,
+    ) -> (Matrix<T, Self, S>, Matrix<T, Self, RS>, Matrix<T, Self, RS>) {
+        assert_eq!(x.dims(), grads.dims());
+
+        let parents = [x.node.idx, grads.node.idx, gamma.node.idx];
+        let mut dx = self.retrieve(x.len(), parents);
+        let mut dgamma = self.retrieve(gamma.len(), parents);
+        let mut dbeta = self.retrieve(gamma.len(), parents);
+
+        layer_norm_grad_slice(
+            x,
+            grads,
+            x.cols(),
+            gamma,
+            eps,
+            &mut dx,
+            &mut dgamma,
+            &mut dbeta,
+        );
+
+        (
+            (dx, x.dims()).into(),
+            (dgamma, gamma.dims()).into(),
+            (dbeta, gamma.dims()).into(),
+        )
+    }
+
+    fn rms_norm(
+        &self,
+        x: &Matrix<T, D, S>,
+        gamma: &Matrix<T, D, RS>,
+        eps: T,
+    ) -> Matrix<T, Self, S> {
+        let mut out = self.retrieve(x.len(), (x.node.idx, gamma.node.idx));
+        rms_norm_slice(x, x.cols(), gamma, eps, &mut out);
+        (out, x.dims()).into()
+    }
+
+    fn rms_norm_grad(
+        &self,
+        x: &Matrix<T, D, S>,
+        grads: &Matrix<T, D, S>,
+        gamma: &Matrix<T, D, RS>,
+        eps: T,
+    ) -> (Matrix<T, Self, S>, Matrix<T, Self, RS>) {
+        assert_eq!(x.dims(), grads.dims());
+
+        let parents = [x.node.idx, grads.node.idx, gamma.node.idx];
+        let mut dx = self.retrieve(x.len(), parents);
+        let mut dgamma = self.retrieve(gamma.len(), parents);
+
+        rms_norm_grad_slice(x, grads, x.cols(), gamma, eps, &mut dx, &mut dgamma);
+
+        ((dx, x.dims()).into(), (dgamma, gamma.dims()).into())
+    }
+}
+
+/// Computes the mean (if `center` is set) and `inv_std` of the row, which starts at `idx`.
+#[cfg(feature = "opencl")]
+fn cl_row_stats(datatype: &str, cols: usize, center: bool) -> String {
+    let mean = if center {
+        format!(
+            "for (size_t col = 0; col < {cols}; col++) {{
+                mean += x[idx + col];
+            }}
+            mean /= {cols};"
+        )
+    } else {
+        String::new()
+    };
+
+    format!(
+        "
+        {datatype} mean = 0;
+        {mean}
+
+        {datatype} var = 0;
+        for (size_t col = 0; col < {cols}; col++) {{
+            {datatype} centered = x[idx + col] - mean;
+            var += centered * centered;
+        }}
+        {datatype} inv_std = 1 / sqrt(var / {cols} + eps);
+    "
+    )
+}
+
+/// Layer normalisation (`beta` is `Some`) or rms normalisation with one work item per row.
+#[cfg(feature = "opencl")]
+pub fn cl_norm<'a, T: CDatatype>(
+    device: &'a OpenCL,
+    x: &CLBuffer<T>,
+    gamma: &CLBuffer<T>,
+    beta: Option<&CLBuffer<T>>,
+    rows: usize,
+    cols: usize,
+    eps: T,
+) -> custos::Result<CLBuffer<'a, T>> {
+    let datatype = T::as_c_type_str();
+    let stats = cl_row_stats(datatype, cols, beta.is_some());
+
+    let (beta_param, shift) = match beta {
+        Some(_) => (format!("__global const {datatype}* beta,"), "+ beta[col]"),
+        None => (String::new(), ""),
+    };
+
+    let src = format!(
+        "
+        __kernel void norm(__global const {datatype}* x, __global const {datatype}* gamma, {beta_param} __global {datatype}* out, const {datatype} eps) {{
+            size_t idx = get_global_id(0) * {cols};
+            {stats}
+
+            for (size_t col = 0; col < {cols}; col++) {{
+                out[idx + col] = (x[idx + col] - mean) * inv_std * gamma[col] {shift};
+            }}
+        }}
+    "
+    );
+
+    let out: CLBuffer<T> = device.retrieve(rows * cols, (x.node.idx, gamma.node.idx));
+
+    match beta {
+        Some(beta) => enqueue_kernel(
+            device,
+            &src,
+            [rows, 0, 0],
+            None,
+            &[x, gamma, beta, &out, &eps],
+        )?,
+        None => enqueue_kernel(device, &src, [rows, 0, 0], None, &[x, gamma, &out, &eps])?,
+    }
+    Ok(out)
+}
+
+/// Computes `dx` with one work item per row, then `dgamma` and `dbeta` (if `center` is set) with one work item per column.
+#[cfg(feature = "opencl")]
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn cl_norm_grad<'a, T: CDatatype>(
+    device: &'a OpenCL,
+    x: &CLBuffer<T>,
+    grads: &CLBuffer<T>,
+    gamma: &CLBuffer<T>,
+    rows: usize,
+    cols: usize,
+    eps: T,
+    center: bool,
+) -> custos::Result<(CLBuffer<'a, T>, CLBuffer<'a, T>, Option<CLBuffer<'a, T>>)> {
+    let datatype = T::as_c_type_str();
+    let stats = cl_row_stats(datatype, cols, center);
+    let dx_hat_mean = if center {
+        format!("dx_hat_mean /= {cols};")
+    } else {
+        "dx_hat_mean = 0;".to_string()
+    };
+
+    let src = format!(
+        "
+        __kernel void norm_grad(__global const {datatype}* x, __global const {datatype}* grads, __global const {datatype}* gamma, __global {datatype}* x_hat, __global {datatype}* dx, const {datatype} eps) {{
+            size_t idx = get_global_id(0) * {cols};
+            {stats}
+
+            {datatype} dx_hat_mean = 0;
+            {datatype} dx_hat_x_hat_mean = 0;
+            for (size_t col = 0; col < {cols}; col++) {{
+                x_hat[idx + col] = (x[idx + col] - mean) * inv_std;
+                {datatype} dx_hat = grads[idx + col] * gamma[col];
+
+                dx_hat_mean += dx_hat;
+                dx_hat_x_hat_mean += dx_hat * x_hat[idx + col];
+            }}
+            {dx_hat_mean}
+            dx_hat_x_hat_mean /= {cols};
+
+            for (size_t col = 0; col < {cols}; col++) {{
+                {datatype} dx_hat = grads[idx + col] * gamma[col];
+                dx[idx + col] = inv_std * (dx_hat - dx_hat_mean - x_hat[idx + col] * dx_hat_x_hat_mean);
+            }}
+        }}
+    "
+    );
+
+    let x_hat: CLBuffer<T> = device.retrieve(rows * cols, (x.node.idx, gamma.node.idx));
+    let dx: CLBuffer<T> = device.retrieve(rows * cols, (x.node.idx, grads.node.idx));
+    enqueue_kernel(
+        device,
+        &src,
+        [rows, 0, 0],
+        None,
+        &[x, grads, gamma, &x_hat, &dx, &eps],
+    )?;
+
+    let (dbeta_param, dbeta) = if center {
+        (
+            format!("__global {datatype}* dbeta,"),
+            format!(
+                "{datatype} dbeta_sum = 0;
+                for (size_t row = 0; row < {rows}; row++) {{
+                    dbeta_sum += grads[row * {cols} + col];
+                }}
+                dbeta[col] = dbeta_sum;"
+            ),
+        )
+    } else {
+        (String::new(), String::new())
+    };
+
+    let src = format!(
+        "
+        __kernel void norm_param_grad(__global const {datatype}* x_hat, __global const {datatype}* grads, {dbeta_param} __global {datatype}* dgamma) {{
+            size_t col = get_global_id(0);
+
+            {datatype} dgamma_sum = 0;
+            for (size_t row = 0; row < {rows}; row++) {{
+                dgamma_sum += grads[row * {cols} + col] * x_hat[row * {cols} + col];
+            }}
+            dgamma[col] = dgamma_sum;
+
+            {dbeta}
+        }}
+    "
+    );
+
+    let dgamma: CLBuffer<T> = device.retrieve(cols, (x_hat.node.idx, grads.node.idx));
+
+    if center {
+        let dbeta: CLBuffer<T> = device.retrieve(cols, grads.node.idx);
+        enqueue_kernel(
+            device,
+            &src,
+            [cols, 0, 0],
+            None,
+            &[&x_hat, grads, &dbeta, &dgamma],
+        )?;
+        Ok((dx, dgamma, Some(dbeta)))
+    } else {
+        enqueue_kernel(device, &src, [cols, 0, 0], None, &[&x_hat, grads, &dgamma])?;
+        Ok((dx, dgamma, None))
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: Float + CDatatype> LayerNormOps<T> for OpenCL {
+    fn layer_norm(
+        &self,
+        x: &Matrix<T, Self>,
+        gamma: &Matrix<T, Self>,
+        beta: &Matrix<T, Self>,
+        eps: T,
+    ) -> Matrix<T, Self> {
+        assert_eq!(gamma.len(), x.cols());
+        assert_eq!(beta.len(), x.cols());
+
+        let out = cl_norm(self, x, gamma, Some(beta), x.rows(), x.cols(), eps).unwrap();
+        (out, x.dims()).into()
+    }
+
+    fn layer_norm_grad(
+        &self,
+        x: &Matrix<T, Self>,
+        grads: &Matrix<T, Self>,
+        gamma: &Matrix<T, Self>,
+        eps: T,
+    ) -> (Matrix<T, Self>, Matrix<T, Self>, Matrix<T, Self>) {
+        assert_eq!(x.dims(), grads.dims());
+        assert_eq!(gamma.len(), x.cols());
+
+        let (dx, dgamma, dbeta) =
+            cl_norm_grad(self, x, grads, gamma, x.rows(), x.cols(), eps, true).unwrap();
+        (
+            (dx, x.dims()).into(),
+            (dgamma, gamma.dims()).into(),
+            (dbeta.unwrap(), gamma.dims()).into(),
+        )
+    }
+
+    fn rms_norm(&self, x: &Matrix<T, Self>, gamma: &Matrix<T, Self>, eps: T) -> Matrix<T, Self> {
+        assert_eq!(gamma.len(), x.cols());
+
+        let out = cl_norm(self, x, gamma, None, x.rows(), x.cols(), eps).unwrap();
+        (out, x.dims()).into()
+    }
+
+    fn rms_norm_grad(
+        &self,
+        x: &Matrix<T, Self>,
+        grads: &Matrix<T, Self>,
+        gamma: &Matrix<T, Self>,
+        eps: T,
+    ) -> (Matrix<T, Self>, Matrix<T, Self>) {
+        assert_eq!(x.dims(), grads.dims());
+        assert_eq!(gamma.len(), x.cols());
+
+        let (dx, dgamma, _) =
+            cl_norm_grad(self, x, grads, gamma, x.rows(), x.cols(), eps, false).unwrap();
+        ((dx, x.dims()).into(), (dgamma, gamma.dims()).into())
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{AdditionalOps, BaseOps, ClipOp, FnsOps, Matrix, SumOps};
+use custos::{number::Float, Device, Shape};
+
+impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
+    /// Binary cross entropy of the probabilities in `self`. See [`BCEOp`].
+    #[inline]
+    pub fn bce(&self, targets: &Matrix<T, D, S>) -> (T, Matrix<'a, T, D, S>)
+    where
+        D: BCEOp<T, S>,
+    {
+        self.device().bce(self, targets)
+    }
+
+    #[inline]
+    pub fn bce_loss(&self, targets: &Matrix<T, D, S>) -> T
+    where
+        D: BCEOp<T, S>,
+    {
+        self.device().bce_loss(self, targets)
+    }
+
+    #[inline]
+    pub fn bce_grad(&self, targets: &Matrix<T, D, S>) -> Matrix<'a, T, D, S>
+    where
+        D: BCEOp<T, S>,
+    {
+        self.device().bce_grad(self, targets)
+    }
+
+    /// Binary cross entropy of the logits in `self`. See [`BCEWithLogitsOp`].
+    #[inline]
+    pub fn bce_with_logits(&self, targets: &Matrix<T, D, S>) -> (T, Matrix<'a, T, D, S>)
+    where
+        D: BCEWithLogitsOp<T, S>,
+    {
+        self.device().bce_with_logits(self, targets)
+    }
+
+    #[inline]
+    pub fn bce_with_logits_loss(&self, targets: &Matrix<T, D, S>) -> T
+    where
+        D: BCEWithLogitsOp<T, S>,
+    {
+        self.device().bce_with_logits_loss(self, targets)
+    }
+
+    #[inline]
+    pub fn bce_with_logits_grad(&self, targets: &Matrix<T, D, S>) -> Matrix<'a, T, D, S>
+    where
+        D: BCEWithLogitsOp<T, S>,
+    {
+        self.device().bce_with_logits_grad(self, targets)
+    }
+}
+
+/// Binary cross entropy: `-mean(targets * ln(preds) + (1 - targets) * ln(1 - preds))`.
+///
+/// Every element is an independent binary prediction, hence the loss and the gradient are averaged over all elements.
+/// Expects probabilities, which are clipped to `[1e-7, 1 - 1e-7]` in the loss and the gradient.
+/// For raw logits, [`BCEWithLogitsOp`] is numerically stable.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, nn::BCEOp};
+///
+/// let device = CPU::new();
+///
+/// let preds = Matrix::from((&device, (1, 2), [0.5, 0.8]));
+/// let targets = Matrix::from((&device, (1, 2), [1., 0.]));
+///
+/// let (loss, grad) = device.bce(&preds, &targets);
+/// assert!((loss - (2f64.ln() + 5f64.ln()) / 2.).abs() < 1e-6);
+/// assert!((grad[0] + 1.).abs() < 1e-6);
+/// assert!((grad[1] - 2.5).abs() < 1e-6);
+/// ```
+pub trait BCEOp<T, S: Shape = (), D: Device = Self>: Device {
+    #[inline]
+    fn bce(&self, preds: &Matrix<T, D, S>, targets: &Matrix<T, D, S>) -> (T, Matrix<T, Self, S>) {
+        (self.bce_loss(preds, targets), self.bce_grad(preds, targets))
+    }
+    fn bce_loss(&self, preds: &Matrix<T, D, S>, targets: &Matrix<T, D, S>) -> T;
+    fn bce_grad(&self, preds: &Matrix<T, D, S>, targets: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
+}
+
+impl<T, D, S: Shape> BCEOp<T, S> for D
+where
+    T: Float,
+    D: ClipOp<T, S> + BaseOps<T, S> + AdditionalOps<T, S> + FnsOps<T, S> + SumOps<T, S>,
+{
+    fn bce_loss(&self, preds: &Matrix<T, D, S>, targets: &Matrix<T, D, S>) -> T {
+        let preds = self.clip(preds, T::as_generic(1E-7), T::as_generic(1. - 1E-7));
+        let inv_preds = self.adds(&self.neg(&preds), T::one());
+        let inv_targets = self.adds(&self.neg(targets), T::one());
+
+        let log_likelihood =
+            self.mul(targets, &self.ln(&preds)) + &inv_targets * &self.ln(&inv_preds);
+        self.mean(&log_likelihood.neg())
+    }
+
+    fn bce_grad(&self, preds: &Matrix<T, D, S>, targets: &Matrix<T, D, S>) -> Matrix<T, Self, S> {
+        let preds = self.clip(preds, T::as_generic(1E-7), T::as_generic(1. - 1E-7));
+        let inv_preds = self.adds(&self.neg(&preds), T::one());
+        let inv_targets = self.adds(&self.neg(targets), T::one());
+
+        let grad = &inv_targets / &inv_preds - self.div(targets, &preds);
+        grad / T::from_usize(preds.len())
+    }
+}
+
+/// Binary cross entropy of `sigmoid(logits)`, computed without a separate sigmoid:
+/// `mean(max(logits, 0) - logits * targets + ln(1 + exp(-|logits|)))`.
+///
+/// Large logits neither overflow nor saturate the loss, and the gradient is `(sigmoid(logits) - targets) / len`.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, nn::BCEWithLogitsOp};
+///
+/// let device = CPU::new();
+///
+/// let logits = Matrix::from((&device, (1, 2), [0., -1000.]));
+/// let targets = Matrix::from((&device, (1, 2), [1., 1.]));
+///
+/// let (loss, grad) = device.bce_with_logits(&logits, &targets);
+/// assert!((loss - (2f64.ln() + 1000.) / 2.).abs() < 1e-9);
+/// assert!((grad[0] + 0.25).abs() < 1e-12);
+/// assert_eq!(grad[1], -0.5);
+/// ```
+pub trait BCEWithLogitsOp<T, S: Shape = (), D: Device = Self>: Device {
+    #[inline]
+    fn bce_with_logits(
+        &self,
+        logits: &Matrix<T, D, S>,
+        targets: &Matrix<T, D, S>,
+    ) -> (T, Matrix<T, Self, S>) {
+        (
+            self.bce_with_logits_loss(logits, targets),
+            self.bce_with_logits_grad(logits, targets),
+        )
+    }
+    fn bce_with_logits_loss(&self, logits: &Matrix<T, D, S>, targets: &Matrix<T, D, S>) -> T;
+    fn bce_with_logits_grad(
+        &self,
+        logits: &Matrix<T, D, S>,
+        targets: &Matrix<T, D, S>,
+    ) -> Matrix<T, Self, S>;
+}
+
+/// Computes `ln(1 + exp(logits))` as `max(logits, 0) + ln(1 + exp(-|logits|))`, which never overflows.
+fn softplus<'a, T, D, S>(device: &'a D, logits: &Matrix<T, D, S>) -> Matrix<'a, T, D, S>
+where
+    T: Float,
+    D: ClipOp<T, S> + BaseOps<T, S> + AdditionalOps<T, S> + FnsOps<T, S>,
+    S: Shape,
+{
+    let pos = device.clip(logits, T::zero(), T::as_generic(f64::INFINITY));
+
+    // -|x| = x - 2 * max(x, 0)
+    let neg_abs = device.sub(logits, &(&pos * T::two()));
+    let log_exp = device.ln(&device.exp(&neg_abs).adds(T::one()));
+    pos + log_exp
+}
+
+impl<T, D, S: Shape> BCEWithLogitsOp<T, S> for D
+where
+    T: Float,
+    D: ClipOp<T, S> + BaseOps<T, S> + AdditionalOps<T, S> + FnsOps<T, S> + SumOps<T, S>,
+{
+    fn bce_with_logits_loss(&self, logits: &Matrix<T, D, S>, targets: &Matrix<T, D, S>) -> T {
+        let loss = softplus(self, logits) - self.mul(logits, targets);
+        self.mean(&loss)
+    }
+
+    fn bce_with_logits_grad(
+        &self,
+        logits: &Matrix<T, D, S>,
+        targets: &Matrix<T, D, S>,
+    ) -> Matrix<T, Self, S> {
+        // logits - softplus(logits) <= 0, hence exp cannot overflow
+        let sigmoid = self.exp(&self.sub(logits, &softplus(self, logits)));
+        self.sub(&sigmoid, targets) / T::from_usize(logits.len())
+    }
+}
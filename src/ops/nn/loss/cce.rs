@@ -7,6 +7,8 @@ pub trait CCE<T> {
 
 impl<'a, T, D> Matrix<'a, T, D> where D: Device {}
 
+/// Expects probabilities, which are clipped to `[1e-7, 1 - 1e-7]`.
+/// For raw logits, [`SoftmaxCCEOp`](crate::nn::SoftmaxCCEOp) is numerically stable and has a simpler gradient.
 pub trait CCEOp<T, S: Shape = (), D = Self>: Device
 where
     D: Device,
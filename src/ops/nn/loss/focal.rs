@@ -0,0 +1,150 @@
+use crate::{AdditionalOps, BaseOps, ClipOp, FnsOps, Matrix, SumOps};
+use custos::{number::Float, Device, Shape};
+
+impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
+    /// Binary focal loss of the probabilities in `self`. See [`FocalOp`].
+    #[inline]
+    pub fn focal(&self, targets: &Matrix<T, D, S>, alpha: T, gamma: T) -> (T, Matrix<'a, T, D, S>)
+    where
+        D: FocalOp<T, S>,
+    {
+        self.device().focal(self, targets, alpha, gamma)
+    }
+
+    #[inline]
+    pub fn focal_loss(&self, targets: &Matrix<T, D, S>, alpha: T, gamma: T) -> T
+    where
+        D: FocalOp<T, S>,
+    {
+        self.device().focal_loss(self, targets, alpha, gamma)
+    }
+
+    #[inline]
+    pub fn focal_grad(&self, targets: &Matrix<T, D, S>, alpha: T, gamma: T) -> Matrix<'a, T, D, S>
+    where
+        D: FocalOp<T, S>,
+    {
+        self.device().focal_grad(self, targets, alpha, gamma)
+    }
+}
+
+/// Binary focal loss: `-mean(alpha_t * (1 - p_t)^gamma * ln(p_t))`,
+/// with `p_t = targets * preds + (1 - targets) * (1 - preds)` and `alpha_t = targets * alpha + (1 - targets) * (1 - alpha)`.
+///
+/// `gamma` down-weights well classified elements, `alpha` weights the positive class.
+/// With `gamma = 0` and `alpha = 0.5`, the loss is half of the [`BCEOp`](crate::nn::BCEOp) loss.
+/// Like `bce`, the loss is averaged over all elements and the probabilities are clipped to `[1e-7, 1 - 1e-7]`.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, nn::FocalOp};
+///
+/// let device = CPU::new();
+///
+/// let preds = Matrix::from((&device, (1, 2), [0.5, 0.9]));
+/// let targets = Matrix::from((&device, (1, 2), [1., 1.]));
+///
+/// let loss = device.focal_loss(&preds, &targets, 1., 2.);
+///
+/// // the confident prediction barely contributes
+/// let expected = (0.25 * 2f64.ln() + 0.01 * (1. / 0.9f64).ln()) / 2.;
+/// assert!((loss - expected).abs() < 1e-6);
+/// ```
+pub trait FocalOp<T, S: Shape = (), D: Device = Self>: Device {
+    #[inline]
+    fn focal(
+        &self,
+        preds: &Matrix<T, D, S>,
+        targets: &Matrix<T, D, S>,
+        alpha: T,
+        gamma: T,
+    ) -> (T, Matrix<T, Self, S>)
+    where
+        T: Copy,
+    {
+        (
+            self.focal_loss(preds, targets, alpha, gamma),
+            self.focal_grad(preds, targets, alpha, gamma),
+        )
+    }
+    fn focal_loss(
+        &self,
+        preds: &Matrix<T, D, S>,
+        targets: &Matrix<T, D, S>,
+        alpha: T,
+        gamma: T,
+    ) -> T;
+    fn focal_grad(
+        &self,
+        preds: &Matrix<T, D, S>,
+        targets: &Matrix<T, D, S>,
+        alpha: T,
+        gamma: T,
+    ) -> Matrix<T, Self, S>;
+}
+
+impl<T, D, S: Shape> FocalOp<T, S> for D
+where
+    T: Float,
+    D: ClipOp<T, S> + BaseOps<T, S> + AdditionalOps<T, S> + FnsOps<T, S> + SumOps<T, S>,
+{
+    fn focal_loss(
+        &self,
+        preds: &Matrix<T, D, S>,
+        targets: &Matrix<T, D, S>,
+        alpha: T,
+        gamma: T,
+    ) -> T {
+        let (p_t, alpha_t) = focal_terms(self, preds, targets, alpha);
+        let inv_p_t = self.adds(&self.neg(&p_t), T::one());
+
+        let loss = self.mul(&alpha_t, &self.powf(&inv_p_t, gamma)) * self.ln(&p_t);
+        self.mean(&loss.neg())
+    }
+
+    fn focal_grad(
+        &self,
+        preds: &Matrix<T, D, S>,
+        targets: &Matrix<T, D, S>,
+        alpha: T,
+        gamma: T,
+    ) -> Matrix<T, Self, S> {
+        let (p_t, alpha_t) = focal_terms(self, preds, targets, alpha);
+        let inv_p_t = self.adds(&self.neg(&p_t), T::one());
+
+        // d loss / d p_t = alpha_t * (gamma * (1 - p_t)^(gamma - 1) * ln(p_t) - (1 - p_t)^gamma / p_t)
+        let grad_p_t = self.powf(&inv_p_t, gamma - T::one()).muls(gamma) * self.ln(&p_t)
+            - &self.powf(&inv_p_t, gamma) / &p_t;
+
+        // d p_t / d preds = 2 * targets - 1
+        let sign = self.subs(&self.muls(targets, T::two()), T::one());
+
+        let grad = alpha_t * grad_p_t * sign;
+        grad / T::from_usize(preds.len())
+    }
+}
+
+/// Returns `p_t` (clipped) and `alpha_t`.
+fn focal_terms<'a, T, D, S>(
+    device: &'a D,
+    preds: &Matrix<T, D, S>,
+    targets: &Matrix<T, D, S>,
+    alpha: T,
+) -> (Matrix<'a, T, D, S>, Matrix<'a, T, D, S>)
+where
+    T: Float,
+    D: ClipOp<T, S> + BaseOps<T, S> + AdditionalOps<T, S> + FnsOps<T, S>,
+    S: Shape,
+{
+    let preds = device.clip(preds, T::as_generic(1E-7), T::as_generic(1. - 1E-7));
+    let inv_preds = device.adds(&device.neg(&preds), T::one());
+    let inv_targets = device.adds(&device.neg(targets), T::one());
+
+    let p_t = device.mul(targets, &preds) + &inv_targets * &inv_preds;
+    let alpha_t = device.adds(
+        &device.muls(targets, alpha * T::two() - T::one()),
+        T::one() - alpha,
+    );
+    (p_t, alpha_t)
+}
@@ -0,0 +1,199 @@
+use custos::{impl_stack, number::Float, Device, MainMemory, Shape, CPU};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+use custos::CDatatype;
+
+#[cfg(feature = "opencl")]
+use crate::cpu_exec_lhs_rhs_with;
+#[cfg(feature = "opencl")]
+use custos::OpenCL;
+
+#[cfg(feature = "cuda")]
+use crate::cu_to_cpu_lr_with;
+#[cfg(feature = "cuda")]
+use custos::CUDA;
+
+use crate::Matrix;
+
+impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
+    /// Multi-class hinge loss of the scores in `self`. See [`HingeOp`].
+    #[inline]
+    pub fn hinge<IS: Shape>(&self, targets: &Matrix<u32, D, IS>) -> (T, Matrix<'a, T, D, S>)
+    where
+        D: HingeOp<T, S>,
+    {
+        self.device().hinge(self, targets)
+    }
+
+    /// Multi-class squared hinge loss of the scores in `self`. See [`HingeOp`].
+    #[inline]
+    pub fn squared_hinge<IS: Shape>(&self, targets: &Matrix<u32, D, IS>) -> (T, Matrix<'a, T, D, S>)
+    where
+        D: HingeOp<T, S>,
+    {
+        self.device().squared_hinge(self, targets)
+    }
+}
+
+/// Multi-class (Weston-Watkins) hinge loss of SVM-style scores.
+///
+/// Every class `j` that is not the target class `y` of a row adds the margin violation `max(0, scores[j] - scores[y] + 1)`.
+/// `squared_hinge` adds the squared violations instead, which penalises large violations harder and is differentiable.
+/// The loss is averaged over the rows and `targets` holds one class index per row.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, nn::HingeOp};
+///
+/// let device = CPU::new();
+///
+/// let scores = Matrix::from((&device, (1, 3), [2., 2.5, 0.5]));
+/// let targets = Matrix::from((&device, (1, 1), [0u32]));
+///
+/// // only the second class violates the margin
+/// let (loss, grad) = device.hinge(&scores, &targets);
+/// assert_eq!(loss, 1.5);
+/// assert_eq!(grad.read(), vec![-1., 1., 0.]);
+///
+/// let (loss, grad) = device.squared_hinge(&scores, &targets);
+/// assert_eq!(loss, 2.25);
+/// assert_eq!(grad.read(), vec![-3., 3., 0.]);
+/// ```
+pub trait HingeOp<T, S: Shape = (), D: Device = Self>: Device {
+    fn hinge<IS: Shape>(
+        &self,
+        scores: &Matrix<T, D, S>,
+        targets: &Matrix<u32, D, IS>,
+    ) -> (T, Matrix<T, Self, S>);
+
+    fn squared_hinge<IS: Shape>(
+        &self,
+        scores: &Matrix<T, D, S>,
+        targets: &Matrix<u32, D, IS>,
+    ) -> (T, Matrix<T, Self, S>);
+}
+
+/// Multi-class hinge loss of row major scores with `cols` columns and one class index per row.
+/// If `squared` is true, the margin violations are squared.
+/// Writes the gradient into `grad` and returns the mean loss.
+pub fn hinge_slice<T: Float>(
+    scores: &[T],
+    targets: &[u32],
+    cols: usize,
+    squared: bool,
+    grad: &mut [T],
+) -> T {
+    let rows = scores.len() / cols;
+    let scale = T::one() / T::from_usize(rows);
+
+    let mut loss = T::default();
+    for ((scores, target), grad) in scores.chunks(cols).zip(targets).zip(grad.chunks_mut(cols)) {
+        let target = *target as usize;
+        assert!(
+            target < cols,
+            "class index {target} is out of range for {cols} classes"
+        );
+
+        let mut target_grad = T::default();
+        for (col, (score, grad)) in scores.iter().zip(grad.iter_mut()).enumerate() {
+            let margin = *score - scores[target] + T::one();
+
+            *grad = T::default();
+            if col == target || margin <= T::default() {
+                continue;
+            }
+
+            if squared {
+                loss += margin * margin;
+                *grad = T::two() * margin * scale;
+            } else {
+                loss += margin;
+                *grad = scale;
+            }
+            target_grad = target_grad - *grad;
+        }
+        grad[target] = target_grad;
+    }
+    loss * scale
+}
+
+#[impl_stack]
+impl<T: Float, D: MainMemory, S: Shape> HingeOp<T, S, D> for CPU {
+    fn hinge<IS: Shape>(
+        &self,
+        scores: &Matrix<T, D, S>,
+        targets: &Matrix<u32, D, IS>,
+    ) -> (T, Matrix<T, Self, S>) {
+        assert_eq!(scores.rows(), targets.len());
+
+        let mut grad = self.retrieve(scores.len(), (scores.node.idx, targets.node.idx));
+        let loss = hinge_slice(scores, targets, scores.cols(), false, &mut grad);
+        (loss, (grad, scores.dims()).into())
+    }
+
+    fn squared_hinge<IS: Shape>(
+        &self,
+        scores: &Matrix<T, D, S>,
+        targets: &Matrix<u32, D, IS>,
+    ) -> (T, Matrix<T, Self, S>) {
+        assert_eq!(scores.rows(), targets.len());
+
+        let mut grad = self.retrieve(scores.len(), (scores.node.idx, targets.node.idx));
+        let loss = hinge_slice(scores, targets, scores.cols(), true, &mut grad);
+        (loss, (grad, scores.dims()).into())
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: Float + CDatatype> HingeOp<T> for OpenCL {
+    fn hinge<IS: Shape>(
+        &self,
+        scores: &Matrix<T, Self>,
+        targets: &Matrix<u32, Self, IS>,
+    ) -> (T, Matrix<T, Self>) {
+        cpu_exec_lhs_rhs_with(self, scores, targets, |cpu, scores, targets| {
+            let (loss, grad) = cpu.hinge(scores, targets);
+            (loss, Matrix::from((self, grad)))
+        })
+    }
+
+    fn squared_hinge<IS: Shape>(
+        &self,
+        scores: &Matrix<T, Self>,
+        targets: &Matrix<u32, Self, IS>,
+    ) -> (T, Matrix<T, Self>) {
+        cpu_exec_lhs_rhs_with(self, scores, targets, |cpu, scores, targets| {
+            let (loss, grad) = cpu.squared_hinge(scores, targets);
+            (loss, Matrix::from((self, grad)))
+        })
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: Float + CDatatype> HingeOp<T> for CUDA {
+    fn hinge<IS: Shape>(
+        &self,
+        scores: &Matrix<T, Self>,
+        targets: &Matrix<u32, Self, IS>,
+    ) -> (T, Matrix<T, Self>) {
+        cu_to_cpu_lr_with(scores, targets, |cpu, scores, targets| {
+            let (loss, grad) = cpu.hinge(scores, targets);
+            (loss, Matrix::from((self, grad)))
+        })
+    }
+
+    fn squared_hinge<IS: Shape>(
+        &self,
+        scores: &Matrix<T, Self>,
+        targets: &Matrix<u32, Self, IS>,
+    ) -> (T, Matrix<T, Self>) {
+        cu_to_cpu_lr_with(scores, targets, |cpu, scores, targets| {
+            let (loss, grad) = cpu.squared_hinge(scores, targets);
+            (loss, Matrix::from((self, grad)))
+        })
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{AdditionalOps, BaseOps, ClipOp, FnsOps, Matrix, SumOps};
+use custos::{number::Float, Device, Shape};
+
+impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
+    /// Kullback-Leibler divergence of the distribution `q` from the log probabilities in `self`. See [`KLDivOp`].
+    #[inline]
+    pub fn kl_div(&self, q: &Matrix<T, D, S>) -> (T, Matrix<'a, T, D, S>)
+    where
+        D: KLDivOp<T, S>,
+    {
+        self.device().kl_div(self, q)
+    }
+
+    #[inline]
+    pub fn kl_div_loss(&self, q: &Matrix<T, D, S>) -> T
+    where
+        D: KLDivOp<T, S>,
+    {
+        self.device().kl_div_loss(self, q)
+    }
+
+    #[inline]
+    pub fn kl_div_grad(&self, q: &Matrix<T, D, S>) -> Matrix<'a, T, D, S>
+    where
+        D: KLDivOp<T, S>,
+    {
+        self.device().kl_div_grad(self, q)
+    }
+}
+
+/// Kullback-Leibler divergence `KL(q || p)` of every row, averaged over the rows:
+/// `sum(q * (ln(q) - log_p)) / rows`.
+///
+/// `log_p` are log probabilities, e.g. the output of `log_softmax`, and `q` are the target probabilities.
+/// Entries with `q = 0` do not contribute to the loss. The gradient with respect to `log_p` is `-q / rows`.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, nn::KLDivOp};
+///
+/// let device = CPU::new();
+///
+/// let log_p = Matrix::from((&device, (1, 2), [0.5f64.ln(), 0.5f64.ln()]));
+///
+/// // identical distributions do not diverge
+/// let q = Matrix::from((&device, (1, 2), [0.5, 0.5]));
+/// assert!(device.kl_div_loss(&log_p, &q).abs() < 1e-12);
+///
+/// let q = Matrix::from((&device, (1, 2), [1., 0.]));
+/// let (loss, grad) = device.kl_div(&log_p, &q);
+/// assert!((loss - 2f64.ln()).abs() < 1e-12);
+/// assert_eq!(grad.read(), vec![-1., 0.]);
+/// ```
+pub trait KLDivOp<T, S: Shape = (), D: Device = Self>: Device {
+    #[inline]
+    fn kl_div(&self, log_p: &Matrix<T, D, S>, q: &Matrix<T, D, S>) -> (T, Matrix<T, Self, S>) {
+        (self.kl_div_loss(log_p, q), self.kl_div_grad(log_p, q))
+    }
+    fn kl_div_loss(&self, log_p: &Matrix<T, D, S>, q: &Matrix<T, D, S>) -> T;
+    fn kl_div_grad(&self, log_p: &Matrix<T, D, S>, q: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
+}
+
+impl<T, D, S: Shape> KLDivOp<T, S> for D
+where
+    T: Float,
+    D: ClipOp<T, S> + BaseOps<T, S> + AdditionalOps<T, S> + FnsOps<T, S> + SumOps<T, S>,
+{
+    fn kl_div_loss(&self, log_p: &Matrix<T, D, S>, q: &Matrix<T, D, S>) -> T {
+        // q * ln(q) is 0 for q = 0. Clipping avoids 0 * ln(0) = NaN
+        let log_q = self.ln(&self.clip(q, T::as_generic(1E-30), T::as_generic(f64::INFINITY)));
+
+        let divergence = self.mul(q, &self.sub(&log_q, log_p));
+        self.sum(&divergence) / T::from_usize(log_p.rows())
+    }
+
+    fn kl_div_grad(&self, log_p: &Matrix<T, D, S>, q: &Matrix<T, D, S>) -> Matrix<T, Self, S> {
+        self.neg(q) / T::from_usize(log_p.rows())
+    }
+}
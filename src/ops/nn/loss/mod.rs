@@ -1,5 +1,19 @@
+mod bce;
 mod cce;
+mod focal;
+mod hinge;
+mod kl_div;
 mod mse;
+mod nll;
+mod regression;
+mod softmax_cce;
 
+pub use bce::*;
 pub use cce::*;
+pub use focal::*;
+pub use hinge::*;
+pub use kl_div::*;
 pub use mse::*;
+pub use nll::*;
+pub use regression::*;
+pub use softmax_cce::*;
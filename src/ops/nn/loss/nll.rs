@@ -0,0 +1,124 @@
+use custos::{impl_stack, number::Float, Device, MainMemory, Shape, CPU};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+use custos::CDatatype;
+
+#[cfg(feature = "opencl")]
+use crate::cpu_exec_lhs_rhs_with;
+#[cfg(feature = "opencl")]
+use custos::OpenCL;
+
+#[cfg(feature = "cuda")]
+use crate::cu_to_cpu_lr_with;
+#[cfg(feature = "cuda")]
+use custos::CUDA;
+
+use crate::Matrix;
+
+impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
+    /// Negative log likelihood of the log probabilities in `self`. See [`NLLOp`].
+    #[inline]
+    pub fn nll<IS: Shape>(&self, targets: &Matrix<u32, D, IS>) -> (T, Matrix<'a, T, D, S>)
+    where
+        D: NLLOp<T, S>,
+    {
+        self.device().nll(self, targets)
+    }
+}
+
+/// Negative log likelihood: `-mean(log_probs[row, targets[row]])`.
+///
+/// `log_probs` are log probabilities, e.g. the output of `log_softmax`, and `targets` holds one class index per row.
+/// The gradient is `-1 / rows` at the target class of every row and 0 everywhere else.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, nn::NLLOp};
+///
+/// let device = CPU::new();
+///
+/// let log_probs = Matrix::from((&device, (2, 3), [-0.5, -1., -2., -3., -0.25, -1.5]));
+/// let targets = Matrix::from((&device, (2, 1), [0u32, 2]));
+///
+/// let (loss, grad) = device.nll(&log_probs, &targets);
+/// assert_eq!(loss, 1.);
+/// assert_eq!(grad.read(), vec![-0.5, 0., 0., 0., 0., -0.5]);
+/// ```
+pub trait NLLOp<T, S: Shape = (), D: Device = Self>: Device {
+    fn nll<IS: Shape>(
+        &self,
+        log_probs: &Matrix<T, D, S>,
+        targets: &Matrix<u32, D, IS>,
+    ) -> (T, Matrix<T, Self, S>);
+}
+
+/// Negative log likelihood of row major log probabilities with `cols` columns and one class index per row.
+/// Writes the gradient into `grad` and returns the mean loss.
+pub fn nll_slice<T: Float>(log_probs: &[T], targets: &[u32], cols: usize, grad: &mut [T]) -> T {
+    let rows = log_probs.len() / cols;
+    let scale = T::one() / T::from_usize(rows);
+
+    for value in grad.iter_mut() {
+        *value = T::default();
+    }
+
+    let mut loss = T::default();
+    for (row, target) in targets.iter().enumerate() {
+        let target = *target as usize;
+        assert!(
+            target < cols,
+            "class index {target} is out of range for {cols} classes"
+        );
+
+        loss = loss - log_probs[row * cols + target];
+        grad[row * cols + target] = T::default() - scale;
+    }
+    loss * scale
+}
+
+#[impl_stack]
+impl<T: Float, D: MainMemory, S: Shape> NLLOp<T, S, D> for CPU {
+    fn nll<IS: Shape>(
+        &self,
+        log_probs: &Matrix<T, D, S>,
+        targets: &Matrix<u32, D, IS>,
+    ) -> (T, Matrix<T, Self, S>) {
+        assert_eq!(log_probs.rows(), targets.len());
+
+        let mut grad = self.retrieve(log_probs.len(), (log_probs.node.idx, targets.node.idx));
+        let loss = nll_slice(log_probs, targets, log_probs.cols(), &mut grad);
+        (loss, (grad, log_probs.dims()).into())
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: Float + CDatatype> NLLOp<T> for OpenCL {
+    fn nll<IS: Shape>(
+        &self,
+        log_probs: &Matrix<T, Self>,
+        targets: &Matrix<u32, Self, IS>,
+    ) -> (T, Matrix<T, Self>) {
+        cpu_exec_lhs_rhs_with(self, log_probs, targets, |cpu, log_probs, targets| {
+            let (loss, grad) = cpu.nll(log_probs, targets);
+            (loss, Matrix::from((self, grad)))
+        })
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: Float + CDatatype> NLLOp<T> for CUDA {
+    fn nll<IS: Shape>(
+        &self,
+        log_probs: &Matrix<T, Self>,
+        targets: &Matrix<u32, Self, IS>,
+    ) -> (T, Matrix<T, Self>) {
+        cu_to_cpu_lr_with(log_probs, targets, |cpu, log_probs, targets| {
+            let (loss, grad) = cpu.nll(log_probs, targets);
+            (loss, Matrix::from((self, grad)))
+        })
+    }
+}
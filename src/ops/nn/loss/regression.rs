@@ -0,0 +1,271 @@
+use crate::{nn::ActivationOps, AdditionalOps, BaseOps, ClipOp, FnsOps, Matrix, SumOps};
+use custos::{
+    number::{Float, Number},
+    Shape,
+};
+#[cfg(feature = "opencl")]
+use custos::{opencl::enqueue_kernel, CDatatype, OpenCL};
+
+// Like `mse`, every loss is averaged over all elements, hence the gradients are divided by `preds.len()`.
+
+/// Mean absolute error: `mean(|preds - targets|)`.
+#[inline]
+pub fn mae<'a, T, D, S>(
+    preds: &Matrix<'a, T, D, S>,
+    targets: &Matrix<'a, T, D, S>,
+) -> (T, Matrix<'a, T, D, S>)
+where
+    T: Float,
+    D: BaseOps<T, S> + SumOps<T, S> + AdditionalOps<T, S> + FnsOps<T, S> + ActivationOps<T, S>,
+    S: Shape,
+{
+    (mae_loss(preds, targets), mae_grad(preds, targets))
+}
+
+pub fn mae_loss<T, D, S>(preds: &Matrix<T, D, S>, targets: &Matrix<T, D, S>) -> T
+where
+    T: Float,
+    D: BaseOps<T, S> + SumOps<T, S> + FnsOps<T, S> + ActivationOps<T, S>,
+    S: Shape,
+{
+    let x = preds - targets;
+    (x.relu() + x.neg().relu()).mean()
+}
+
+/// The gradient at `preds == targets` is 0.
+pub fn mae_grad<'a, T, D, S>(
+    preds: &Matrix<'a, T, D, S>,
+    targets: &Matrix<'a, T, D, S>,
+) -> Matrix<'a, T, D, S>
+where
+    T: Float,
+    D: BaseOps<T, S> + AdditionalOps<T, S> + FnsOps<T, S> + ActivationOps<T, S>,
+    S: Shape,
+{
+    let x = preds - targets;
+
+    // relu_grad is 1 for x >= 0, hence the difference is the sign of x
+    let sign = x.relu_grad() - x.neg().relu_grad();
+    sign / T::from_usize(preds.len())
+}
+
+/// Huber loss: `0.5 * x^2` for `|x| <= delta`, otherwise `delta * (|x| - 0.5 * delta)`, with `x = preds - targets`.
+#[inline]
+pub fn huber<'a, T, D, S>(
+    preds: &Matrix<'a, T, D, S>,
+    targets: &Matrix<'a, T, D, S>,
+    delta: T,
+) -> (T, Matrix<'a, T, D, S>)
+where
+    T: Number,
+    D: BaseOps<T, S> + SumOps<T, S> + AdditionalOps<T, S> + ClipOp<T, S>,
+    S: Shape,
+{
+    (
+        huber_loss(preds, targets, delta),
+        huber_grad(preds, targets, delta),
+    )
+}
+
+pub fn huber_loss<T, D, S>(preds: &Matrix<T, D, S>, targets: &Matrix<T, D, S>, delta: T) -> T
+where
+    T: Number,
+    D: BaseOps<T, S> + SumOps<T, S> + AdditionalOps<T, S> + ClipOp<T, S>,
+    S: Shape,
+{
+    let x = preds - targets;
+    let clipped = x.clip(T::default() - delta, delta);
+
+    // clipped * (x - clipped / 2) is 0.5 * x^2 inside and delta * |x| - 0.5 * delta^2 outside of [-delta, delta]
+    (&clipped * &(&x - &(&clipped / T::two()))).mean()
+}
+
+pub fn huber_grad<'a, T, D, S>(
+    preds: &Matrix<'a, T, D, S>,
+    targets: &Matrix<'a, T, D, S>,
+    delta: T,
+) -> Matrix<'a, T, D, S>
+where
+    T: Number,
+    D: BaseOps<T, S> + AdditionalOps<T, S> + ClipOp<T, S>,
+    S: Shape,
+{
+    let x = preds - targets;
+    x.device().clip(&x, T::default() - delta, delta) / T::from_usize(preds.len())
+}
+
+/// Smooth L1 loss: `0.5 * x^2 / beta` for `|x| < beta`, otherwise `|x| - 0.5 * beta`, with `x = preds - targets`.
+///
+/// This is the Huber loss with `delta = beta`, divided by `beta`.
+/// Hence the gradient of large errors is ±1 like the one of [`mae`], independent of `beta`.
+#[inline]
+pub fn smooth_l1<'a, T, D, S>(
+    preds: &Matrix<'a, T, D, S>,
+    targets: &Matrix<'a, T, D, S>,
+    beta: T,
+) -> (T, Matrix<'a, T, D, S>)
+where
+    T: Number,
+    D: BaseOps<T, S> + SumOps<T, S> + AdditionalOps<T, S> + ClipOp<T, S>,
+    S: Shape,
+{
+    (
+        smooth_l1_loss(preds, targets, beta),
+        smooth_l1_grad(preds, targets, beta),
+    )
+}
+
+#[inline]
+pub fn smooth_l1_loss<T, D, S>(preds: &Matrix<T, D, S>, targets: &Matrix<T, D, S>, beta: T) -> T
+where
+    T: Number,
+    D: BaseOps<T, S> + SumOps<T, S> + AdditionalOps<T, S> + ClipOp<T, S>,
+    S: Shape,
+{
+    huber_loss(preds, targets, beta) / beta
+}
+
+#[inline]
+pub fn smooth_l1_grad<'a, T, D, S>(
+    preds: &Matrix<'a, T, D, S>,
+    targets: &Matrix<'a, T, D, S>,
+    beta: T,
+) -> Matrix<'a, T, D, S>
+where
+    T: Number,
+    D: BaseOps<T, S> + AdditionalOps<T, S> + ClipOp<T, S>,
+    S: Shape,
+{
+    huber_grad(preds, targets, beta) / beta
+}
+
+/// Log-cosh loss: `mean(ln(cosh(preds - targets)))`.
+///
+/// Behaves like `0.5 * x^2` for small and like `|x| - ln(2)` for large errors.
+/// The loss is computed as `|x| + ln(1 + exp(-2|x|)) - ln(2)`, hence `cosh` never overflows.
+#[inline]
+pub fn log_cosh<'a, T, D, S>(
+    preds: &Matrix<'a, T, D, S>,
+    targets: &Matrix<'a, T, D, S>,
+) -> (T, Matrix<'a, T, D, S>)
+where
+    T: Float,
+    D: BaseOps<T, S> + SumOps<T, S> + AdditionalOps<T, S> + FnsOps<T, S> + ActivationOps<T, S>,
+    S: Shape,
+{
+    (log_cosh_loss(preds, targets), log_cosh_grad(preds, targets))
+}
+
+pub fn log_cosh_loss<T, D, S>(preds: &Matrix<T, D, S>, targets: &Matrix<T, D, S>) -> T
+where
+    T: Float,
+    D: BaseOps<T, S> + SumOps<T, S> + AdditionalOps<T, S> + FnsOps<T, S> + ActivationOps<T, S>,
+    S: Shape,
+{
+    let x = preds - targets;
+    let abs = x.relu() + x.neg().relu();
+
+    let log_exp = abs.muls(T::as_generic(-2.)).exp().adds(T::one()).ln();
+    (abs + log_exp).mean() - T::as_generic(core::f64::consts::LN_2)
+}
+
+pub fn log_cosh_grad<'a, T, D, S>(
+    preds: &Matrix<'a, T, D, S>,
+    targets: &Matrix<'a, T, D, S>,
+) -> Matrix<'a, T, D, S>
+where
+    T: Float,
+    D: BaseOps<T, S> + AdditionalOps<T, S> + ActivationOps<T, S>,
+    S: Shape,
+{
+    let x = preds - targets;
+    x.tanh() / T::from_usize(preds.len())
+}
+
+/// Fuses `preds - targets`, the gradient `op` of the difference `x` and the division by the length into one kernel.
+/// `op` may use the scalar `param`.
+#[cfg(feature = "opencl")]
+fn cl_regression_grad<'a, T: CDatatype>(
+    device: &'a OpenCL,
+    preds: &Matrix<'a, T, OpenCL>,
+    targets: &Matrix<'a, T, OpenCL>,
+    op: &str,
+    param: T,
+) -> Matrix<'a, T, OpenCL> {
+    use custos::Device;
+
+    assert_eq!(preds.dims(), targets.dims());
+
+    let src = format!(
+        "
+        __kernel void regression_grad(__global const {datatype}* preds,
+            __global const {datatype}* targets,
+            __global {datatype}* out,
+            const {datatype} param, const {datatype} len)
+
+        {{
+            size_t id = get_global_id(0);
+
+            {datatype} x = preds[id] - targets[id];
+            out[id] = ({op}) / len;
+        }}
+    ",
+        datatype = T::as_c_type_str()
+    );
+
+    let out: custos::Buffer<T, OpenCL> =
+        device.retrieve(preds.len(), (preds.node.idx, targets.node.idx));
+    enqueue_kernel(
+        device,
+        &src,
+        [preds.len(), 0, 0],
+        None,
+        &[preds, targets, &out, &param, &T::from_usize(preds.len())],
+    )
+    .unwrap();
+    (out, preds.dims()).into()
+}
+
+#[cfg(feature = "opencl")]
+pub fn mae_grad_cl<'a, T: CDatatype>(
+    device: &'a OpenCL,
+    preds: &Matrix<'a, T, OpenCL>,
+    targets: &Matrix<'a, T, OpenCL>,
+) -> Matrix<'a, T, OpenCL> {
+    cl_regression_grad(device, preds, targets, "(x > 0) - (x < 0)", T::default())
+}
+
+#[cfg(feature = "opencl")]
+pub fn huber_grad_cl<'a, T: CDatatype>(
+    device: &'a OpenCL,
+    preds: &Matrix<'a, T, OpenCL>,
+    targets: &Matrix<'a, T, OpenCL>,
+    delta: T,
+) -> Matrix<'a, T, OpenCL> {
+    cl_regression_grad(device, preds, targets, "clamp(x, -param, param)", delta)
+}
+
+#[cfg(feature = "opencl")]
+pub fn smooth_l1_grad_cl<'a, T: CDatatype>(
+    device: &'a OpenCL,
+    preds: &Matrix<'a, T, OpenCL>,
+    targets: &Matrix<'a, T, OpenCL>,
+    beta: T,
+) -> Matrix<'a, T, OpenCL> {
+    cl_regression_grad(
+        device,
+        preds,
+        targets,
+        "clamp(x, -param, param) / param",
+        beta,
+    )
+}
+
+#[cfg(feature = "opencl")]
+pub fn log_cosh_grad_cl<'a, T: CDatatype>(
+    device: &'a OpenCL,
+    preds: &Matrix<'a, T, OpenCL>,
+    targets: &Matrix<'a, T, OpenCL>,
+) -> Matrix<'a, T, OpenCL> {
+    cl_regression_grad(device, preds, targets, "tanh(x)", T::default())
+}
@@ -0,0 +1,227 @@
+use custos::{impl_stack, number::Float, Device, MainMemory, Shape, CPU};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+use custos::CDatatype;
+
+#[cfg(feature = "opencl")]
+use crate::cpu_exec_lhs_rhs_with;
+#[cfg(feature = "opencl")]
+use custos::OpenCL;
+
+#[cfg(feature = "cuda")]
+use crate::cu_to_cpu_lr_with;
+#[cfg(feature = "cuda")]
+use custos::CUDA;
+
+use crate::{nn::log_sum_exp, Matrix};
+
+impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
+    /// Fused softmax and categorical cross entropy. See [`SoftmaxCCEOp`].
+    #[inline]
+    pub fn softmax_cce(&self, targets: &Matrix<T, D, S>) -> (T, Matrix<'a, T, D, S>)
+    where
+        D: SoftmaxCCEOp<T, S>,
+    {
+        self.device().softmax_cce(self, targets)
+    }
+
+    /// Fused softmax and categorical cross entropy with class index targets. See [`SoftmaxCCEOp`].
+    #[inline]
+    pub fn softmax_cce_idx<IS: Shape>(
+        &self,
+        targets: &Matrix<u32, D, IS>,
+    ) -> (T, Matrix<'a, T, D, S>)
+    where
+        D: SoftmaxCCEOp<T, S>,
+    {
+        self.device().softmax_cce_idx(self, targets)
+    }
+}
+
+/// Categorical cross entropy of the row wise softmax of `logits`.
+/// Returns the mean loss over all rows and the gradient with respect to the logits: `(softmax(logits) - targets) / rows`.
+///
+/// The probabilities are never clipped, as the loss is computed from the log softmax of the logits.
+/// `targets` is either a one-hot encoded matrix with the dimensions of `logits` or,
+/// for `softmax_cce_idx`, one class index per row, e.g. the output of `argmax_cols`.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::{CPU, Read};
+/// use custos_math::{Matrix, nn::SoftmaxCCEOp};
+///
+/// let device = CPU::new();
+///
+/// let logits = Matrix::from((&device, (2, 3), [0., 0., 0., 1000., 0., 0.,]));
+///
+/// let one_hot = Matrix::from((&device, (2, 3), [1., 0., 0., 1., 0., 0.,]));
+/// let (loss, grad) = device.softmax_cce(&logits, &one_hot);
+///
+/// let idx = Matrix::from((&device, (2, 1), [0u32, 0]));
+/// let (idx_loss, idx_grad) = device.softmax_cce_idx(&logits, &idx);
+///
+/// assert!((loss - 3f64.ln() / 2.).abs() < 1e-12);
+/// assert_eq!(loss, idx_loss);
+/// assert_eq!(grad.read(), idx_grad.read());
+/// ```
+pub trait SoftmaxCCEOp<T, S: Shape = (), D: Device = Self>: Device {
+    fn softmax_cce(
+        &self,
+        logits: &Matrix<T, D, S>,
+        targets: &Matrix<T, D, S>,
+    ) -> (T, Matrix<T, Self, S>);
+
+    fn softmax_cce_idx<IS: Shape>(
+        &self,
+        logits: &Matrix<T, D, S>,
+        targets: &Matrix<u32, D, IS>,
+    ) -> (T, Matrix<T, Self, S>);
+}
+
+/// Computes the loss of a single row and writes `(softmax(logits) - target) * scale` into `grad`.
+fn softmax_cce_row<T, F>(logits: &[T], grad: &mut [T], scale: T, target: F) -> T
+where
+    T: Float,
+    F: Fn(usize) -> T,
+{
+    let log_sum_exp = log_sum_exp(logits);
+
+    let mut loss = T::default();
+    for (col, (logit, grad)) in logits.iter().zip(grad).enumerate() {
+        let log_prob = *logit - log_sum_exp;
+        let target = target(col);
+
+        if target != T::default() {
+            loss = loss - target * log_prob;
+        }
+        *grad = (log_prob.exp() - target) * scale;
+    }
+    loss
+}
+
+/// Softmax cross entropy of row major logits with `cols` columns and one-hot `targets`.
+/// Writes the gradient into `grad` and returns the mean loss.
+pub fn softmax_cce_slice<T: Float>(logits: &[T], targets: &[T], cols: usize, grad: &mut [T]) -> T {
+    let rows = logits.len() / cols;
+    let scale = T::one() / T::from_usize(rows);
+
+    let mut loss = T::default();
+    for ((logits, targets), grad) in logits
+        .chunks(cols)
+        .zip(targets.chunks(cols))
+        .zip(grad.chunks_mut(cols))
+    {
+        loss += softmax_cce_row(logits, grad, scale, |col| targets[col]);
+    }
+    loss * scale
+}
+
+/// Softmax cross entropy of row major logits with `cols` columns and one class index per row.
+/// Writes the gradient into `grad` and returns the mean loss.
+pub fn softmax_cce_idx_slice<T: Float>(
+    logits: &[T],
+    targets: &[u32],
+    cols: usize,
+    grad: &mut [T],
+) -> T {
+    let rows = logits.len() / cols;
+    let scale = T::one() / T::from_usize(rows);
+
+    let mut loss = T::default();
+    for ((logits, target), grad) in logits.chunks(cols).zip(targets).zip(grad.chunks_mut(cols)) {
+        let target = *target as usize;
+        assert!(
+            target < cols,
+            "class index {target} is out of range for {cols} classes"
+        );
+
+        loss += softmax_cce_row(logits, grad, scale, |col| {
+            if col == target {
+                T::one()
+            } else {
+                T::default()
+            }
+        });
+    }
+    loss * scale
+}
+
+#[impl_stack]
+impl<T: Float, D: MainMemory, S: Shape> SoftmaxCCEOp<T, S, D> for CPU {
+    fn softmax_cce(
+        &self,
+        logits: &Matrix<T, D, S>,
+        targets: &Matrix<T, D, S>,
+    ) -> (T, Matrix<T, Self, S>) {
+        assert_eq!(logits.dims(), targets.dims());
+
+        let mut grad = self.retrieve(logits.len(), (logits.node.idx, targets.node.idx));
+        let loss = softmax_cce_slice(logits, targets, logits.cols(), &mut grad);
+        (loss, (grad, logits.dims()).into())
+    }
+
+    fn softmax_cce_idx<IS: Shape>(
+        &self,
+        logits: &Matrix<T, D, S>,
+        targets: &Matrix<u32, D, IS>,
+    ) -> (T, Matrix<T, Self, S>) {
+        assert_eq!(logits.rows(), targets.len());
+
+        let mut grad = self.retrieve(logits.len(), (logits.node.idx, targets.node.idx));
+        let loss = softmax_cce_idx_slice(logits, targets, logits.cols(), &mut grad);
+        (loss, (grad, logits.dims()).into())
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: Float + CDatatype> SoftmaxCCEOp<T> for OpenCL {
+    fn softmax_cce(
+        &self,
+        logits: &Matrix<T, Self>,
+        targets: &Matrix<T, Self>,
+    ) -> (T, Matrix<T, Self>) {
+        cpu_exec_lhs_rhs_with(self, logits, targets, |cpu, logits, targets| {
+            let (loss, grad) = cpu.softmax_cce(logits, targets);
+            (loss, Matrix::from((self, grad)))
+        })
+    }
+
+    fn softmax_cce_idx<IS: Shape>(
+        &self,
+        logits: &Matrix<T, Self>,
+        targets: &Matrix<u32, Self, IS>,
+    ) -> (T, Matrix<T, Self>) {
+        cpu_exec_lhs_rhs_with(self, logits, targets, |cpu, logits, targets| {
+            let (loss, grad) = cpu.softmax_cce_idx(logits, targets);
+            (loss, Matrix::from((self, grad)))
+        })
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: Float + CDatatype> SoftmaxCCEOp<T> for CUDA {
+    fn softmax_cce(
+        &self,
+        logits: &Matrix<T, Self>,
+        targets: &Matrix<T, Self>,
+    ) -> (T, Matrix<T, Self>) {
+        cu_to_cpu_lr_with(logits, targets, |cpu, logits, targets| {
+            let (loss, grad) = cpu.softmax_cce(logits, targets);
+            (loss, Matrix::from((self, grad)))
+        })
+    }
+
+    fn softmax_cce_idx<IS: Shape>(
+        &self,
+        logits: &Matrix<T, Self>,
+        targets: &Matrix<u32, Self, IS>,
+    ) -> (T, Matrix<T, Self>) {
+        cu_to_cpu_lr_with(logits, targets, |cpu, logits, targets| {
+            let (loss, grad) = cpu.softmax_cce_idx(logits, targets);
+            (loss, Matrix::from((self, grad)))
+        })
+    }
+}
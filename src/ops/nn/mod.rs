@@ -1,7 +1,17 @@
 mod activations;
+mod batch_norm;
+#[cfg(feature = "fastrand")]
+mod dropout;
+mod layer_norm;
 mod loss;
+mod optim;
 mod softmax;
 
 pub use activations::*;
+pub use batch_norm::*;
+#[cfg(feature = "fastrand")]
+pub use dropout::*;
+pub use layer_norm::*;
 pub use loss::*;
+pub use optim::*;
 pub use softmax::*;
@@ -0,0 +1,288 @@
+use crate::{
+    adagrad_step_slice, adam_step_slice, rmsprop_step_slice, sgd_momentum_step_slice, Matrix,
+};
+use custos::{impl_stack, number::Float, Device, MainMemory, Shape, CPU};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[cfg(feature = "opencl")]
+use crate::{cl_adagrad_step, cl_adam_step, cl_rmsprop_step, cl_sgd_momentum_step};
+#[cfg(feature = "opencl")]
+use custos::{CDatatype, OpenCL};
+
+impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
+    /// Updates the parameters in `self` in place. See [`OptimOps::sgd_momentum_step`].
+    #[inline]
+    pub fn sgd_momentum_step(
+        &mut self,
+        grad: &Matrix<T, D, S>,
+        velocity: &mut Matrix<T, D, S>,
+        lr: T,
+        mu: T,
+        nesterov: bool,
+    ) where
+        D: OptimOps<T, S>,
+    {
+        self.device()
+            .sgd_momentum_step(self, grad, velocity, lr, mu, nesterov)
+    }
+
+    /// Updates the parameters in `self` in place. See [`OptimOps::adam_step`].
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub fn adam_step(
+        &mut self,
+        grad: &Matrix<T, D, S>,
+        m: &mut Matrix<T, D, S>,
+        v: &mut Matrix<T, D, S>,
+        lr: T,
+        betas: (T, T),
+        eps: T,
+        t: usize,
+        weight_decay: T,
+    ) where
+        D: OptimOps<T, S>,
+    {
+        self.device()
+            .adam_step(self, grad, m, v, lr, betas, eps, t, weight_decay)
+    }
+
+    /// Updates the parameters in `self` in place. See [`OptimOps::rmsprop_step`].
+    #[inline]
+    pub fn rmsprop_step(
+        &mut self,
+        grad: &Matrix<T, D, S>,
+        sq_avg: &mut Matrix<T, D, S>,
+        lr: T,
+        alpha: T,
+        eps: T,
+    ) where
+        D: OptimOps<T, S>,
+    {
+        self.device()
+            .rmsprop_step(self, grad, sq_avg, lr, alpha, eps)
+    }
+
+    /// Updates the parameters in `self` in place. See [`OptimOps::adagrad_step`].
+    #[inline]
+    pub fn adagrad_step(
+        &mut self,
+        grad: &Matrix<T, D, S>,
+        sq_sum: &mut Matrix<T, D, S>,
+        lr: T,
+        eps: T,
+    ) where
+        D: OptimOps<T, S>,
+    {
+        self.device().adagrad_step(self, grad, sq_sum, lr, eps)
+    }
+}
+
+/// Fused, in-place parameter updates of gradient descent optimizers.
+///
+/// Every step updates `param` and the optimizer state (`velocity`, `m`, `v`, ...) with a single pass (or kernel) over the elements.
+/// The state has the dimensions of `param` and starts at zero, e.g. `Matrix::from((&device, param.dims(), vec![0.; param.len()]))`.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, nn::OptimOps};
+///
+/// let device = CPU::new();
+///
+/// let mut param = Matrix::from((&device, (1, 2), [1., -2.]));
+/// let grad = Matrix::from((&device, (1, 2), [0.5, -1.]));
+/// let mut velocity = Matrix::from((&device, (1, 2), [0., 0.]));
+///
+/// device.sgd_momentum_step(&mut param, &grad, &mut velocity, 0.1, 0.9, false);
+/// assert_eq!(velocity.read(), vec![0.5, -1.]);
+/// assert_eq!(param.read(), vec![0.95, -1.9]);
+///
+/// let mut m = Matrix::from((&device, (1, 2), [0., 0.]));
+/// let mut v = Matrix::from((&device, (1, 2), [0., 0.]));
+///
+/// // the first (bias corrected) adam step moves every parameter by about lr
+/// device.adam_step(&mut param, &grad, &mut m, &mut v, 0.1, (0.9, 0.999), 1e-8, 1, 0.);
+/// assert!((param[0] - 0.85).abs() < 1e-6);
+/// assert!((param[1] + 1.8).abs() < 1e-6);
+/// ```
+pub trait OptimOps<T, S: Shape = (), D: Device = Self>: Device {
+    /// SGD with momentum `mu`: `velocity = mu * velocity + grad`.
+    /// Then `param -= lr * velocity` or, if `nesterov` is set, `param -= lr * (grad + mu * velocity)`.
+    fn sgd_momentum_step(
+        &self,
+        param: &mut Matrix<T, D, S>,
+        grad: &Matrix<T, D, S>,
+        velocity: &mut Matrix<T, D, S>,
+        lr: T,
+        mu: T,
+        nesterov: bool,
+    );
+
+    /// Adam with the first and second moments `m` and `v`, `betas = (beta1, beta2)` and the (1-based) step count `t`,
+    /// which is used for the bias correction of the moments.
+    ///
+    /// `weight_decay` is decoupled from the gradient like in AdamW: `param -= lr * weight_decay * param`.
+    /// With a `weight_decay` of 0, this is plain Adam.
+    /// # Panics
+    /// If `t` is 0.
+    #[allow(clippy::too_many_arguments)]
+    fn adam_step(
+        &self,
+        param: &mut Matrix<T, D, S>,
+        grad: &Matrix<T, D, S>,
+        m: &mut Matrix<T, D, S>,
+        v: &mut Matrix<T, D, S>,
+        lr: T,
+        betas: (T, T),
+        eps: T,
+        t: usize,
+        weight_decay: T,
+    );
+
+    /// RMSprop with the moving average `sq_avg` of the squared gradients and the smoothing constant `alpha`.
+    fn rmsprop_step(
+        &self,
+        param: &mut Matrix<T, D, S>,
+        grad: &Matrix<T, D, S>,
+        sq_avg: &mut Matrix<T, D, S>,
+        lr: T,
+        alpha: T,
+        eps: T,
+    );
+
+    /// AdaGrad with the sum `sq_sum` of all squared gradients.
+    fn adagrad_step(
+        &self,
+        param: &mut Matrix<T, D, S>,
+        grad: &Matrix<T, D, S>,
+        sq_sum: &mut Matrix<T, D, S>,
+        lr: T,
+        eps: T,
+    );
+}
+
+fn assert_same_dims<T, D: Device, S: Shape>(param: &Matrix<T, D, S>, others: &[&Matrix<T, D, S>]) {
+    for other in others {
+        assert_eq!(
+            param.dims(),
+            other.dims(),
+            "The gradient and the optimizer state must have the dimensions of the parameters."
+        );
+    }
+}
+
+#[impl_stack]
+impl<T: Float, D: MainMemory, S: Shape> OptimOps<T, S, D> for CPU {
+    fn sgd_momentum_step(
+        &self,
+        param: &mut Matrix<T, D, S>,
+        grad: &Matrix<T, D, S>,
+        velocity: &mut Matrix<T, D, S>,
+        lr: T,
+        mu: T,
+        nesterov: bool,
+    ) {
+        assert_same_dims(param, &[grad, &*velocity]);
+        sgd_momentum_step_slice(param, grad, velocity, lr, mu, nesterov);
+    }
+
+    fn adam_step(
+        &self,
+        param: &mut Matrix<T, D, S>,
+        grad: &Matrix<T, D, S>,
+        m: &mut Matrix<T, D, S>,
+        v: &mut Matrix<T, D, S>,
+        lr: T,
+        betas: (T, T),
+        eps: T,
+        t: usize,
+        weight_decay: T,
+    ) {
+        assert_same_dims(param, &[grad, &*m, &*v]);
+        adam_step_slice(param, grad, m, v, lr, betas, eps, t, weight_decay);
+    }
+
+    fn rmsprop_step(
+        &self,
+        param: &mut Matrix<T, D, S>,
+        grad: &Matrix<T, D, S>,
+        sq_avg: &mut Matrix<T, D, S>,
+        lr: T,
+        alpha: T,
+        eps: T,
+    ) {
+        assert_same_dims(param, &[grad, &*sq_avg]);
+        rmsprop_step_slice(param, grad, sq_avg, lr, alpha, eps);
+    }
+
+    fn adagrad_step(
+        &self,
+        param: &mut Matrix<T, D, S>,
+        grad: &Matrix<T, D, S>,
+        sq_sum: &mut Matrix<T, D, S>,
+        lr: T,
+        eps: T,
+    ) {
+        assert_same_dims(param, &[grad, &*sq_sum]);
+        adagrad_step_slice(param, grad, sq_sum, lr, eps);
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: CDatatype + Float> OptimOps<T> for OpenCL {
+    fn sgd_momentum_step(
+        &self,
+        param: &mut Matrix<T, Self>,
+        grad: &Matrix<T, Self>,
+        velocity: &mut Matrix<T, Self>,
+        lr: T,
+        mu: T,
+        nesterov: bool,
+    ) {
+        assert_same_dims(param, &[grad, &*velocity]);
+        cl_sgd_momentum_step(self, param, grad, velocity, lr, mu, nesterov).unwrap();
+    }
+
+    fn adam_step(
+        &self,
+        param: &mut Matrix<T, Self>,
+        grad: &Matrix<T, Self>,
+        m: &mut Matrix<T, Self>,
+        v: &mut Matrix<T, Self>,
+        lr: T,
+        betas: (T, T),
+        eps: T,
+        t: usize,
+        weight_decay: T,
+    ) {
+        assert_same_dims(param, &[grad, &*m, &*v]);
+        cl_adam_step(self, param, grad, m, v, lr, betas, eps, t, weight_decay).unwrap();
+    }
+
+    fn rmsprop_step(
+        &self,
+        param: &mut Matrix<T, Self>,
+        grad: &Matrix<T, Self>,
+        sq_avg: &mut Matrix<T, Self>,
+        lr: T,
+        alpha: T,
+        eps: T,
+    ) {
+        assert_same_dims(param, &[grad, &*sq_avg]);
+        cl_rmsprop_step(self, param, grad, sq_avg, lr, alpha, eps).unwrap();
+    }
+
+    fn adagrad_step(
+        &self,
+        param: &mut Matrix<T, Self>,
+        grad: &Matrix<T, Self>,
+        sq_sum: &mut Matrix<T, Self>,
+        lr: T,
+        eps: T,
+    ) {
+        assert_same_dims(param, &[grad, &*sq_sum]);
+        cl_adagrad_step(self, param, grad, sq_sum, lr, eps).unwrap();
+    }
+}
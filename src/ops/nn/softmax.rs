@@ -1,32 +1,113 @@
 #[cfg(feature = "opencl")]
 use crate::{
     cl_diagflat,
-    ops::{cl_to_cpu_lr, cl_to_cpu_s},
+    ops::{cl_to_cpu_s, TransposeOp},
 };
-use crate::{
-    matrix_multiply::MatrixMultiply, ColOp, FnsOps, Matrix, MaxOps, SumOverOps, TransposeOp,
-};
-use custos::{number::Float, range, Device, GenericBlas, CPU};
+use crate::{matrix_multiply::MatrixMultiply, ColOp, FnsOps, Matrix, MaxOps, SumOverOps};
+use custos::{number::Float, Device, GenericBlas, MainMemory, Shape, CPU};
 #[cfg(feature = "opencl")]
-use custos::{CDatatype, OpenCL};
+use custos::{opencl::enqueue_kernel, prelude::CLBuffer, CDatatype, OpenCL};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
 
 #[cfg(feature = "cuda")]
 use crate::{cu_to_cpu_lr, cu_to_cpu_s};
 #[cfg(feature = "cuda")]
 use custos::CUDA;
 
-impl<'a, T, D: SoftmaxOps<T>> Matrix<'a, T, D> {
-    pub fn softmax(&self) -> Matrix<'a, T, D> {
+impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S>
+where
+    D: SoftmaxOps<T, S>,
+{
+    pub fn softmax(&self) -> Matrix<'a, T, D, S> {
         self.device().softmax(self)
     }
-    pub fn softmax_grad(&self, activated: &Matrix<T, D>) -> Matrix<'a, T, D> {
+    pub fn log_softmax(&self) -> Matrix<'a, T, D, S> {
+        self.device().log_softmax(self)
+    }
+    pub fn softmax_grad(&self, activated: &Matrix<T, D, S>) -> Matrix<'a, T, D, S> {
         self.device().softmax_grad(activated, self)
     }
 }
 
-pub trait SoftmaxOps<T, D: Device = Self>: Device {
-    fn softmax(&self, inputs: &Matrix<T, D>) -> Matrix<T, Self>;
-    fn softmax_grad(&self, activated: &Matrix<T, D>, grads: &Matrix<T, D>) -> Matrix<T, Self>;
+pub trait SoftmaxOps<T, S: Shape = (), D: Device = Self>: Device {
+    fn softmax(&self, inputs: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
+    /// Row wise `ln(softmax(x))`, computed as `x - max - ln(sum(exp(x - max)))`.
+    /// Unlike `softmax(x).ln()`, this does not produce `-inf` for very small probabilities.
+    fn log_softmax(&self, inputs: &Matrix<T, D, S>) -> Matrix<T, Self, S>;
+    /// Backpropagates `grads` through a row wise softmax.
+    /// `activated` is the output of [`SoftmaxOps::softmax`].
+    ///
+    /// The product of every row with the softmax jacobian `diag(s) - s * s^T` is computed in closed form:
+    /// `grads * s - s * sum(grads * s)`.
+    fn softmax_grad(
+        &self,
+        activated: &Matrix<T, D, S>,
+        grads: &Matrix<T, D, S>,
+    ) -> Matrix<T, Self, S>;
+}
+
+/// Row wise softmax of a slice with `cols` columns.
+pub fn softmax_slice<T: Float>(x: &[T], cols: usize, out: &mut [T]) {
+    for (x, out) in x.chunks(cols).zip(out.chunks_mut(cols)) {
+        let max = x
+            .iter()
+            .copied()
+            .reduce(|max, value| if value > max { value } else { max })
+            .unwrap_or_default();
+
+        let mut sum = T::default();
+        for (out, x) in out.iter_mut().zip(x) {
+            *out = (*x - max).exp();
+            sum += *out;
+        }
+
+        for out in out.iter_mut() {
+            *out = *out / sum;
+        }
+    }
+}
+
+/// Row wise log softmax of a slice with `cols` columns.
+pub fn log_softmax_slice<T: Float>(x: &[T], cols: usize, out: &mut [T]) {
+    for (x, out) in x.chunks(cols).zip(out.chunks_mut(cols)) {
+        let log_sum_exp = log_sum_exp(x);
+
+        for (out, x) in out.iter_mut().zip(x) {
+            *out = *x - log_sum_exp;
+        }
+    }
+}
+
+/// Computes `ln(sum(exp(x)))` of a row. The maximum is subtracted before exponentiating to avoid overflows.
+pub fn log_sum_exp<T: Float>(x: &[T]) -> T {
+    let max = x
+        .iter()
+        .copied()
+        .reduce(|max, value| if value > max { value } else { max })
+        .unwrap_or_default();
+
+    let sum = x
+        .iter()
+        .fold(T::default(), |sum, value| sum + (*value - max).exp());
+    max + sum.ln()
+}
+
+/// Row wise softmax gradient of slices with `cols` columns: `grads * s - s * sum(grads * s)`.
+pub fn softmax_grad_slice<T: Float>(activated: &[T], grads: &[T], cols: usize, out: &mut [T]) {
+    let rows = activated.chunks(cols).zip(grads.chunks(cols));
+
+    for ((activated, grads), out) in rows.zip(out.chunks_mut(cols)) {
+        let dot = activated
+            .iter()
+            .zip(grads)
+            .fold(T::default(), |acc, (s, grad)| acc + *s * *grad);
+
+        for ((out, s), grad) in out.iter_mut().zip(activated).zip(grads) {
+            *out = *s * (*grad - dot);
+        }
+    }
 }
 
 #[cfg(feature = "cpu")]
@@ -39,78 +120,45 @@ where
         self.div_col(&exp, &self.sum_cols(&exp))
     }
 
-    #[cfg(not(feature = "safe"))]
-    fn softmax_grad(&self, activated: &Matrix<T>, grads: &Matrix<T>) -> Matrix<T> {
-        use custos::Cache;
-
-        use crate::{BaseOps, Gemm};
-
-        let mut data: Matrix<T> = (Cache::get(self, grads.len(), ()), grads.dims()).into();
-
-        let rows = grads.rows();
-        let cols = grads.cols();
-
-        for idx in range(rows - 1) {
-            let index = idx * cols;
-
-            let single_out = Matrix::from((
-                self,
-                (&activated[index..index + cols]).as_ptr() as *mut T,
-                (cols, 1),
-            ));
-            let single_grad = Matrix::from((
-                self,
-                (&grads[index..index + cols]).as_ptr() as *mut T,
-                (cols, 1),
-            ));
-
-            let diagflat = single_out.diagflat();
-
-            // cols 1 x 1 cols
-            let jacobian_matrix =
-                self.sub(&diagflat, &self.gemm(&single_out, &single_out.T::<()>()));
-
-            //GenericBlas::gemm();
-            let res: Matrix<T> = jacobian_matrix.gemm(&single_grad);
-
-            let data_row = &mut data[index..index + cols];
-            data_row.copy_from_slice(&res);
-        }
-        data
+    fn log_softmax(&self, inputs: &Matrix<T>) -> Matrix<T> {
+        let mut out = self.retrieve(inputs.len(), inputs.node.idx);
+        log_softmax_slice(inputs, inputs.cols(), &mut out);
+        (out, inputs.dims()).into()
     }
 
-    #[cfg(feature = "safe")]
     fn softmax_grad(&self, activated: &Matrix<T>, grads: &Matrix<T>) -> Matrix<T> {
-        use crate::{BaseOps, Gemm};
+        assert_eq!(activated.dims(), grads.dims());
 
-        let device = CPU::new();
-        let mut data = cached(self, grads.dims());
-
-        let rows = grads.rows();
-        let cols = grads.cols();
-
-        for idx in range(rows - 1) {
-            let index = idx * cols;
-
-            let single_out =
-                Matrix::from((&device, (cols, 1), &activated[index..index + cols].to_vec()));
-
-            let single_grad =
-                Matrix::from((&device, (cols, 1), &grads[index..index + cols].to_vec()));
+        let mut out = self.retrieve(grads.len(), (activated.node.idx, grads.node.idx));
+        softmax_grad_slice(activated, grads, grads.cols(), &mut out);
+        (out, grads.dims()).into()
+    }
+}
 
-            let diagflat = self.diagflat(&single_out);
+#[cfg(feature = "stack")]
+impl<T: Float, D: MainMemory, S: Shape> SoftmaxOps<T, S, D> for Stack {
+    fn softmax(&self, inputs: &Matrix<T, D, S>) -> Matrix<T, Self, S> {
+        let mut out = self.retrieve(inputs.len(), inputs.node.idx);
+        softmax_slice(inputs, inputs.cols(), &mut out);
+        (out, inputs.dims()).into()
+    }
 
-            let jacobian_matrix = self.sub(
-                &diagflat,
-                &self.gemm(&single_out, &self.transpose(&single_out)),
-            );
-            //cols cols x cols 1
-            let res = self.gemm(&jacobian_matrix, &single_grad);
+    fn log_softmax(&self, inputs: &Matrix<T, D, S>) -> Matrix<T, Self, S> {
+        let mut out = self.retrieve(inputs.len(), inputs.node.idx);
+        log_softmax_slice(inputs, inputs.cols(), &mut out);
+        (out, inputs.dims()).into()
+    }
 
-            let data_row = &mut data[index..index + cols];
-            data_row.copy_from_slice(res.as_slice());
-        }
-        data
+    fn softmax_grad(
+        &self,
+        activated: &Matrix<T, D, S>,
+        grads: &Matrix<T, D, S>,
+    ) -> Matrix<T, Self, S> {
+        assert_eq!(activated.dims(), grads.dims());
+
+        let mut out = self.retrieve(grads.len(), (activated.node.idx, grads.node.idx));
+        softmax_grad_slice(activated, grads, grads.cols(), &mut out);
+        (out, grads.dims()).into()
     }
 }
 
@@ -120,6 +168,10 @@ impl<T: GenericBlas + MatrixMultiply + Float> SoftmaxOps<T> for CUDA {
         cu_to_cpu_s(self, inputs, |cpu, x| cpu.softmax(&x))
     }
 
+    fn log_softmax(&self, inputs: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cu_to_cpu_s(self, inputs, |cpu, x| cpu.log_softmax(&x))
+    }
+
     fn softmax_grad(
         &self,
         activated: &Matrix<T, Self>,
@@ -133,22 +185,59 @@ impl<T: GenericBlas + MatrixMultiply + Float> SoftmaxOps<T> for CUDA {
 
 #[cfg(feature = "opencl")]
 // TODO: Softmax running on the opencl device
-impl<T: GenericBlas + MatrixMultiply + Float> SoftmaxOps<T> for OpenCL {
+impl<T: GenericBlas + MatrixMultiply + Float + CDatatype> SoftmaxOps<T> for OpenCL {
     fn softmax(&self, inputs: &Matrix<T, Self>) -> Matrix<T, Self> {
         cl_to_cpu_s(self, inputs, |device, inputs| device.softmax(inputs))
     }
 
+    fn log_softmax(&self, inputs: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cl_to_cpu_s(self, inputs, |device, inputs| device.log_softmax(inputs))
+    }
+
     fn softmax_grad(
         &self,
         activated: &Matrix<T, Self>,
         grads: &Matrix<T, Self>,
     ) -> Matrix<T, Self> {
-        cl_to_cpu_lr(self, activated, grads, |device, activated, grads| {
-            device.softmax_grad(activated, grads)
-        })
+        assert_eq!(activated.dims(), grads.dims());
+
+        let out = cl_softmax_grad(self, activated, grads, grads.rows(), grads.cols()).unwrap();
+        (out, grads.dims()).into()
     }
 }
 
+/// Computes the softmax gradient `grads * s - s * sum(grads * s)` with one work item per row.
+#[cfg(feature = "opencl")]
+pub fn cl_softmax_grad<'a, T: CDatatype>(
+    device: &'a OpenCL,
+    activated: &CLBuffer<T>,
+    grads: &CLBuffer<T>,
+    rows: usize,
+    cols: usize,
+) -> custos::Result<CLBuffer<'a, T>> {
+    let src = format!(
+        "
+        __kernel void softmax_grad(__global const {datatype}* activated, __global const {datatype}* grads, __global {datatype}* out) {{
+            size_t idx = get_global_id(0) * {cols};
+
+            {datatype} dot = 0;
+            for (size_t col = 0; col < {cols}; col++) {{
+                dot += activated[idx + col] * grads[idx + col];
+            }}
+
+            for (size_t col = 0; col < {cols}; col++) {{
+                out[idx + col] = activated[idx + col] * (grads[idx + col] - dot);
+            }}
+        }}
+    ",
+        datatype = T::as_c_type_str()
+    );
+
+    let out: CLBuffer<T> = device.retrieve(rows * cols, (activated.node.idx, grads.node.idx));
+    enqueue_kernel(device, &src, [rows, 0, 0], None, &[activated, grads, &out])?;
+    Ok(out)
+}
+
 #[cfg(feature = "opencl")]
 pub fn cl_softmax<'a, T: CDatatype>(
     device: &'a OpenCL,
@@ -0,0 +1,210 @@
+use custos::{impl_stack, number::Float, Buffer, Device, MainMemory, Shape, CPU};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+use custos::CDatatype;
+
+#[cfg(feature = "opencl")]
+use custos::OpenCL;
+
+#[cfg(feature = "opencl")]
+use crate::{
+    cl_cond, cl_elementwise_norm, cl_frobenius_norm, cl_inf_norm, cl_l1_norm, cl_spectral_norm,
+    cl_trace,
+};
+
+#[cfg(feature = "cuda")]
+use crate::cu_to_cpu_scalar;
+#[cfg(feature = "cuda")]
+use custos::CUDA;
+
+use crate::{
+    elementwise_norm_slice, frobenius_norm_slice, inf_norm_slice, l1_norm_slice,
+    spectral_norm_slice, svd_slice, trace_slice, Matrix,
+};
+
+/// The matrix norm that is computed by [`NormOps::norm`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Norm<T> {
+    /// The square root of the sum of all squared values.
+    Frobenius,
+    /// The maximum absolute column sum.
+    L1,
+    /// The maximum absolute row sum.
+    Inf,
+    /// The largest singular value, estimated with a power iteration.
+    /// The estimate converges slowly if the two largest singular values are close to each other.
+    Spectral,
+    /// `(sum |x|^p)^(1/p)` over all values, where `p` must be positive.
+    Elementwise(T),
+}
+
+impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
+    /// Computes the matrix norm `norm` of `self`.
+    /// # Example
+    #[cfg_attr(feature = "cpu", doc = "```")]
+    #[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+    /// use custos::CPU;
+    /// use custos_math::{Matrix, Norm};
+    ///
+    /// let device = CPU::new();
+    /// let x = Matrix::from((&device, (2, 2), [1., -2., 3., 4.]));
+    ///
+    /// assert_eq!(x.norm(Norm::L1), 6.);
+    /// assert_eq!(x.norm(Norm::Inf), 7.);
+    /// assert_eq!(x.norm(Norm::Elementwise(1.)), 10.);
+    /// assert!((x.norm(Norm::Frobenius) - 30f64.sqrt()).abs() < 1e-12);
+    /// ```
+    #[inline]
+    pub fn norm(&self, norm: Norm<T>) -> T
+    where
+        D: NormOps<T, S>,
+    {
+        self.device().norm(self, norm)
+    }
+
+    /// The sum of the main diagonal of `self`. See [`NormOps::trace`].
+    #[inline]
+    pub fn trace(&self) -> T
+    where
+        D: NormOps<T, S>,
+    {
+        self.device().trace(self)
+    }
+
+    /// The condition number of `self`. See [`NormOps::cond`].
+    #[inline]
+    pub fn cond(&self) -> T
+    where
+        D: NormOps<T, S>,
+    {
+        self.device().cond(self)
+    }
+}
+
+/// Matrix norms and related scalars, e.g. for convergence checks or for monitoring gradient norms.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, Norm, NormOps};
+///
+/// let device = CPU::new();
+/// let x = Matrix::from((&device, (2, 2), [3., 0., 0., -0.5]));
+///
+/// assert!((device.norm(&x, Norm::Spectral) - 3.).abs() < 1e-12);
+/// assert_eq!(device.trace(&x), 2.5);
+/// assert!((device.cond(&x) - 6.).abs() < 1e-12);
+/// ```
+pub trait NormOps<T, S: Shape = (), D: Device = Self>: Device {
+    /// Computes the matrix norm `norm` of `x`.
+    fn norm(&self, x: &Matrix<T, D, S>, norm: Norm<T>) -> T;
+
+    /// The sum of the main diagonal of `x`. Rectangular matrices are supported.
+    fn trace(&self, x: &Matrix<T, D, S>) -> T;
+
+    /// The condition number of `x` in the spectral norm, i.e. the ratio of the largest and the smallest singular value.
+    /// The singular values are computed with [`SvdOps`](crate::SvdOps), the OpenCL implementation estimates them iteratively.
+    /// Returns infinity if `x` is (numerically) rank deficient.
+    fn cond(&self, x: &Matrix<T, D, S>) -> T;
+}
+
+#[impl_stack]
+impl<T: Float, D: MainMemory, S: Shape> NormOps<T, S, D> for CPU {
+    fn norm(&self, x: &Matrix<T, D, S>, norm: Norm<T>) -> T {
+        let (rows, cols) = x.dims();
+
+        match norm {
+            Norm::Frobenius => frobenius_norm_slice(x),
+            Norm::L1 => l1_norm_slice(x, rows, cols),
+            Norm::Inf => inf_norm_slice(x, rows, cols),
+            Norm::Elementwise(p) => elementwise_norm_slice(x, p),
+            Norm::Spectral => {
+                if rows == 0 || cols == 0 {
+                    return T::default();
+                }
+
+                // v and w need at most rows * cols elements each
+                let mut v: Buffer<T, Self, S> = self.retrieve(x.len(), x.node.idx);
+                let mut w: Buffer<T, Self, S> = self.retrieve(x.len(), x.node.idx);
+                spectral_norm_slice(x, rows, cols, &mut v[..cols], &mut w[..rows])
+            }
+        }
+    }
+
+    #[inline]
+    fn trace(&self, x: &Matrix<T, D, S>) -> T {
+        trace_slice(x, x.rows(), x.cols())
+    }
+
+    fn cond(&self, x: &Matrix<T, D, S>) -> T {
+        let (m, n) = x.dims();
+        let k = m.min(n);
+        if k == 0 {
+            return T::default();
+        }
+
+        let mut u: Buffer<T, Self, S> = self.retrieve(x.len(), x.node.idx);
+        let mut sigma: Buffer<T, Self, S> = self.retrieve(x.len(), x.node.idx);
+        let mut vt: Buffer<T, Self, S> = self.retrieve(x.len(), x.node.idx);
+        svd_slice(x, m, n, &mut u[..m * k], &mut sigma[..k], &mut vt[..k * n]);
+
+        // singular values below the rank tolerance are flushed to zero
+        if sigma[k - 1] == T::default() {
+            return T::one() / T::default();
+        }
+        sigma[0] / sigma[k - 1]
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: Float + CDatatype> NormOps<T> for OpenCL {
+    fn norm(&self, x: &Matrix<T, Self>, norm: Norm<T>) -> T {
+        let (rows, cols) = x.dims();
+
+        match norm {
+            Norm::Frobenius => cl_frobenius_norm(self, x),
+            Norm::L1 => cl_l1_norm(self, x, rows, cols),
+            Norm::Inf => cl_inf_norm(self, x, rows, cols),
+            Norm::Elementwise(p) => cl_elementwise_norm(self, x, p),
+            Norm::Spectral => {
+                if rows == 0 || cols == 0 {
+                    return T::default();
+                }
+                cl_spectral_norm(self, x, rows, cols)
+            }
+        }
+        .unwrap()
+    }
+
+    #[inline]
+    fn trace(&self, x: &Matrix<T, Self>) -> T {
+        cl_trace(self, x, x.rows(), x.cols()).unwrap()
+    }
+
+    /// Estimates the extreme singular values on the device, see [`cl_cond`].
+    #[inline]
+    fn cond(&self, x: &Matrix<T, Self>) -> T {
+        cl_cond(self, x, x.rows(), x.cols()).unwrap()
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: Float + CDatatype> NormOps<T> for CUDA {
+    #[inline]
+    fn norm(&self, x: &Matrix<T, Self>, norm: Norm<T>) -> T {
+        cu_to_cpu_scalar(x, |cpu, x| cpu.norm(&x, norm))
+    }
+
+    #[inline]
+    fn trace(&self, x: &Matrix<T, Self>) -> T {
+        cu_to_cpu_scalar(x, |cpu, x| cpu.trace(&x))
+    }
+
+    #[inline]
+    fn cond(&self, x: &Matrix<T, Self>) -> T {
+        cu_to_cpu_scalar(x, |cpu, x| cpu.cond(&x))
+    }
+}
@@ -0,0 +1,356 @@
+use crate::{
+    avg_pool2d_grad_mut, avg_pool2d_mut, max_pool2d_grad_mut, max_pool2d_mut, ConvConfig, Matrix,
+};
+use custos::{impl_stack, number::Number, Device, MainMemory, Shape, CPU};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[cfg(feature = "opencl")]
+use crate::{cl_avg_pool2d, cl_avg_pool2d_grad, cl_max_pool2d, cl_max_pool2d_grad};
+#[cfg(feature = "opencl")]
+use custos::{CDatatype, OpenCL};
+
+impl<'a, T, D: Device, IS: Shape> Matrix<'a, T, D, IS> {
+    /// Returns the pooled matrix and the indices of the maxima. See [`PoolOps`].
+    #[inline]
+    pub fn max_pool2d<OS: Shape>(
+        &self,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> (Matrix<'a, T, D, OS>, Matrix<'a, u32, D, OS>)
+    where
+        D: PoolOps<T, IS, OS>,
+    {
+        self.device()
+            .max_pool2d(self, img_dims, kernel_dims, config)
+    }
+
+    #[inline]
+    pub fn avg_pool2d<OS: Shape>(
+        &self,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<'a, T, D, OS>
+    where
+        D: PoolOps<T, IS, OS>,
+    {
+        self.device()
+            .avg_pool2d(self, img_dims, kernel_dims, config)
+    }
+}
+
+impl<'a, T, D: Device, OS: Shape> Matrix<'a, T, D, OS> {
+    /// `self` are the gradients of the max pooling output.
+    #[inline]
+    pub fn max_pool2d_grad<IS: Shape>(
+        &self,
+        indices: &Matrix<u32, D, OS>,
+        img_dims: (usize, usize),
+    ) -> Matrix<'a, T, D, IS>
+    where
+        D: PoolOps<T, IS, OS>,
+    {
+        self.device().max_pool2d_grad(self, indices, img_dims)
+    }
+
+    /// `self` are the gradients of the average pooling output.
+    #[inline]
+    pub fn avg_pool2d_grad<IS: Shape>(
+        &self,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<'a, T, D, IS>
+    where
+        D: PoolOps<T, IS, OS>,
+    {
+        self.device()
+            .avg_pool2d_grad(self, img_dims, kernel_dims, config)
+    }
+}
+
+/// 2D max and average pooling.
+///
+/// Like [`Im2ColOps`](crate::Im2ColOps), the input stores one channel per row: `channels` x (`img_dims.0` * `img_dims.1`).
+/// The output has the dimensions `channels` x (`out_rows` * `out_cols`),
+/// with (`out_rows`, `out_cols`) = `config.out_dims(img_dims, kernel_dims)`.
+/// The stride, padding and dilation of the windows are taken from `config`.
+///
+/// `max_pool2d` never selects padding entries and additionally returns the position of every maximum inside its channel,
+/// which `max_pool2d_grad` uses to scatter the gradients back.
+/// `avg_pool2d` treats padding entries as zeros, hence every window is divided by the kernel size.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{ConvConfig, Matrix, PoolOps};
+///
+/// let device = CPU::new();
+///
+/// let x = Matrix::from((&device, (1, 16), [
+///     1., 2., 5., 6.,
+///     3., 4., 8., 7.,
+///     0., -1., 2., 2.,
+///     -2., -3., 2., 2.,
+/// ]));
+///
+/// let config = ConvConfig::new((2, 2), (0, 0), (1, 1));
+///
+/// let (out, indices) = x.max_pool2d::<()>((4, 4), (2, 2), config);
+/// assert_eq!(out.dims(), (1, 4));
+/// assert_eq!(out.read(), vec![4., 8., 0., 2.]);
+/// assert_eq!(indices.read(), vec![5, 6, 8, 10]);
+///
+/// let grads = Matrix::from((&device, (1, 4), [1., 2., 3., 4.]));
+/// let dx = grads.max_pool2d_grad::<()>(&indices, (4, 4));
+/// assert_eq!(dx.read(), vec![
+///     0., 0., 0., 0.,
+///     0., 1., 2., 0.,
+///     3., 0., 4., 0.,
+///     0., 0., 0., 0.,
+/// ]);
+///
+/// let out: Matrix<f64> = device.avg_pool2d(&x, (4, 4), (2, 2), config);
+/// assert_eq!(out.read(), vec![2.5, 6.5, -1.5, 2.]);
+/// ```
+pub trait PoolOps<T, IS: Shape = (), OS: Shape = (), D: Device = Self>: Device {
+    fn max_pool2d(
+        &self,
+        x: &Matrix<T, D, IS>,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> (Matrix<T, Self, OS>, Matrix<u32, Self, OS>);
+
+    /// Gradient of [`PoolOps::max_pool2d`] with respect to the input.
+    /// `indices` are the indices returned by the forward pass.
+    fn max_pool2d_grad(
+        &self,
+        grads: &Matrix<T, D, OS>,
+        indices: &Matrix<u32, D, OS>,
+        img_dims: (usize, usize),
+    ) -> Matrix<T, Self, IS>;
+
+    fn avg_pool2d(
+        &self,
+        x: &Matrix<T, D, IS>,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T, Self, OS>;
+
+    /// Gradient of [`PoolOps::avg_pool2d`] with respect to the input.
+    fn avg_pool2d_grad(
+        &self,
+        grads: &Matrix<T, D, OS>,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T, Self, IS>;
+}
+
+fn pool_dims(
+    x_dims: (usize, usize),
+    img_dims: (usize, usize),
+    kernel_dims: (usize, usize),
+    config: &ConvConfig,
+) -> (usize, usize) {
+    assert_eq!(
+        x_dims.1,
+        img_dims.0 * img_dims.1,
+        "Every row must hold one channel of the image."
+    );
+    assert!(
+        config.dilation.0 > 0 && config.dilation.1 > 0,
+        "The dilation of a pooling window must be positive."
+    );
+    let (out_rows, out_cols) = config.out_dims(img_dims, kernel_dims);
+    let rows_overlap = windows_overlap(
+        img_dims.0,
+        kernel_dims.0,
+        out_rows,
+        config.stride.0,
+        config.padding.0,
+        config.dilation.0,
+    );
+    let cols_overlap = windows_overlap(
+        img_dims.1,
+        kernel_dims.1,
+        out_cols,
+        config.stride.1,
+        config.padding.1,
+        config.dilation.1,
+    );
+    assert!(
+        rows_overlap && cols_overlap,
+        "Every pooling window must have at least one tap inside the image."
+    );
+    (x_dims.0, out_rows * out_cols)
+}
+
+/// Returns `true` if every one of the `out_len` windows along one axis has a tap inside `0..len`.
+/// With dilation, the taps of a window can skip over the whole image, so every window is checked.
+fn windows_overlap(
+    len: usize,
+    kernel_len: usize,
+    out_len: usize,
+    stride: usize,
+    padding: usize,
+    dilation: usize,
+) -> bool {
+    (0..out_len).all(|window| {
+        (0..kernel_len).any(|tap| {
+            let pos = window * stride + tap * dilation;
+            pos >= padding && pos - padding < len
+        })
+    })
+}
+
+fn pool_grad_dims(
+    grads_dims: (usize, usize),
+    img_dims: (usize, usize),
+    kernel_dims: (usize, usize),
+    config: &ConvConfig,
+) -> (usize, usize) {
+    let (out_rows, out_cols) = config.out_dims(img_dims, kernel_dims);
+    assert_eq!(
+        grads_dims.1,
+        out_rows * out_cols,
+        "The gradients do not match the image and kernel dimensions."
+    );
+    (grads_dims.0, img_dims.0 * img_dims.1)
+}
+
+#[impl_stack]
+impl<T: Number, D: MainMemory, IS: Shape, OS: Shape> PoolOps<T, IS, OS, D> for CPU {
+    fn max_pool2d(
+        &self,
+        x: &Matrix<T, D, IS>,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> (Matrix<T, Self, OS>, Matrix<u32, Self, OS>) {
+        let dims = pool_dims(x.dims(), img_dims, kernel_dims, &config);
+
+        let mut out = self.retrieve(dims.0 * dims.1, x.node.idx);
+        let mut indices = self.retrieve(dims.0 * dims.1, x.node.idx);
+        max_pool2d_mut(
+            x,
+            x.rows(),
+            img_dims,
+            kernel_dims,
+            &config,
+            &mut out,
+            &mut indices,
+        );
+
+        ((out, dims).into(), (indices, dims).into())
+    }
+
+    fn max_pool2d_grad(
+        &self,
+        grads: &Matrix<T, D, OS>,
+        indices: &Matrix<u32, D, OS>,
+        img_dims: (usize, usize),
+    ) -> Matrix<T, Self, IS> {
+        assert_eq!(grads.dims(), indices.dims());
+
+        let dims = (grads.rows(), img_dims.0 * img_dims.1);
+
+        let mut out = self.retrieve(dims.0 * dims.1, (grads.node.idx, indices.node.idx));
+        max_pool2d_grad_mut(grads, indices, grads.rows(), dims.1, &mut out);
+        (out, dims).into()
+    }
+
+    fn avg_pool2d(
+        &self,
+        x: &Matrix<T, D, IS>,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T, Self, OS> {
+        let dims = pool_dims(x.dims(), img_dims, kernel_dims, &config);
+
+        let mut out = self.retrieve(dims.0 * dims.1, x.node.idx);
+        avg_pool2d_mut(x, x.rows(), img_dims, kernel_dims, &config, &mut out);
+        (out, dims).into()
+    }
+
+    fn avg_pool2d_grad(
+        &self,
+        grads: &Matrix<T, D, OS>,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T, Self, IS> {
+        let dims = pool_grad_dims(grads.dims(), img_dims, kernel_dims, &config);
+
+        let mut out = self.retrieve(dims.0 * dims.1, grads.node.idx);
+        avg_pool2d_grad_mut(
+            grads,
+            grads.rows(),
+            img_dims,
+            kernel_dims,
+            &config,
+            &mut out,
+        );
+        (out, dims).into()
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: CDatatype> PoolOps<T> for OpenCL {
+    fn max_pool2d(
+        &self,
+        x: &Matrix<T, Self>,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> (Matrix<T, Self>, Matrix<u32, Self>) {
+        let dims = pool_dims(x.dims(), img_dims, kernel_dims, &config);
+        let (out, indices) =
+            cl_max_pool2d(self, x, x.rows(), img_dims, kernel_dims, &config).unwrap();
+        ((out, dims).into(), (indices, dims).into())
+    }
+
+    fn max_pool2d_grad(
+        &self,
+        grads: &Matrix<T, Self>,
+        indices: &Matrix<u32, Self>,
+        img_dims: (usize, usize),
+    ) -> Matrix<T, Self> {
+        assert_eq!(grads.dims(), indices.dims());
+
+        let dims = (grads.rows(), img_dims.0 * img_dims.1);
+        let out = cl_max_pool2d_grad(self, grads, indices, grads.rows(), dims.1).unwrap();
+        (out, dims).into()
+    }
+
+    fn avg_pool2d(
+        &self,
+        x: &Matrix<T, Self>,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T, Self> {
+        let dims = pool_dims(x.dims(), img_dims, kernel_dims, &config);
+        let out = cl_avg_pool2d(self, x, x.rows(), img_dims, kernel_dims, &config).unwrap();
+        (out, dims).into()
+    }
+
+    fn avg_pool2d_grad(
+        &self,
+        grads: &Matrix<T, Self>,
+        img_dims: (usize, usize),
+        kernel_dims: (usize, usize),
+        config: ConvConfig,
+    ) -> Matrix<T, Self> {
+        let dims = pool_grad_dims(grads.dims(), img_dims, kernel_dims, &config);
+        let out =
+            cl_avg_pool2d_grad(self, grads, grads.rows(), img_dims, kernel_dims, &config).unwrap();
+        (out, dims).into()
+    }
+}
@@ -0,0 +1,182 @@
+use custos::{impl_stack, number::Float, Buffer, Device, MainMemory, Shape, CPU};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+use crate::{lstsq_slice, qr_slice, LinalgError, Matrix};
+
+/// Selects the dimensions of the factors of a QR decomposition of a `m x n` matrix with `k = min(m, n)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QrMode {
+    /// `q` is `m x k` and `r` is `k x n`.
+    Thin,
+    /// `q` is `m x m` and `r` is `m x n`.
+    Full,
+}
+
+impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
+    /// Householder QR decomposition. See [`QrOps`].
+    #[inline]
+    pub fn qr<QS: Shape, RS: Shape>(
+        &self,
+        mode: QrMode,
+    ) -> (Matrix<'a, T, D, QS>, Matrix<'a, T, D, RS>)
+    where
+        D: QrOps<T, S, QS, RS>,
+    {
+        self.device().qr(self, mode)
+    }
+
+    /// Least squares solution of `self * x = b`. See [`LstsqOp`].
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    pub fn lstsq<BS: Shape, XS: Shape, ES: Shape>(
+        &self,
+        b: &Matrix<T, D, BS>,
+    ) -> Result<(Matrix<'a, T, D, XS>, Matrix<'a, T, D, ES>, usize), LinalgError>
+    where
+        D: LstsqOp<T, S, BS, XS, ES>,
+    {
+        self.device().lstsq(self, b)
+    }
+}
+
+/// Householder QR decomposition `a = q * r` of a `m x n` matrix,
+/// where `q` has orthonormal columns and `r` is upper triangular.
+///
+/// `QS` and `RS` are the shapes of `q` and `r`, which depend on the [`QrMode`].
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, QrMode, QrOps};
+///
+/// let device = CPU::new();
+///
+/// let a = Matrix::from((&device, (3, 2), [3., 1., 0., 1., 4., 1.]));
+///
+/// let (q, r): (Matrix, Matrix) = device.qr(&a, QrMode::Thin);
+/// assert_eq!(q.dims(), (3, 2));
+/// assert_eq!(r.dims(), (2, 2));
+/// assert!((r[0] + 5.).abs() < 1e-12);
+///
+/// let (q, r): (Matrix, Matrix) = device.qr(&a, QrMode::Full);
+/// assert_eq!(q.dims(), (3, 3));
+/// assert_eq!(r.dims(), (3, 2));
+/// ```
+pub trait QrOps<T, S: Shape = (), QS: Shape = (), RS: Shape = (), D: Device = Self>:
+    Device
+{
+    fn qr(&self, a: &Matrix<T, D, S>, mode: QrMode) -> (Matrix<T, Self, QS>, Matrix<T, Self, RS>);
+}
+
+#[impl_stack]
+impl<T, D, S, QS, RS> QrOps<T, S, QS, RS, D> for CPU
+where
+    T: Float,
+    D: MainMemory,
+    S: Shape,
+    QS: Shape,
+    RS: Shape,
+{
+    fn qr(&self, a: &Matrix<T, D, S>, mode: QrMode) -> (Matrix<T, Self, QS>, Matrix<T, Self, RS>) {
+        let (m, n) = a.dims();
+        let q_cols = match mode {
+            QrMode::Thin => m.min(n),
+            QrMode::Full => m,
+        };
+
+        let mut work: Buffer<T, Self, S> = self.retrieve(a.len(), a.node.idx);
+        work.copy_from_slice(a);
+
+        let mut q = self.retrieve(m * q_cols, a.node.idx);
+        let mut r = self.retrieve(q_cols * n, a.node.idx);
+        qr_slice(&mut work, m, n, &mut q, q_cols, &mut r);
+
+        ((q, m, q_cols).into(), (r, q_cols, n).into())
+    }
+}
+
+/// Least squares solution `x` of `a * x = b` for every column of `b`,
+/// based on a Householder QR decomposition of the `m x n` matrix `a`.
+///
+/// Returns `(x, residuals, rank)`:
+/// - `x` is `n x b.cols()`.
+/// - `residuals` is `1 x b.cols()` and holds the squared euclidean norm of every column of `b - a * x`.
+/// - `rank` is the numerical rank of `a`.
+///
+/// Columns of `a` that are linearly dependent on the columns before them get a coefficient of 0.
+/// # Errors
+/// [`LinalgError::DimsMismatch`] if `b` does not have as many rows as `a`.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, LstsqOp};
+///
+/// let device = CPU::new();
+///
+/// // fit y = c0 + c1 * x through (0, 1), (1, 2), (2, 2), (3, 4)
+/// let a = Matrix::from((&device, (4, 2), [1., 0., 1., 1., 1., 2., 1., 3.]));
+/// let y = Matrix::from((&device, (4, 1), [1., 2., 2., 4.]));
+///
+/// let (coefs, residuals, rank): (Matrix, Matrix, usize) = device.lstsq(&a, &y).unwrap();
+/// assert!((coefs[0] - 0.9).abs() < 1e-12);
+/// assert!((coefs[1] - 0.9).abs() < 1e-12);
+/// assert!((residuals[0] - 0.7).abs() < 1e-12);
+/// assert_eq!(rank, 2);
+/// ```
+pub trait LstsqOp<
+    T,
+    S: Shape = (),
+    BS: Shape = (),
+    XS: Shape = (),
+    ES: Shape = (),
+    D: Device = Self,
+>: Device
+{
+    #[allow(clippy::type_complexity)]
+    fn lstsq(
+        &self,
+        a: &Matrix<T, D, S>,
+        b: &Matrix<T, D, BS>,
+    ) -> Result<(Matrix<T, Self, XS>, Matrix<T, Self, ES>, usize), LinalgError>;
+}
+
+#[impl_stack]
+impl<T, D, S, BS, XS, ES> LstsqOp<T, S, BS, XS, ES, D> for CPU
+where
+    T: Float,
+    D: MainMemory,
+    S: Shape,
+    BS: Shape,
+    XS: Shape,
+    ES: Shape,
+{
+    fn lstsq(
+        &self,
+        a: &Matrix<T, D, S>,
+        b: &Matrix<T, D, BS>,
+    ) -> Result<(Matrix<T, Self, XS>, Matrix<T, Self, ES>, usize), LinalgError> {
+        let (m, n) = a.dims();
+        if b.rows() != m {
+            return Err(LinalgError::DimsMismatch {
+                lhs: a.dims(),
+                rhs: b.dims(),
+            });
+        }
+        let nrhs = b.cols();
+
+        let mut work: Buffer<T, Self, S> = self.retrieve(a.len(), a.node.idx);
+        work.copy_from_slice(a);
+
+        let mut rhs: Buffer<T, Self, BS> = self.retrieve(b.len(), b.node.idx);
+        rhs.copy_from_slice(b);
+
+        let mut x = self.retrieve(n * nrhs, (a.node.idx, b.node.idx));
+        let mut residuals = self.retrieve(nrhs, (a.node.idx, b.node.idx));
+        let rank = lstsq_slice(&mut work, m, n, &mut rhs, nrhs, &mut x, &mut residuals);
+
+        Ok(((x, n, nrhs).into(), (residuals, 1, nrhs).into(), rank))
+    }
+}
@@ -2,6 +2,7 @@ use crate::Matrix;
 #[cfg(feature = "opencl")]
 use custos::OpenCL;
 use custos::{impl_stack, number::Float, Buffer, Device, MainMemory, Shape, CPU};
+use fastrand::Rng;
 //use rand::{thread_rng, Rng, distributions::uniform::SampleUniform};
 
 #[cfg(feature = "stack")]
@@ -12,12 +13,30 @@ use crate::opencl::cl_write;
 
 pub trait RandBuf<T> {
     fn rand(&mut self, lo: T, hi: T);
+    fn rand_with(&mut self, rng: &mut Rng, lo: T, hi: T);
+    fn randn(&mut self, mean: T, std: T);
+    fn truncated_normal(&mut self, mean: T, std: T);
 }
 impl<T, S: Shape, D: RandOp<T, S>> RandBuf<T> for Buffer<'_, T, D, S> {
     #[inline]
     fn rand(&mut self, lo: T, hi: T) {
         self.device().rand(self, lo, hi)
     }
+
+    #[inline]
+    fn rand_with(&mut self, rng: &mut Rng, lo: T, hi: T) {
+        self.device().rand_with(self, rng, lo, hi)
+    }
+
+    #[inline]
+    fn randn(&mut self, mean: T, std: T) {
+        self.device().randn(self, mean, std)
+    }
+
+    #[inline]
+    fn truncated_normal(&mut self, mean: T, std: T) {
+        self.device().truncated_normal(self, mean, std)
+    }
 }
 
 impl<'a, T, S: Shape, D: RandOp<T, S>> Matrix<'a, T, D, S> {
@@ -25,50 +44,243 @@ impl<'a, T, S: Shape, D: RandOp<T, S>> Matrix<'a, T, D, S> {
     pub fn rand(&mut self, lo: T, hi: T) {
         self.as_buf_mut().rand(lo, hi);
     }
+
+    /// Fills the matrix with uniformly distributed values in `[lo, hi)`, which are drawn from `rng`.
+    /// # Example
+    #[cfg_attr(feature = "cpu", doc = "```")]
+    #[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+    /// use custos::CPU;
+    /// use custos_math::Matrix;
+    ///
+    /// let device = CPU::new();
+    ///
+    /// let mut a = Matrix::<f32>::from((&device, (2, 3), [0.; 6]));
+    /// let mut b = Matrix::<f32>::from((&device, (2, 3), [0.; 6]));
+    ///
+    /// a.rand_with(&mut fastrand::Rng::with_seed(7), -1., 1.);
+    /// b.rand_with(&mut fastrand::Rng::with_seed(7), -1., 1.);
+    /// assert_eq!(a.read(), b.read());
+    /// ```
+    #[inline]
+    pub fn rand_with(&mut self, rng: &mut Rng, lo: T, hi: T) {
+        self.as_buf_mut().rand_with(rng, lo, hi);
+    }
+
+    #[inline]
+    pub fn randn(&mut self, mean: T, std: T) {
+        self.as_buf_mut().randn(mean, std);
+    }
+
+    #[inline]
+    pub fn truncated_normal(&mut self, mean: T, std: T) {
+        self.as_buf_mut().truncated_normal(mean, std);
+    }
 }
 
+/// Weight initialisers. The matrix is treated as the weights of a layer, which computes `inputs * weights`,
+/// hence fan-in is the number of rows and fan-out the number of columns.
+impl<'a, T: Float, S: Shape, D: RandOp<T, S>> Matrix<'a, T, D, S> {
+    /// Uniform values in `[-limit, limit)` with `limit = sqrt(6 / (fan_in + fan_out))`.
+    #[inline]
+    pub fn xavier_uniform(&mut self) {
+        let limit = T::as_generic((6. / (self.rows() + self.cols()) as f64).sqrt());
+        self.rand(-limit, limit);
+    }
+
+    /// Normal values with `std = sqrt(2 / (fan_in + fan_out))`.
+    #[inline]
+    pub fn xavier_normal(&mut self) {
+        let std = T::as_generic((2. / (self.rows() + self.cols()) as f64).sqrt());
+        self.randn(T::zero(), std);
+    }
+
+    /// Uniform values in `[-limit, limit)` with `limit = sqrt(6 / fan_in)`.
+    #[inline]
+    pub fn he_uniform(&mut self) {
+        let limit = T::as_generic((6. / self.rows() as f64).sqrt());
+        self.rand(-limit, limit);
+    }
+
+    /// Normal values with `std = sqrt(2 / fan_in)`.
+    #[inline]
+    pub fn he_normal(&mut self) {
+        let std = T::as_generic((2. / self.rows() as f64).sqrt());
+        self.randn(T::zero(), std);
+    }
+
+    /// Normal values with `std = sqrt(1 / fan_in)`.
+    #[inline]
+    pub fn lecun(&mut self) {
+        let std = T::as_generic((1. / self.rows() as f64).sqrt());
+        self.randn(T::zero(), std);
+    }
+}
+
+/// Uniformly distributed random values.
+/// The values are always generated on the host, hence the same seed yields the same values on every device.
 pub trait RandOp<T, S: Shape = (), D: Device = Self>: Device {
+    /// Fills `x` with values in `[lo, hi)`, which are drawn from the thread local generator.
+    /// The generator can be seeded with [`RandOp::seed`].
     fn rand(&self, x: &mut Buffer<T, D, S>, lo: T, hi: T);
+    /// Fills `x` with values in `[lo, hi)`, which are drawn from `rng`.
+    fn rand_with(&self, x: &mut Buffer<T, D, S>, rng: &mut Rng, lo: T, hi: T);
+    /// Fills `x` with normally distributed values, which are drawn from the thread local generator.
+    fn randn(&self, x: &mut Buffer<T, D, S>, mean: T, std: T);
+    /// Like [`RandOp::randn`], but values further than two standard deviations away from the mean are redrawn.
+    fn truncated_normal(&self, x: &mut Buffer<T, D, S>, mean: T, std: T);
+
+    /// Seeds the thread local generator, which is used by [`RandOp::rand`].
+    /// As the generator does not depend on `T`, the datatype can be chosen freely: `RandOp::<f32>::seed(&device, 42)`.
+    #[inline]
+    fn seed(&self, seed: u64) {
+        fastrand::seed(seed)
+    }
 }
 
+/// Fills the slice with values in `[lo, hi)`, which are drawn from the thread local generator.
 pub fn rand_slice<T: PartialOrd + Copy + Float>(slice: &mut [T], lo: T, hi: T) {
-    let rng = fastrand::Rng::new();
+    for value in slice {
+        *value = T::as_generic(fastrand::f64()) * (hi - (lo)) + (lo);
+    }
+}
+
+/// Fills the slice with values in `[lo, hi)`, which are drawn from `rng`.
+pub fn rand_slice_with<T: PartialOrd + Copy + Float>(rng: &mut Rng, slice: &mut [T], lo: T, hi: T) {
     for value in slice {
         *value = T::as_generic(rng.f64()) * (hi - (lo)) + (lo);
     }
 }
 
+/// Fills the slice with normally distributed values, which are drawn from the thread local generator.
+pub fn randn_slice<T: Float>(slice: &mut [T], mean: T, std: T) {
+    normal_slice(slice, mean, std, false, fastrand::f64)
+}
+
+/// Fills the slice with normally distributed values in `[mean - 2 * std, mean + 2 * std]`,
+/// which are drawn from the thread local generator.
+pub fn truncated_normal_slice<T: Float>(slice: &mut [T], mean: T, std: T) {
+    normal_slice(slice, mean, std, true, fastrand::f64)
+}
+
+/// Draws standard normal values with the Box-Muller transform.
+/// `uniform` returns values in `[0, 1)`.
+fn normal_slice<T, F>(slice: &mut [T], mean: T, std: T, truncate: bool, mut uniform: F)
+where
+    T: Float,
+    F: FnMut() -> f64,
+{
+    let mut normal = || loop {
+        // 1 - [0, 1) = (0, 1], which avoids ln(0)
+        let radius = (-2. * (1. - uniform()).ln()).sqrt();
+        let z = radius * (core::f64::consts::TAU * uniform()).cos();
+
+        if !truncate || z.abs() <= 2. {
+            return z;
+        }
+    };
+
+    for value in slice {
+        *value = T::as_generic(normal()) * std + mean;
+    }
+}
+
 #[impl_stack]
 impl<T: Float, D: MainMemory, S: Shape> RandOp<T, S, D> for CPU {
     #[inline]
     fn rand(&self, x: &mut Buffer<T, D, S>, lo: T, hi: T) {
         rand_slice(x, lo, hi)
     }
+
+    #[inline]
+    fn rand_with(&self, x: &mut Buffer<T, D, S>, rng: &mut Rng, lo: T, hi: T) {
+        rand_slice_with(rng, x, lo, hi)
+    }
+
+    #[inline]
+    fn randn(&self, x: &mut Buffer<T, D, S>, mean: T, std: T) {
+        randn_slice(x, mean, std)
+    }
+
+    #[inline]
+    fn truncated_normal(&self, x: &mut Buffer<T, D, S>, mean: T, std: T) {
+        truncated_normal_slice(x, mean, std)
+    }
+}
+
+/// The values are generated on the host and written to the OpenCL buffer afterwards.
+#[cfg(feature = "opencl")]
+fn cl_fill<T: Default + Copy, F: FnOnce(&mut [T])>(
+    device: &OpenCL,
+    x: &mut Buffer<T, OpenCL>,
+    fill: F,
+) {
+    #[cfg(unified_cl)]
+    {
+        let _ = device;
+        let slice: &mut [T] = x;
+        fill(slice);
+    }
+
+    #[cfg(not(unified_cl))]
+    {
+        let mut data = vec![T::default(); x.len()];
+        fill(&mut data);
+        cl_write(device, x, &data)
+    };
 }
 
 #[cfg(feature = "opencl")]
 impl<T: Float> RandOp<T> for OpenCL {
+    #[inline]
     fn rand(&self, x: &mut Buffer<T, OpenCL>, lo: T, hi: T) {
-        #[cfg(unified_cl)]
-        rand_slice(x, lo, hi);
+        cl_fill(self, x, |data| rand_slice(data, lo, hi))
+    }
+
+    #[inline]
+    fn rand_with(&self, x: &mut Buffer<T, OpenCL>, rng: &mut Rng, lo: T, hi: T) {
+        cl_fill(self, x, |data| rand_slice_with(rng, data, lo, hi))
+    }
 
-        #[cfg(not(unified_cl))]
-        {
-            let mut data = vec![T::default(); x.len()];
-            rand_slice(&mut data, lo, hi);
-            cl_write(self, x, &data)
-        };
+    #[inline]
+    fn randn(&self, x: &mut Buffer<T, OpenCL>, mean: T, std: T) {
+        cl_fill(self, x, |data| randn_slice(data, mean, std))
+    }
+
+    #[inline]
+    fn truncated_normal(&self, x: &mut Buffer<T, OpenCL>, mean: T, std: T) {
+        cl_fill(self, x, |data| truncated_normal_slice(data, mean, std))
     }
 }
 
 #[cfg(feature = "cuda")]
 use custos::{cuda::api::cu_write, CUDA};
 
+#[cfg(feature = "cuda")]
+fn cu_fill<T: Default + Copy, F: FnOnce(&mut [T])>(x: &mut Buffer<T, CUDA>, fill: F) {
+    let mut data = vec![T::default(); x.len()];
+    fill(&mut data);
+    cu_write(x.ptr.ptr, &data).unwrap();
+}
+
 #[cfg(feature = "cuda")]
 impl<T: Float> RandOp<T> for CUDA {
+    #[inline]
     fn rand(&self, x: &mut Buffer<T, CUDA>, lo: T, hi: T) {
-        let mut data = vec![T::default(); x.len()];
-        rand_slice(&mut data, lo, hi);
-        cu_write(x.ptr.ptr, &data).unwrap();
+        cu_fill(x, |data| rand_slice(data, lo, hi))
+    }
+
+    #[inline]
+    fn rand_with(&self, x: &mut Buffer<T, CUDA>, rng: &mut Rng, lo: T, hi: T) {
+        cu_fill(x, |data| rand_slice_with(rng, data, lo, hi))
+    }
+
+    #[inline]
+    fn randn(&self, x: &mut Buffer<T, CUDA>, mean: T, std: T) {
+        cu_fill(x, |data| randn_slice(data, mean, std))
+    }
+
+    #[inline]
+    fn truncated_normal(&self, x: &mut Buffer<T, CUDA>, mean: T, std: T) {
+        cu_fill(x, |data| truncated_normal_slice(data, mean, std))
     }
 }
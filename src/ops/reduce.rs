@@ -0,0 +1,229 @@
+use crate::{arg_by, Axis, Matrix};
+use custos::{number::Number, Alloc, Device, MainMemory, Shape};
+
+#[cfg(any(feature = "cpu", feature = "stack"))]
+use crate::{MaxOps, MinOps, SumOps, SumOverOps};
+#[cfg(feature = "cpu")]
+use custos::CPU;
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+use custos::CDatatype;
+
+#[cfg(feature = "opencl")]
+use super::cl_to_cpu_s;
+#[cfg(feature = "opencl")]
+use custos::OpenCL;
+
+#[cfg(feature = "cuda")]
+use crate::cu_to_cpu_s;
+#[cfg(feature = "cuda")]
+use custos::CUDA;
+
+/// The reduction that is applied by [`ReduceOps::reduce`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reduce {
+    Sum,
+    Mean,
+    Max,
+    Min,
+    Prod,
+    /// The index of the (first) maximum value, stored as `T`.
+    ArgMax,
+    /// The index of the (first) minimum value, stored as `T`.
+    ArgMin,
+    /// The population variance.
+    Var,
+}
+
+impl<'a, T, D: Device, IS: Shape> Matrix<'a, T, D, IS> {
+    /// Reduces the matrix along `axis` using `op`.
+    /// # Example
+    #[cfg_attr(feature = "cpu", doc = "```")]
+    #[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+    /// use custos::CPU;
+    /// use custos_math::{Axis, Matrix, Reduce};
+    ///
+    /// let device = CPU::new();
+    /// let x = Matrix::from((&device, (2, 3), [1., 2., 3., 4., 5., 6.,]));
+    ///
+    /// assert_eq!(x.reduce(Axis::Rows, Reduce::Sum).read(), vec![5., 7., 9.]);
+    /// assert_eq!(x.reduce(Axis::Cols, Reduce::Max).read(), vec![3., 6.]);
+    /// assert_eq!(x.reduce(Axis::All, Reduce::Mean).read(), vec![3.5]);
+    /// ```
+    #[inline]
+    pub fn reduce<OS: Shape>(&self, axis: Axis, op: Reduce) -> Matrix<'a, T, D, OS>
+    where
+        D: ReduceOps<T, IS, OS>,
+    {
+        self.device().reduce(self, axis, op)
+    }
+}
+
+pub trait ReduceOps<T, IS: Shape = (), OS: Shape = (), D: Device = Self>: Device {
+    /// Reduces `x` along `axis`.
+    /// The output has the dimensions returned by [`Axis::reduced_dims`].
+    fn reduce(&self, x: &Matrix<T, D, IS>, axis: Axis, op: Reduce) -> Matrix<T, Self, OS>;
+}
+
+/// Reduces all values of an iterator using `op`.
+pub fn reduce_iter<T, I>(values: I, op: Reduce) -> T
+where
+    T: Number,
+    I: Iterator<Item = T> + Clone,
+{
+    match op {
+        Reduce::Sum => values.fold(T::default(), |acc, value| acc + value),
+        Reduce::Mean => {
+            let (sum, count) = values.fold((T::default(), 0), |(acc, count), value| {
+                (acc + value, count + 1)
+            });
+            sum / T::from_usize(count)
+        }
+        Reduce::Max => values
+            .reduce(|max, value| if value > max { value } else { max })
+            .unwrap_or_default(),
+        Reduce::Min => values
+            .reduce(|min, value| if value < min { value } else { min })
+            .unwrap_or_default(),
+        Reduce::Prod => values.fold(T::one(), |acc, value| acc * value),
+        Reduce::ArgMax => T::from_usize(arg_by(values, |value, max| value > max)),
+        Reduce::ArgMin => T::from_usize(arg_by(values, |value, min| value < min)),
+        Reduce::Var => {
+            let mean = reduce_iter(values.clone(), Reduce::Mean);
+            let (sum, count) = values.fold((T::default(), 0), |(acc, count), value| {
+                let diff = value - mean;
+                (acc + diff * diff, count + 1)
+            });
+            sum / T::from_usize(count)
+        }
+    }
+}
+
+/// Reduces a row major `rows` x `cols` slice along `axis` and writes the result into `out`.
+pub fn reduce_slice<T: Number>(
+    x: &[T],
+    rows: usize,
+    cols: usize,
+    axis: Axis,
+    op: Reduce,
+    out: &mut [T],
+) {
+    match axis {
+        Axis::All => out[0] = reduce_iter(x.iter().copied(), op),
+        Axis::Rows => {
+            for (col, value) in out.iter_mut().enumerate().take(cols) {
+                *value = reduce_iter((0..rows).map(|row| x[row * cols + col]), op);
+            }
+        }
+        Axis::Cols => {
+            for (row, value) in out.iter_mut().enumerate().take(rows) {
+                let index = row * cols;
+                *value = reduce_iter(x[index..index + cols].iter().copied(), op);
+            }
+        }
+    }
+}
+
+pub fn reduce_op<'a, T, D, IS, OS, Host>(
+    device: &'a Host,
+    x: &Matrix<T, D, IS>,
+    axis: Axis,
+    op: Reduce,
+) -> Matrix<'a, T, Host, OS>
+where
+    T: Number,
+    D: MainMemory,
+    IS: Shape,
+    OS: Shape,
+    Host: for<'b> Alloc<'b, T, OS> + MainMemory,
+{
+    let (rows, cols) = axis.reduced_dims(x.dims());
+
+    let mut out = device.retrieve(rows * cols, x.node.idx);
+    reduce_slice(x, x.rows(), x.cols(), axis, op, &mut out);
+    (out, rows, cols).into()
+}
+
+/// Dispatches the reductions that have a dedicated implementation to [`SumOps`], [`SumOverOps`], [`MaxOps`] and [`MinOps`]
+/// and computes the others with [`reduce_op`].
+#[cfg(any(feature = "cpu", feature = "stack"))]
+fn reduce_dispatch<'a, T, D, IS, OS, Host>(
+    device: &'a Host,
+    x: &Matrix<T, D, IS>,
+    axis: Axis,
+    op: Reduce,
+) -> Matrix<'a, T, Host, OS>
+where
+    T: Number,
+    D: MainMemory,
+    IS: Shape,
+    OS: Shape,
+    Host: SumOps<T, IS, D>
+        + SumOverOps<T, IS, OS, D>
+        + MaxOps<T, IS, OS, D>
+        + MinOps<T, IS, OS, D>
+        + for<'b> Alloc<'b, T, OS>
+        + MainMemory,
+{
+    match (axis, op) {
+        (Axis::All, Reduce::Sum | Reduce::Mean | Reduce::Max | Reduce::Min) => {
+            let value = match op {
+                Reduce::Sum => device.sum(x),
+                Reduce::Mean => device.mean(x),
+                Reduce::Max => device.max(x),
+                _ => device.min(x),
+            };
+
+            let mut out = device.retrieve(1, x.node.idx);
+            out[0] = value;
+            (out, 1, 1).into()
+        }
+        (Axis::Rows, Reduce::Sum) => device.sum_rows(x),
+        (Axis::Cols, Reduce::Sum) => device.sum_cols(x),
+        (Axis::Rows, Reduce::Max) => device.max_rows(x),
+        (Axis::Cols, Reduce::Max) => device.max_cols(x),
+        (Axis::Rows, Reduce::Min) => device.min_rows(x),
+        (Axis::Cols, Reduce::Min) => device.min_cols(x),
+        _ => reduce_op(device, x, axis, op),
+    }
+}
+
+#[cfg(feature = "cpu")]
+impl<T: Number, D: MainMemory> ReduceOps<T, (), (), D> for CPU {
+    #[inline]
+    fn reduce(&self, x: &Matrix<T, D>, axis: Axis, op: Reduce) -> Matrix<T> {
+        reduce_dispatch(self, x, axis, op)
+    }
+}
+
+#[cfg(feature = "stack")]
+impl<T: Number, D: MainMemory, IS: Shape, OS: Shape> ReduceOps<T, IS, OS, D> for Stack {
+    #[inline]
+    fn reduce(&self, x: &Matrix<T, D, IS>, axis: Axis, op: Reduce) -> Matrix<T, Self, OS> {
+        reduce_dispatch(self, x, axis, op)
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: CDatatype> ReduceOps<T> for OpenCL {
+    fn reduce(&self, x: &Matrix<T, Self>, axis: Axis, op: Reduce) -> Matrix<T, Self> {
+        match (axis, op) {
+            (Axis::Rows, Reduce::Sum) => self.sum_rows(x),
+            (Axis::Cols, Reduce::Sum) => self.sum_cols(x),
+            (Axis::Rows, Reduce::Max) => self.max_rows(x),
+            (Axis::Cols, Reduce::Max) => self.max_cols(x),
+            _ => cl_to_cpu_s(self, x, |device, x| device.reduce(x, axis, op)),
+        }
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: CDatatype> ReduceOps<T> for CUDA {
+    #[inline]
+    fn reduce(&self, x: &Matrix<T, CUDA>, axis: Axis, op: Reduce) -> Matrix<T, CUDA> {
+        cu_to_cpu_s(self, x, |device, x| device.reduce(x, axis, op))
+    }
+}
@@ -33,11 +33,47 @@ impl<'a, T, LS: Shape, D: Device> Matrix<'a, T, D, LS> {
     {
         rhs.device().add_row_mut(self, rhs)
     }
+
+    #[inline]
+    pub fn sub_row<RS: Shape>(&self, rhs: &Matrix<T, D, RS>) -> Matrix<'a, T, D, LS>
+    where
+        D: RowOp<T, LS, RS>,
+    {
+        self.device().sub_row(self, rhs)
+    }
+
+    #[inline]
+    pub fn sub_row_mut<RS: Shape>(&mut self, rhs: &Matrix<'a, T, D, RS>)
+    where
+        D: RowOp<T, LS, RS>,
+    {
+        rhs.device().sub_row_mut(self, rhs)
+    }
+
+    #[inline]
+    pub fn mul_row<RS: Shape>(&self, rhs: &Matrix<T, D, RS>) -> Matrix<'a, T, D, LS>
+    where
+        D: RowOp<T, LS, RS>,
+    {
+        self.device().mul_row(self, rhs)
+    }
+
+    #[inline]
+    pub fn mul_row_mut<RS: Shape>(&mut self, rhs: &Matrix<'a, T, D, RS>)
+    where
+        D: RowOp<T, LS, RS>,
+    {
+        rhs.device().mul_row_mut(self, rhs)
+    }
 }
 
 pub trait RowOp<T, LS: Shape = (), RS: Shape = (), D: Device = Self>: Device {
     fn add_row(&self, lhs: &Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>) -> Matrix<T, Self, LS>;
     fn add_row_mut(&self, lhs: &mut Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>);
+    fn sub_row(&self, lhs: &Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>) -> Matrix<T, Self, LS>;
+    fn sub_row_mut(&self, lhs: &mut Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>);
+    fn mul_row(&self, lhs: &Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>) -> Matrix<T, Self, LS>;
+    fn mul_row_mut(&self, lhs: &mut Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>);
 }
 
 //#[cfg(feature = "cpu")]
@@ -53,6 +89,28 @@ impl<T: Number, D: MainMemory, LS: Shape, RS: Shape> RowOp<T, LS, RS, D> for CPU
         let (lhs_rows, lhs_cols) = lhs.dims();
         row_op_slice_lhs(lhs, lhs_rows, lhs_cols, rhs, |c, a| *c += a)
     }
+
+    #[inline]
+    fn sub_row(&self, lhs: &Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>) -> Matrix<T, Self, LS> {
+        row_op(self, lhs, rhs, |c, a, b| *c = a - b)
+    }
+
+    #[inline]
+    fn sub_row_mut(&self, lhs: &mut Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>) {
+        let (lhs_rows, lhs_cols) = lhs.dims();
+        row_op_slice_lhs(lhs, lhs_rows, lhs_cols, rhs, |c, a| *c -= a)
+    }
+
+    #[inline]
+    fn mul_row(&self, lhs: &Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>) -> Matrix<T, Self, LS> {
+        row_op(self, lhs, rhs, |c, a, b| *c = a * b)
+    }
+
+    #[inline]
+    fn mul_row_mut(&self, lhs: &mut Matrix<T, D, LS>, rhs: &Matrix<T, D, RS>) {
+        let (lhs_rows, lhs_cols) = lhs.dims();
+        row_op_slice_lhs(lhs, lhs_rows, lhs_cols, rhs, |c, a| *c *= a)
+    }
 }
 
 // TODO: Implement add_ro_mut (for cuda as well)
@@ -68,6 +126,28 @@ impl<T: CDatatype> RowOp<T> for OpenCL {
         opencl::cpu_exec_lhs_rhs_mut(self, lhs, rhs, |cpu, lhs, rhs| cpu.add_row_mut(lhs, rhs))
             .unwrap();
     }
+
+    #[inline]
+    fn sub_row(&self, lhs: &Matrix<T, Self>, rhs: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cl_to_cpu_lr(self, lhs, rhs, |device, lhs, rhs| device.sub_row(lhs, rhs))
+    }
+
+    #[inline]
+    fn sub_row_mut(&self, lhs: &mut Matrix<T, Self>, rhs: &Matrix<T, Self>) {
+        opencl::cpu_exec_lhs_rhs_mut(self, lhs, rhs, |cpu, lhs, rhs| cpu.sub_row_mut(lhs, rhs))
+            .unwrap();
+    }
+
+    #[inline]
+    fn mul_row(&self, lhs: &Matrix<T, Self>, rhs: &Matrix<T, Self>) -> Matrix<T, Self> {
+        cl_to_cpu_lr(self, lhs, rhs, |device, lhs, rhs| device.mul_row(lhs, rhs))
+    }
+
+    #[inline]
+    fn mul_row_mut(&self, lhs: &mut Matrix<T, Self>, rhs: &Matrix<T, Self>) {
+        opencl::cpu_exec_lhs_rhs_mut(self, lhs, rhs, |cpu, lhs, rhs| cpu.mul_row_mut(lhs, rhs))
+            .unwrap();
+    }
 }
 
 #[cfg(feature = "cuda")]
@@ -83,4 +163,28 @@ impl<T: CDatatype> RowOp<T> for CUDA {
             device.add_row_mut(lhs, rhs)
         })
     }
+
+    #[inline]
+    fn sub_row(&self, lhs: &Matrix<T, CUDA>, rhs: &Matrix<T, CUDA>) -> Matrix<T, CUDA> {
+        cu_to_cpu_lr(self, lhs, rhs, |device, lhs, rhs| device.sub_row(lhs, rhs))
+    }
+
+    #[inline]
+    fn sub_row_mut(&self, lhs: &mut Matrix<T, CUDA>, rhs: &Matrix<T, CUDA>) {
+        cu_to_cpu_lr_mut(self, lhs, rhs, |device, lhs, rhs| {
+            device.sub_row_mut(lhs, rhs)
+        })
+    }
+
+    #[inline]
+    fn mul_row(&self, lhs: &Matrix<T, CUDA>, rhs: &Matrix<T, CUDA>) -> Matrix<T, CUDA> {
+        cu_to_cpu_lr(self, lhs, rhs, |device, lhs, rhs| device.mul_row(lhs, rhs))
+    }
+
+    #[inline]
+    fn mul_row_mut(&self, lhs: &mut Matrix<T, CUDA>, rhs: &Matrix<T, CUDA>) {
+        cu_to_cpu_lr_mut(self, lhs, rhs, |device, lhs, rhs| {
+            device.mul_row_mut(lhs, rhs)
+        })
+    }
 }
@@ -0,0 +1,260 @@
+use custos::{impl_stack, number::Float, Buffer, Device, MainMemory, Shape, CPU};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+use custos::CDatatype;
+
+#[cfg(feature = "opencl")]
+use crate::cpu_exec_with;
+#[cfg(feature = "opencl")]
+use custos::OpenCL;
+
+#[cfg(feature = "cuda")]
+use crate::cu_to_cpu_with;
+#[cfg(feature = "cuda")]
+use custos::CUDA;
+
+use crate::{pinv_slice, svd_slice, Matrix};
+
+impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
+    /// Thin singular value decomposition of `self`. See [`SvdOps`].
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    pub fn svd<US: Shape, SS: Shape, VS: Shape>(
+        &self,
+    ) -> (
+        Matrix<'a, T, D, US>,
+        Matrix<'a, T, D, SS>,
+        Matrix<'a, T, D, VS>,
+    )
+    where
+        D: SvdOps<T, S, US, SS, VS>,
+    {
+        self.device().svd(self)
+    }
+
+    /// The Moore-Penrose pseudo-inverse of `self`. See [`PinvOp`].
+    #[inline]
+    pub fn pinv<PS: Shape>(&self) -> Matrix<'a, T, D, PS>
+    where
+        D: PinvOp<T, S, PS>,
+    {
+        self.device().pinv(self)
+    }
+
+    /// The numerical rank of `self`. See [`MatrixRankOp`].
+    #[inline]
+    pub fn matrix_rank(&self) -> usize
+    where
+        D: MatrixRankOp<T, S>,
+    {
+        self.device().matrix_rank(self)
+    }
+}
+
+/// Thin singular value decomposition `a = u * diag(sigma) * vt` of a `m x n` matrix
+/// with the one-sided Jacobi method, where `k = min(m, n)`.
+///
+/// Returns `(u, sigma, vt)`:
+/// - `u` is `m x k` with orthonormal columns.
+/// - `sigma` is `1 x k` and holds the singular values in descending order.
+/// - `vt` is `k x n` with orthonormal rows.
+///
+/// `US`, `SS` and `VS` are the shapes of `u`, `sigma` and `vt`.
+/// Singular values below `max(m, n) * eps * max(sigma)` are flushed to zero.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, SvdOps};
+///
+/// let device = CPU::new();
+///
+/// let a = Matrix::from((&device, (2, 3), [3., 2., 2., 2., 3., -2.]));
+///
+/// let (u, sigma, vt): (Matrix, Matrix, Matrix) = device.svd(&a);
+/// assert_eq!(u.dims(), (2, 2));
+/// assert_eq!(vt.dims(), (2, 3));
+///
+/// assert!((sigma[0] - 5.).abs() < 1e-12);
+/// assert!((sigma[1] - 3.).abs() < 1e-12);
+/// ```
+pub trait SvdOps<T, S: Shape = (), US: Shape = (), SS: Shape = (), VS: Shape = (), D: Device = Self>:
+    Device
+{
+    #[allow(clippy::type_complexity)]
+    fn svd(
+        &self,
+        a: &Matrix<T, D, S>,
+    ) -> (
+        Matrix<T, Self, US>,
+        Matrix<T, Self, SS>,
+        Matrix<T, Self, VS>,
+    );
+}
+
+#[impl_stack]
+impl<T, D, S, US, SS, VS> SvdOps<T, S, US, SS, VS, D> for CPU
+where
+    T: Float,
+    D: MainMemory,
+    S: Shape,
+    US: Shape,
+    SS: Shape,
+    VS: Shape,
+{
+    fn svd(
+        &self,
+        a: &Matrix<T, D, S>,
+    ) -> (
+        Matrix<T, Self, US>,
+        Matrix<T, Self, SS>,
+        Matrix<T, Self, VS>,
+    ) {
+        let (m, n) = a.dims();
+        let k = m.min(n);
+
+        let mut u = self.retrieve(m * k, a.node.idx);
+        let mut sigma = self.retrieve(k, a.node.idx);
+        let mut vt = self.retrieve(k * n, a.node.idx);
+        svd_slice(a, m, n, &mut u, &mut sigma, &mut vt);
+
+        ((u, m, k).into(), (sigma, 1, k).into(), (vt, k, n).into())
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: Float + CDatatype> SvdOps<T> for OpenCL {
+    fn svd(&self, a: &Matrix<T, Self>) -> (Matrix<T, Self>, Matrix<T, Self>, Matrix<T, Self>) {
+        cpu_exec_with(self, a, |cpu, a| {
+            let (u, sigma, vt): (Matrix<T>, Matrix<T>, Matrix<T>) = cpu.svd(a);
+            (
+                Matrix::from((self, u)),
+                Matrix::from((self, sigma)),
+                Matrix::from((self, vt)),
+            )
+        })
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: Float + CDatatype> SvdOps<T> for CUDA {
+    fn svd(&self, a: &Matrix<T, Self>) -> (Matrix<T, Self>, Matrix<T, Self>, Matrix<T, Self>) {
+        cu_to_cpu_with(a, |cpu, a| {
+            let (u, sigma, vt): (Matrix<T>, Matrix<T>, Matrix<T>) = cpu.svd(a);
+            (
+                Matrix::from((self, u)),
+                Matrix::from((self, sigma)),
+                Matrix::from((self, vt)),
+            )
+        })
+    }
+}
+
+/// The Moore-Penrose pseudo-inverse `v * diag(sigma)^+ * u^T` of a `m x n` matrix,
+/// computed with a thin singular value decomposition (see [`SvdOps`]).
+///
+/// The result is `n x m`, `PS` is its shape.
+/// Singular values below `max(m, n) * eps * max(sigma)` are treated as zero.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, PinvOp};
+///
+/// let device = CPU::new();
+///
+/// let a = Matrix::from((&device, (2, 2), [1., 2., 2., 4.]));
+///
+/// let pinv: Matrix = device.pinv(&a);
+/// for (x, expected) in pinv.iter().zip([0.04, 0.08, 0.08, 0.16]) {
+///     assert!((x - expected).abs() < 1e-12);
+/// }
+/// ```
+pub trait PinvOp<T, S: Shape = (), PS: Shape = (), D: Device = Self>: Device {
+    fn pinv(&self, a: &Matrix<T, D, S>) -> Matrix<T, Self, PS>;
+}
+
+#[impl_stack]
+impl<T: Float, D: MainMemory, S: Shape, PS: Shape> PinvOp<T, S, PS, D> for CPU {
+    fn pinv(&self, a: &Matrix<T, D, S>) -> Matrix<T, Self, PS> {
+        let (m, n) = a.dims();
+        let k = m.min(n);
+
+        // u, sigma and vt need at most m * n elements each
+        let mut u: Buffer<T, Self, S> = self.retrieve(a.len(), a.node.idx);
+        let mut sigma: Buffer<T, Self, S> = self.retrieve(a.len(), a.node.idx);
+        let mut vt: Buffer<T, Self, S> = self.retrieve(a.len(), a.node.idx);
+        svd_slice(a, m, n, &mut u[..m * k], &mut sigma[..k], &mut vt[..k * n]);
+
+        let mut out = self.retrieve(a.len(), a.node.idx);
+        pinv_slice(&u[..m * k], &sigma[..k], &vt[..k * n], m, n, &mut out);
+
+        (out, n, m).into()
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: Float + CDatatype> PinvOp<T> for OpenCL {
+    fn pinv(&self, a: &Matrix<T, Self>) -> Matrix<T, Self> {
+        super::cl_to_cpu_s(self, a, |cpu, a| cpu.pinv(a))
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: Float + CDatatype> PinvOp<T> for CUDA {
+    fn pinv(&self, a: &Matrix<T, Self>) -> Matrix<T, Self> {
+        crate::cu_to_cpu_s(self, a, |cpu, a| cpu.pinv(&a))
+    }
+}
+
+/// The numerical rank of a `m x n` matrix, which is the number of singular values (see [`SvdOps`])
+/// above `max(m, n) * eps * max(sigma)`.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use custos::CPU;
+/// use custos_math::{Matrix, MatrixRankOp};
+///
+/// let device = CPU::new();
+///
+/// let a = Matrix::from((&device, (3, 3), [1., 2., 3., 2., 4., 6., 1., 0., 1.]));
+/// assert_eq!(device.matrix_rank(&a), 2);
+/// ```
+pub trait MatrixRankOp<T, S: Shape = (), D: Device = Self>: Device {
+    fn matrix_rank(&self, a: &Matrix<T, D, S>) -> usize;
+}
+
+#[impl_stack]
+impl<T: Float, D: MainMemory, S: Shape> MatrixRankOp<T, S, D> for CPU {
+    fn matrix_rank(&self, a: &Matrix<T, D, S>) -> usize {
+        let (m, n) = a.dims();
+        let k = m.min(n);
+
+        let mut u: Buffer<T, Self, S> = self.retrieve(a.len(), a.node.idx);
+        let mut sigma: Buffer<T, Self, S> = self.retrieve(a.len(), a.node.idx);
+        let mut vt: Buffer<T, Self, S> = self.retrieve(a.len(), a.node.idx);
+        svd_slice(a, m, n, &mut u[..m * k], &mut sigma[..k], &mut vt[..k * n]);
+
+        sigma[..k]
+            .iter()
+            .filter(|value| **value != T::default())
+            .count()
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: Float + CDatatype> MatrixRankOp<T> for OpenCL {
+    fn matrix_rank(&self, a: &Matrix<T, Self>) -> usize {
+        cpu_exec_with(self, a, |cpu, a| cpu.matrix_rank(a))
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: Float + CDatatype> MatrixRankOp<T> for CUDA {
+    fn matrix_rank(&self, a: &Matrix<T, Self>) -> usize {
+        cu_to_cpu_with(a, |cpu, a| cpu.matrix_rank(a))
+    }
+}
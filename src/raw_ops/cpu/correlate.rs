@@ -80,3 +80,224 @@ pub fn rot_kernel<T: Number>(kernel: &[T], kernel_shape: (usize, usize)) -> Vec<
     }
     rotated
 }
+
+/// Stride, zero padding and dilation of a 2D cross-correlation, given as (rows, cols).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConvConfig {
+    pub stride: (usize, usize),
+    pub padding: (usize, usize),
+    pub dilation: (usize, usize),
+}
+
+impl Default for ConvConfig {
+    #[inline]
+    fn default() -> Self {
+        ConvConfig {
+            stride: (1, 1),
+            padding: (0, 0),
+            dilation: (1, 1),
+        }
+    }
+}
+
+impl ConvConfig {
+    #[inline]
+    pub fn new(stride: (usize, usize), padding: (usize, usize), dilation: (usize, usize)) -> Self {
+        assert!(
+            stride.0 > 0 && stride.1 > 0,
+            "The stride of a convolution must be positive."
+        );
+        ConvConfig {
+            stride,
+            padding,
+            dilation,
+        }
+    }
+
+    /// A stride 1 config that pads the input with zeros, so that every overlap of input and kernel is computed.
+    #[inline]
+    pub fn full(kernel_dims: (usize, usize)) -> Self {
+        ConvConfig {
+            padding: (kernel_dims.0 - 1, kernel_dims.1 - 1),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the dimensions of the output of a correlation of an input with dimensions `x_dims` and a kernel with dimensions `kernel_dims`.
+    pub fn out_dims(&self, x_dims: (usize, usize), kernel_dims: (usize, usize)) -> (usize, usize) {
+        (
+            conv_out_len(
+                x_dims.0,
+                kernel_dims.0,
+                self.stride.0,
+                self.padding.0,
+                self.dilation.0,
+            ),
+            conv_out_len(
+                x_dims.1,
+                kernel_dims.1,
+                self.stride.1,
+                self.padding.1,
+                self.dilation.1,
+            ),
+        )
+    }
+
+    /// Maps an output position and a kernel position to the position in the (unpadded) input.
+    /// Returns `None` if the position lies in the zero padding.
+    #[inline]
+    pub fn input_idx(
+        &self,
+        x_dims: (usize, usize),
+        out_pos: (usize, usize),
+        kernel_pos: (usize, usize),
+    ) -> Option<(usize, usize)> {
+        let row = out_pos.0 * self.stride.0 + kernel_pos.0 * self.dilation.0;
+        let col = out_pos.1 * self.stride.1 + kernel_pos.1 * self.dilation.1;
+
+        if row < self.padding.0 || col < self.padding.1 {
+            return None;
+        }
+
+        let (row, col) = (row - self.padding.0, col - self.padding.1);
+
+        if row >= x_dims.0 || col >= x_dims.1 {
+            return None;
+        }
+        Some((row, col))
+    }
+}
+
+#[inline]
+fn conv_out_len(
+    len: usize,
+    kernel_len: usize,
+    stride: usize,
+    padding: usize,
+    dilation: usize,
+) -> usize {
+    assert!(
+        stride > 0 && kernel_len > 0,
+        "The stride and the dimensions of the kernel must be positive."
+    );
+    let kernel_len = dilation * (kernel_len - 1) + 1;
+    assert!(
+        len + 2 * padding >= kernel_len,
+        "The (dilated) kernel is larger than the padded input."
+    );
+    (len + 2 * padding - kernel_len) / stride + 1
+}
+
+/// Cross-correlation of `lhs` and `kernel` using the stride, padding and dilation of `config`.
+/// `out` must have the length of `config.out_dims(lhs_dims, kernel_dims)`.
+pub fn correlate_mut<T: Number>(
+    lhs: &[T],
+    lhs_dims: (usize, usize),
+    kernel: &[T],
+    kernel_dims: (usize, usize),
+    config: &ConvConfig,
+    out: &mut [T],
+) {
+    correlate_indexed(lhs, lhs_dims, kernel, kernel_dims, config, out, |idx| idx)
+}
+
+/// Convolution of `lhs` and `kernel`, i.e. [`correlate_mut`] with the kernel rotated by 180 degrees.
+/// The kernel is read in reverse order, so no rotated copy of it is allocated.
+pub fn convolve_mut<T: Number>(
+    lhs: &[T],
+    lhs_dims: (usize, usize),
+    kernel: &[T],
+    kernel_dims: (usize, usize),
+    config: &ConvConfig,
+    out: &mut [T],
+) {
+    let len = kernel_dims.0 * kernel_dims.1;
+    correlate_indexed(lhs, lhs_dims, kernel, kernel_dims, config, out, |idx| {
+        len - 1 - idx
+    })
+}
+
+/// `kernel_idx` maps the row major index of a kernel position to the index that is read from `kernel`.
+fn correlate_indexed<T: Number>(
+    lhs: &[T],
+    lhs_dims: (usize, usize),
+    kernel: &[T],
+    kernel_dims: (usize, usize),
+    config: &ConvConfig,
+    out: &mut [T],
+    kernel_idx: impl Fn(usize) -> usize,
+) {
+    let (out_rows, out_cols) = config.out_dims(lhs_dims, kernel_dims);
+
+    for y in 0..out_rows {
+        for x in 0..out_cols {
+            let mut sum = T::default();
+            for ky in 0..kernel_dims.0 {
+                for kx in 0..kernel_dims.1 {
+                    if let Some((row, col)) = config.input_idx(lhs_dims, (y, x), (ky, kx)) {
+                        sum += lhs[row * lhs_dims.1 + col]
+                            * kernel[kernel_idx(ky * kernel_dims.1 + kx)];
+                    }
+                }
+            }
+            out[y * out_cols + x] = sum;
+        }
+    }
+}
+
+/// Gradient of [`correlate_mut`] with respect to the kernel.
+/// `grad` is the gradient of the correlation output, `out` has the dimensions of the kernel.
+pub fn correlate_grad_kernel_mut<T: Number>(
+    lhs: &[T],
+    lhs_dims: (usize, usize),
+    grad: &[T],
+    kernel_dims: (usize, usize),
+    config: &ConvConfig,
+    out: &mut [T],
+) {
+    let (out_rows, out_cols) = config.out_dims(lhs_dims, kernel_dims);
+
+    for ky in 0..kernel_dims.0 {
+        for kx in 0..kernel_dims.1 {
+            let mut sum = T::default();
+            for y in 0..out_rows {
+                for x in 0..out_cols {
+                    if let Some((row, col)) = config.input_idx(lhs_dims, (y, x), (ky, kx)) {
+                        sum += lhs[row * lhs_dims.1 + col] * grad[y * out_cols + x];
+                    }
+                }
+            }
+            out[ky * kernel_dims.1 + kx] = sum;
+        }
+    }
+}
+
+/// Gradient of [`correlate_mut`] with respect to the input.
+/// `grad` is the gradient of the correlation output, `out` has the dimensions of the input.
+pub fn correlate_grad_input_mut<T: Number>(
+    grad: &[T],
+    kernel: &[T],
+    kernel_dims: (usize, usize),
+    lhs_dims: (usize, usize),
+    config: &ConvConfig,
+    out: &mut [T],
+) {
+    let (out_rows, out_cols) = config.out_dims(lhs_dims, kernel_dims);
+
+    for value in out.iter_mut() {
+        *value = T::default();
+    }
+
+    for y in 0..out_rows {
+        for x in 0..out_cols {
+            let grad = grad[y * out_cols + x];
+            for ky in 0..kernel_dims.0 {
+                for kx in 0..kernel_dims.1 {
+                    if let Some((row, col)) = config.input_idx(lhs_dims, (y, x), (ky, kx)) {
+                        out[row * lhs_dims.1 + col] += grad * kernel[ky * kernel_dims.1 + kx];
+                    }
+                }
+            }
+        }
+    }
+}
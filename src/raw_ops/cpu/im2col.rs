@@ -0,0 +1,79 @@
+use custos::number::Number;
+
+use crate::ConvConfig;
+
+/// Unfolds every kernel sized patch of a `channels` x (`img_dims.0` * `img_dims.1`) input into a column.
+/// `out` has the dimensions (`channels` * `kernel_dims.0` * `kernel_dims.1`) x (`out_rows` * `out_cols`),
+/// where (`out_rows`, `out_cols`) = `config.out_dims(img_dims, kernel_dims)`.
+/// Values inside the zero padding are written as `T::default()`.
+pub fn im2col_mut<T: Number>(
+    x: &[T],
+    channels: usize,
+    img_dims: (usize, usize),
+    kernel_dims: (usize, usize),
+    config: &ConvConfig,
+    out: &mut [T],
+) {
+    let (out_rows, out_cols) = config.out_dims(img_dims, kernel_dims);
+    let (img_rows, img_cols) = img_dims;
+    let patches = out_rows * out_cols;
+
+    for channel in 0..channels {
+        let img = &x[channel * img_rows * img_cols..(channel + 1) * img_rows * img_cols];
+
+        for ky in 0..kernel_dims.0 {
+            for kx in 0..kernel_dims.1 {
+                let row = (channel * kernel_dims.0 + ky) * kernel_dims.1 + kx;
+                let out_row = &mut out[row * patches..(row + 1) * patches];
+
+                for y in 0..out_rows {
+                    for x in 0..out_cols {
+                        out_row[y * out_cols + x] =
+                            match config.input_idx(img_dims, (y, x), (ky, kx)) {
+                                Some((row, col)) => img[row * img_cols + col],
+                                None => T::default(),
+                            };
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The adjoint of [`im2col_mut`]: sums every column entry back into its position of the input.
+/// `out` has the dimensions `channels` x (`img_dims.0` * `img_dims.1`). Padding entries are dropped.
+pub fn col2im_mut<T: Number>(
+    cols: &[T],
+    channels: usize,
+    img_dims: (usize, usize),
+    kernel_dims: (usize, usize),
+    config: &ConvConfig,
+    out: &mut [T],
+) {
+    let (out_rows, out_cols) = config.out_dims(img_dims, kernel_dims);
+    let (img_rows, img_cols) = img_dims;
+    let patches = out_rows * out_cols;
+
+    for value in out.iter_mut() {
+        *value = T::default();
+    }
+
+    for channel in 0..channels {
+        let img = &mut out[channel * img_rows * img_cols..(channel + 1) * img_rows * img_cols];
+
+        for ky in 0..kernel_dims.0 {
+            for kx in 0..kernel_dims.1 {
+                let row = (channel * kernel_dims.0 + ky) * kernel_dims.1 + kx;
+                let cols_row = &cols[row * patches..(row + 1) * patches];
+
+                for y in 0..out_rows {
+                    for x in 0..out_cols {
+                        if let Some((row, col)) = config.input_idx(img_dims, (y, x), (ky, kx)) {
+                            img[row * img_cols + col] += cols_row[y * out_cols + x];
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use custos::number::Float;
+
+use crate::machine_epsilon;
+
+/// Upper bound of Jacobi sweeps. Both iterations converge quadratically, so this is never reached for finite input.
+const MAX_SWEEPS: usize = 64;
+
+/// Element `e` of the vector `p` is stored at `idx(p, e)`, e.g. `move |p, e| e * cols + p` for columns.
+fn dot<T: Float>(
+    x: &[T],
+    idx: impl Fn(usize, usize) -> usize,
+    p: usize,
+    q: usize,
+    len: usize,
+) -> T {
+    (0..len).fold(T::default(), |dot, e| dot + x[idx(p, e)] * x[idx(q, e)])
+}
+
+/// Replaces the vectors `p` and `q` with `c * p - s * q` and `s * p + c * q`.
+fn rotate<T: Float>(
+    x: &mut [T],
+    idx: impl Fn(usize, usize) -> usize,
+    p: usize,
+    q: usize,
+    len: usize,
+    c: T,
+    s: T,
+) {
+    for e in 0..len {
+        let xp = x[idx(p, e)];
+        let xq = x[idx(q, e)];
+        x[idx(p, e)] = c * xp - s * xq;
+        x[idx(q, e)] = s * xp + c * xq;
+    }
+}
+
+fn swap_vectors<T>(
+    x: &mut [T],
+    idx: impl Fn(usize, usize) -> usize,
+    p: usize,
+    q: usize,
+    len: usize,
+) {
+    for e in 0..len {
+        x.swap(idx(p, e), idx(q, e));
+    }
+}
+
+/// The smaller root `t = tan(phi)` of `t^2 + 2 * t * theta - 1 = 0`, which belongs to a rotation angle `|phi| <= pi / 4`.
+fn rotation<T: Float>(theta: T) -> (T, T) {
+    let sign = if theta >= T::default() {
+        T::one()
+    } else {
+        T::default() - T::one()
+    };
+    let t = sign / (theta.abs() + (theta * theta + T::one()).sqrt());
+    let c = T::one() / (t * t + T::one()).sqrt();
+    (c, t * c)
+}
+
+/// Eigendecomposition of the symmetric row major `n x n` matrix in `a` with the cyclic Jacobi method.
+///
+/// Only the lower triangle of `a` is read. `a` is overwritten.
+/// Writes the eigenvalues in ascending order into `w` and the corresponding orthonormal eigenvectors
+/// as columns of the `n x n` matrix `v`.
+pub fn eigh_slice<T: Float>(a: &mut [T], n: usize, w: &mut [T], v: &mut [T]) {
+    let col = move |p: usize, e: usize| e * n + p;
+    let row = move |p: usize, e: usize| p * n + e;
+
+    for r in 0..n {
+        for c in r + 1..n {
+            a[r * n + c] = a[c * n + r];
+        }
+    }
+
+    for (idx, value) in v.iter_mut().enumerate() {
+        *value = if idx / n == idx % n {
+            T::one()
+        } else {
+            T::default()
+        };
+    }
+
+    let eps = machine_epsilon::<T>();
+    let norm = a.iter().fold(T::default(), |sum, x| sum + *x * *x);
+
+    for _ in 0..MAX_SWEEPS {
+        let off = (0..n).fold(T::default(), |sum, p| {
+            (p + 1..n).fold(sum, |sum, q| sum + a[p * n + q] * a[p * n + q])
+        });
+        if off <= eps * eps * norm {
+            break;
+        }
+
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = a[p * n + q];
+                if apq == T::default() {
+                    continue;
+                }
+
+                let (c, s) = rotation((a[q * n + q] - a[p * n + p]) / (T::two() * apq));
+                rotate(a, col, p, q, n, c, s);
+                rotate(a, row, p, q, n, c, s);
+                rotate(v, col, p, q, n, c, s);
+            }
+        }
+    }
+
+    for (i, value) in w.iter_mut().enumerate() {
+        *value = a[i * n + i];
+    }
+
+    for i in 0..n {
+        let mut min = i;
+        for j in i + 1..n {
+            if w[j] < w[min] {
+                min = j;
+            }
+        }
+        if min != i {
+            w.swap(i, min);
+            swap_vectors(v, col, i, min, n);
+        }
+    }
+}
+
+/// One-sided Jacobi iteration: orthogonalises the `count` vectors of length `len` in `x`
+/// and applies the same rotations to the `count` vectors of length `count` in `acc`, which start as the identity.
+///
+/// Afterwards, `sigma` holds the norms of the vectors in descending order and the vectors are normalised.
+/// Norms below `max(len, count) * eps * max(sigma)` are flushed to zero and their vectors are replaced
+/// with unit vectors that are orthogonal to all vectors before them.
+#[allow(clippy::too_many_arguments)]
+fn one_sided_jacobi<T: Float>(
+    x: &mut [T],
+    x_idx: impl Fn(usize, usize) -> usize + Copy,
+    len: usize,
+    count: usize,
+    acc: &mut [T],
+    acc_idx: impl Fn(usize, usize) -> usize + Copy,
+    sigma: &mut [T],
+) {
+    for p in 0..count {
+        for e in 0..count {
+            acc[acc_idx(p, e)] = if p == e { T::one() } else { T::default() };
+        }
+    }
+
+    let eps = machine_epsilon::<T>();
+
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+
+        for p in 0..count {
+            for q in p + 1..count {
+                let alpha = dot(x, x_idx, p, p, len);
+                let beta = dot(x, x_idx, q, q, len);
+                let gamma = dot(x, x_idx, p, q, len);
+
+                // p and q are orthogonal to working precision
+                if gamma.abs() <= eps * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+
+                let (c, s) = rotation((beta - alpha) / (T::two() * gamma));
+                rotate(x, x_idx, p, q, len, c, s);
+                rotate(acc, acc_idx, p, q, count, c, s);
+            }
+        }
+
+        if !rotated {
+            break;
+        }
+    }
+
+    for (p, value) in sigma.iter_mut().enumerate() {
+        *value = dot(x, x_idx, p, p, len).sqrt();
+    }
+
+    for i in 0..count {
+        let mut max = i;
+        for j in i + 1..count {
+            if sigma[j] > sigma[max] {
+                max = j;
+            }
+        }
+        if max != i {
+            sigma.swap(i, max);
+            swap_vectors(x, x_idx, i, max, len);
+            swap_vectors(acc, acc_idx, i, max, count);
+        }
+    }
+
+    let tol = match sigma.first() {
+        Some(max) => T::from_usize(len.max(count)) * eps * *max,
+        None => return,
+    };
+
+    for p in 0..count {
+        if sigma[p] > tol {
+            for e in 0..len {
+                x[x_idx(p, e)] = x[x_idx(p, e)] / sigma[p];
+            }
+        } else {
+            sigma[p] = T::default();
+            complete_orthonormal(x, x_idx, len, p);
+        }
+    }
+}
+
+/// Overwrites the vector `p` with a unit vector that is orthogonal to the orthonormal vectors `0..p`.
+///
+/// The standard basis vectors are orthogonalised one after another (twice, for stability)
+/// until the remainder is large enough. As the squared remainders of all basis vectors sum up to `len - p`,
+/// one of them is at least `1 / len`.
+fn complete_orthonormal<T: Float>(
+    x: &mut [T],
+    idx: impl Fn(usize, usize) -> usize + Copy,
+    len: usize,
+    p: usize,
+) {
+    let threshold = T::one() / (T::two() * T::from_usize(len));
+
+    for basis in 0..len {
+        for e in 0..len {
+            x[idx(p, e)] = if e == basis { T::one() } else { T::default() };
+        }
+
+        for _ in 0..2 {
+            for other in 0..p {
+                let projection = dot(x, idx, other, p, len);
+                for e in 0..len {
+                    x[idx(p, e)] = x[idx(p, e)] - projection * x[idx(other, e)];
+                }
+            }
+        }
+
+        let norm_sq = dot(x, idx, p, p, len);
+        if norm_sq > threshold {
+            let norm = norm_sq.sqrt();
+            for e in 0..len {
+                x[idx(p, e)] = x[idx(p, e)] / norm;
+            }
+            return;
+        }
+    }
+}
+
+/// Thin singular value decomposition `a = u * diag(s) * vt` of the row major `m x n` matrix `a`
+/// with the one-sided Jacobi method, where `k = min(m, n)`.
+///
+/// Writes the `m x k` matrix `u`, the `k` singular values in descending order into `s` and the `k x n` matrix `vt`.
+/// The columns of `u` and the rows of `vt` are orthonormal.
+/// Singular values below `max(m, n) * eps * max(s)` are flushed to zero.
+pub fn svd_slice<T: Float>(a: &[T], m: usize, n: usize, u: &mut [T], s: &mut [T], vt: &mut [T]) {
+    if m >= n {
+        // orthogonalise the columns of a, the rotations accumulate to v
+        u.copy_from_slice(a);
+        one_sided_jacobi(u, move |p, e| e * n + p, m, n, vt, move |p, e| p * n + e, s);
+    } else {
+        // orthogonalise the rows of a, the rotations accumulate to u
+        vt.copy_from_slice(a);
+        one_sided_jacobi(vt, move |p, e| p * n + e, n, m, u, move |p, e| e * m + p, s);
+    }
+}
+
+/// Writes the `n x m` pseudo-inverse `vt^T * diag(s)^+ * u^T` of a thin singular value decomposition
+/// (see [`svd_slice`]) of a `m x n` matrix into `out`. Zero singular values are skipped.
+pub fn pinv_slice<T: Float>(u: &[T], s: &[T], vt: &[T], m: usize, n: usize, out: &mut [T]) {
+    let k = s.len();
+
+    for i in 0..n {
+        for j in 0..m {
+            out[i * m + j] = (0..k)
+                .filter(|l| s[*l] != T::default())
+                .fold(T::default(), |sum, l| {
+                    sum + vt[l * n + i] * u[j * k + l] / s[l]
+                });
+        }
+    }
+}
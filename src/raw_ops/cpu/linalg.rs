@@ -0,0 +1,195 @@
+use core::cmp::Ordering;
+
+use custos::number::Float;
+
+use crate::LinalgError;
+
+/// Returns the difference between 1 and the next larger representable number of `T`.
+pub fn machine_epsilon<T: Float>() -> T {
+    let mut eps = T::one();
+    while T::one() + eps / T::two() != T::one() {
+        eps = eps / T::two();
+    }
+    eps
+}
+
+/// The relative pivot tolerance `n * eps` of [`lu_in_place`] for a `n x n` matrix.
+pub fn pivot_tolerance<T: Float>(n: usize) -> T {
+    T::from_usize(n) * machine_epsilon::<T>()
+}
+
+/// Factorises the row major `n x n` matrix in `a` in place with partial pivoting.
+///
+/// Afterwards, the strict lower triangle of `a` holds the multipliers of the unit lower triangular `L`
+/// and the upper triangle holds `U`.
+/// `swap(k, pivot)` is called whenever the rows `k` and `pivot` are exchanged.
+///
+/// The pivot of column `k` is treated as zero if its absolute value is at most `rtol` times
+/// the largest absolute value in column `k` of the partially factorised matrix.
+/// Hence, the tolerance does not depend on the scaling of the other columns.
+/// With `rtol` set to zero, only exactly zero pivots are rejected.
+/// # Errors
+/// [`LinalgError::Singular`] if a pivot is treated as zero or is not finite. `a` is left partially factorised.
+pub fn lu_in_place<T: Float>(
+    a: &mut [T],
+    n: usize,
+    rtol: T,
+    mut swap: impl FnMut(usize, usize),
+) -> Result<(), LinalgError> {
+    for k in 0..n {
+        let mut pivot = k;
+        let mut column_max = T::default();
+        for row in 0..n {
+            let value = a[row * n + k].abs();
+            if value > column_max {
+                column_max = value;
+            }
+            if row > k && value > a[pivot * n + k].abs() {
+                pivot = row;
+            }
+        }
+
+        let value = a[pivot * n + k];
+        // x * 0 is NaN for infinite and NaN values
+        if value.abs() <= rtol * column_max || value * T::default() != T::default() {
+            return Err(LinalgError::Singular { pivot: k });
+        }
+
+        if pivot != k {
+            for col in 0..n {
+                a.swap(k * n + col, pivot * n + col);
+            }
+            swap(k, pivot);
+        }
+
+        let diag = a[k * n + k];
+        for row in k + 1..n {
+            let factor = a[row * n + k] / diag;
+            a[row * n + k] = factor;
+
+            for col in k + 1..n {
+                a[row * n + col] = a[row * n + col] - factor * a[k * n + col];
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Solves `L * U * x = b` for the `nrhs` columns of the row major `n x nrhs` matrix `b` in place.
+/// `lu` is the output of [`lu_in_place`] and the rows of `b` must already be swapped like the rows of `lu`.
+pub fn lu_solve_in_place<T: Float>(lu: &[T], n: usize, b: &mut [T], nrhs: usize) {
+    // forward substitution with the unit lower triangular L
+    for row in 1..n {
+        for k in 0..row {
+            let factor = lu[row * n + k];
+            for col in 0..nrhs {
+                b[row * nrhs + col] = b[row * nrhs + col] - factor * b[k * nrhs + col];
+            }
+        }
+    }
+
+    // back substitution with U
+    for row in (0..n).rev() {
+        for k in row + 1..n {
+            let factor = lu[row * n + k];
+            for col in 0..nrhs {
+                b[row * nrhs + col] = b[row * nrhs + col] - factor * b[k * nrhs + col];
+            }
+        }
+
+        let diag = lu[row * n + row];
+        for col in 0..nrhs {
+            b[row * nrhs + col] = b[row * nrhs + col] / diag;
+        }
+    }
+}
+
+/// Swaps the rows `i` and `j` of a row major matrix with `cols` columns.
+pub fn swap_rows<T>(x: &mut [T], cols: usize, i: usize, j: usize) {
+    for col in 0..cols {
+        x.swap(i * cols + col, j * cols + col);
+    }
+}
+
+/// Writes the `n x n` identity matrix into `out`.
+pub fn identity_slice<T: Float>(out: &mut [T], n: usize) {
+    for (idx, value) in out.iter_mut().enumerate() {
+        *value = if idx / n == idx % n {
+            T::one()
+        } else {
+            T::default()
+        };
+    }
+}
+
+/// Replaces the symmetric positive-definite `n x n` matrix in `a` with its lower triangular Cholesky factor `L`, `a = L * L^T`.
+/// Only the lower triangle of `a` is read and the upper triangle is set to zero.
+/// # Errors
+/// [`LinalgError::NotPositiveDefinite`] if a pivot is not positive.
+pub fn cholesky_in_place<T: Float>(a: &mut [T], n: usize) -> Result<(), LinalgError> {
+    for col in 0..n {
+        let mut diag = a[col * n + col];
+        for k in 0..col {
+            diag = diag - a[col * n + k] * a[col * n + k];
+        }
+
+        // NaN is not ordered and fails as well
+        if diag.partial_cmp(&T::default()) != Some(Ordering::Greater) {
+            return Err(LinalgError::NotPositiveDefinite { pivot: col });
+        }
+        let diag = diag.sqrt();
+        a[col * n + col] = diag;
+
+        for row in col + 1..n {
+            let mut value = a[row * n + col];
+            for k in 0..col {
+                value = value - a[row * n + k] * a[col * n + k];
+            }
+            a[row * n + col] = value / diag;
+        }
+
+        for upper in col + 1..n {
+            a[col * n + upper] = T::default();
+        }
+    }
+    Ok(())
+}
+
+/// Solves `L * L^T * x = b` for the `nrhs` columns of the row major `n x nrhs` matrix `b` in place.
+/// `l` is the output of [`cholesky_in_place`].
+pub fn cholesky_solve_in_place<T: Float>(l: &[T], n: usize, b: &mut [T], nrhs: usize) {
+    // L * y = b
+    for row in 0..n {
+        for k in 0..row {
+            let factor = l[row * n + k];
+            for col in 0..nrhs {
+                b[row * nrhs + col] = b[row * nrhs + col] - factor * b[k * nrhs + col];
+            }
+        }
+
+        let diag = l[row * n + row];
+        for col in 0..nrhs {
+            b[row * nrhs + col] = b[row * nrhs + col] / diag;
+        }
+    }
+
+    // L^T * x = y
+    for row in (0..n).rev() {
+        for k in row + 1..n {
+            let factor = l[k * n + row];
+            for col in 0..nrhs {
+                b[row * nrhs + col] = b[row * nrhs + col] - factor * b[k * nrhs + col];
+            }
+        }
+
+        let diag = l[row * n + row];
+        for col in 0..nrhs {
+            b[row * nrhs + col] = b[row * nrhs + col] / diag;
+        }
+    }
+}
+
+/// The logarithm of the determinant of `L * L^T`, where `l` is a `n x n` Cholesky factor.
+pub fn cholesky_logdet_slice<T: Float>(l: &[T], n: usize) -> T {
+    (0..n).fold(T::default(), |logdet, k| logdet + l[k * n + k].ln()) * T::two()
+}
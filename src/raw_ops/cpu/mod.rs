@@ -1,9 +1,23 @@
 mod assign_to_lhs;
 mod correlate;
 mod ew;
+mod im2col;
+mod jacobi;
+mod linalg;
 mod naive_gemm;
+mod norm;
+mod optim;
+mod pool;
+mod qr;
 
 pub use assign_to_lhs::*;
 pub use correlate::*;
 pub use ew::*;
+pub use im2col::*;
+pub use jacobi::*;
+pub use linalg::*;
 pub use naive_gemm::*;
+pub use norm::*;
+pub use optim::*;
+pub use pool::*;
+pub use qr::*;
@@ -1,4 +1,4 @@
-use core::ops::{AddAssign, Mul};
+use core::ops::{Add, AddAssign, Mul};
 
 pub fn naive_gemm<T>(m: usize, k: usize, n: usize, a: &[T], b: &[T], c: &mut [T])
 where
@@ -16,6 +16,69 @@ where
     }
 }
 
+/// Computes `op(a) * op(b)`, where `op` transposes the operand if `trans_a` / `trans_b` is set.
+/// `op(a)` is a m x k matrix and `op(b)` is a k x n matrix.
+/// The operands are read in place, hence no transposed copy is created.
+#[allow(clippy::too_many_arguments)]
+pub fn naive_gemm_trans<T>(
+    trans_a: bool,
+    trans_b: bool,
+    m: usize,
+    k: usize,
+    n: usize,
+    a: &[T],
+    b: &[T],
+    c: &mut [T],
+) where
+    T: Mul<Output = T> + Copy + Default + AddAssign,
+{
+    let (rsa, csa) = if trans_a { (1, m) } else { (k, 1) };
+    let (rsb, csb) = if trans_b { (1, k) } else { (n, 1) };
+
+    for row in 0..m {
+        for col in 0..n {
+            let mut acc = T::default();
+            for elem in 0..k {
+                acc += a[row * rsa + elem * csa] * b[elem * rsb + col * csb];
+            }
+            c[row * n + col] = acc;
+        }
+    }
+}
+
+/// Computes `c = alpha * a * b + beta * c`, where `a` is a m x k matrix and `b` is a k x n matrix.
+/// If `beta` is zero, `c` is not read.
+#[allow(clippy::too_many_arguments)]
+pub fn naive_gemm_acc<T>(
+    m: usize,
+    k: usize,
+    n: usize,
+    alpha: T,
+    a: &[T],
+    b: &[T],
+    beta: T,
+    c: &mut [T],
+) where
+    T: Mul<Output = T> + Add<Output = T> + Copy + Default + AddAssign + PartialEq,
+{
+    for row in 0..m {
+        for col in 0..n {
+            let a_row = &a[row * k..row * k + k];
+            let mut acc = T::default();
+            for elem in 0..k {
+                acc += a_row[elem] * b[elem * n + col];
+            }
+
+            let c = &mut c[row * n + col];
+            *c = if beta == T::default() {
+                alpha * acc
+            } else {
+                alpha * acc + beta * *c
+            };
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
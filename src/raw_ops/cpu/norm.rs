@@ -0,0 +1,134 @@
+use custos::number::Float;
+
+use crate::machine_epsilon;
+
+/// Upper bound of power iterations in [`spectral_norm_slice`].
+pub const MAX_POWER_ITERATIONS: usize = 1000;
+
+/// The square root of the sum of all squared values.
+pub fn frobenius_norm_slice<T: Float>(x: &[T]) -> T {
+    x.iter()
+        .fold(T::default(), |sum, value| sum + *value * *value)
+        .sqrt()
+}
+
+/// `(sum |x|^p)^(1/p)` over all values of `x`. `p` must be positive.
+pub fn elementwise_norm_slice<T: Float>(x: &[T], p: T) -> T {
+    if p == T::one() {
+        return x.iter().fold(T::default(), |sum, value| sum + value.abs());
+    }
+    if p == T::two() {
+        return frobenius_norm_slice(x);
+    }
+
+    let sum = x.iter().fold(T::default(), |sum, value| {
+        sum + non_negative_pow(value.abs(), p)
+    });
+    non_negative_pow(sum, T::one() / p)
+}
+
+/// `base^exp` for a non-negative `base`.
+pub(crate) fn non_negative_pow<T: Float>(base: T, exp: T) -> T {
+    if base == T::default() {
+        return T::default();
+    }
+    (exp * base.ln()).exp()
+}
+
+/// The maximum absolute column sum of the row major `rows x cols` matrix `x`.
+pub fn l1_norm_slice<T: Float>(x: &[T], rows: usize, cols: usize) -> T {
+    (0..cols)
+        .map(|col| (0..rows).fold(T::default(), |sum, row| sum + x[row * cols + col].abs()))
+        .fold(T::default(), |max, sum| if sum > max { sum } else { max })
+}
+
+/// The maximum absolute row sum of the row major `rows x cols` matrix `x`.
+pub fn inf_norm_slice<T: Float>(x: &[T], rows: usize, cols: usize) -> T {
+    (0..rows)
+        .map(|row| {
+            x[row * cols..(row + 1) * cols]
+                .iter()
+                .fold(T::default(), |sum, value| sum + value.abs())
+        })
+        .fold(T::default(), |max, sum| if sum > max { sum } else { max })
+}
+
+/// The sum of the main diagonal of the row major `rows x cols` matrix `x`.
+pub fn trace_slice<T: Float>(x: &[T], rows: usize, cols: usize) -> T {
+    (0..rows.min(cols)).fold(T::default(), |sum, idx| sum + x[idx * (cols + 1)])
+}
+
+/// Estimates the largest singular value of the row major `rows x cols` matrix `x`
+/// with a power iteration on `x^T * x`, using `v` (`cols` values) and `w` (`rows` values) as scratch space.
+///
+/// The start vector is `x^T * s`, where the signs `s_i = ±1` are chosen one row after another
+/// so that the start vector grows. Therefore, it is only zero if `x` is zero.
+/// The iteration stops once the estimate does not increase anymore or after [`MAX_POWER_ITERATIONS`].
+pub fn spectral_norm_slice<T: Float>(
+    x: &[T],
+    rows: usize,
+    cols: usize,
+    v: &mut [T],
+    w: &mut [T],
+) -> T {
+    for value in v.iter_mut() {
+        *value = T::default();
+    }
+
+    for row in 0..rows {
+        let row = &x[row * cols..(row + 1) * cols];
+        let dot = row
+            .iter()
+            .zip(v.iter())
+            .fold(T::default(), |dot, (x, v)| dot + *x * *v);
+
+        for (v, x) in v.iter_mut().zip(row) {
+            *v = if dot >= T::default() {
+                *v + *x
+            } else {
+                *v - *x
+            };
+        }
+    }
+
+    let norm = frobenius_norm_slice(v);
+    if norm == T::default() {
+        return T::default();
+    }
+    for value in v.iter_mut() {
+        *value = *value / norm;
+    }
+
+    let tol = T::from_usize(4) * machine_epsilon::<T>();
+    let mut sigma = T::default();
+
+    for _ in 0..MAX_POWER_ITERATIONS {
+        // w = x * v
+        for (row, value) in w.iter_mut().enumerate() {
+            *value = x[row * cols..(row + 1) * cols]
+                .iter()
+                .zip(v.iter())
+                .fold(T::default(), |sum, (x, v)| sum + *x * *v);
+        }
+        let next = frobenius_norm_slice(w);
+
+        // v = x^T * w
+        for (col, value) in v.iter_mut().enumerate() {
+            *value = (0..rows).fold(T::default(), |sum, row| sum + x[row * cols + col] * w[row]);
+        }
+        let norm = frobenius_norm_slice(v);
+        if norm == T::default() {
+            return next;
+        }
+        for value in v.iter_mut() {
+            *value = *value / norm;
+        }
+
+        let converged = next - sigma <= tol * next;
+        sigma = next;
+        if converged {
+            break;
+        }
+    }
+    sigma
+}
@@ -0,0 +1,88 @@
+use custos::number::Float;
+
+/// SGD with momentum: `velocity = mu * velocity + grad`.
+/// Afterwards, `param -= lr * velocity` or, with `nesterov`, `param -= lr * (grad + mu * velocity)`.
+pub fn sgd_momentum_step_slice<T: Float>(
+    param: &mut [T],
+    grad: &[T],
+    velocity: &mut [T],
+    lr: T,
+    mu: T,
+    nesterov: bool,
+) {
+    for ((param, grad), velocity) in param.iter_mut().zip(grad).zip(velocity.iter_mut()) {
+        *velocity = mu * *velocity + *grad;
+
+        let step = if nesterov {
+            *grad + mu * *velocity
+        } else {
+            *velocity
+        };
+        *param = *param - lr * step;
+    }
+}
+
+/// Adam step with bias corrected moments. `t` is the (1-based) number of the step.
+/// A non-zero `weight_decay` is decoupled from the gradient (AdamW): `param -= lr * weight_decay * param`.
+#[allow(clippy::too_many_arguments)]
+pub fn adam_step_slice<T: Float>(
+    param: &mut [T],
+    grad: &[T],
+    m: &mut [T],
+    v: &mut [T],
+    lr: T,
+    betas: (T, T),
+    eps: T,
+    t: usize,
+    weight_decay: T,
+) {
+    let (beta1, beta2) = betas;
+    let (correction1, correction2) = adam_bias_corrections(betas, t);
+
+    for (((param, grad), m), v) in param
+        .iter_mut()
+        .zip(grad)
+        .zip(m.iter_mut())
+        .zip(v.iter_mut())
+    {
+        *m = beta1 * *m + (T::one() - beta1) * *grad;
+        *v = beta2 * *v + (T::one() - beta2) * *grad * *grad;
+
+        let m_hat = *m / correction1;
+        let v_hat = *v / correction2;
+
+        *param = *param - lr * (weight_decay * *param + m_hat / (v_hat.sqrt() + eps));
+    }
+}
+
+/// Returns `(1 - beta1^t, 1 - beta2^t)`.
+pub fn adam_bias_corrections<T: Float>(betas: (T, T), t: usize) -> (T, T) {
+    assert!(t > 0, "The steps of adam are counted from 1.");
+    (
+        T::one() - betas.0.powi(t as i32),
+        T::one() - betas.1.powi(t as i32),
+    )
+}
+
+/// RMSprop: `sq_avg = alpha * sq_avg + (1 - alpha) * grad^2`, `param -= lr * grad / (sqrt(sq_avg) + eps)`.
+pub fn rmsprop_step_slice<T: Float>(
+    param: &mut [T],
+    grad: &[T],
+    sq_avg: &mut [T],
+    lr: T,
+    alpha: T,
+    eps: T,
+) {
+    for ((param, grad), sq_avg) in param.iter_mut().zip(grad).zip(sq_avg.iter_mut()) {
+        *sq_avg = alpha * *sq_avg + (T::one() - alpha) * *grad * *grad;
+        *param = *param - lr * *grad / (sq_avg.sqrt() + eps);
+    }
+}
+
+/// AdaGrad: `sq_sum += grad^2`, `param -= lr * grad / (sqrt(sq_sum) + eps)`.
+pub fn adagrad_step_slice<T: Float>(param: &mut [T], grad: &[T], sq_sum: &mut [T], lr: T, eps: T) {
+    for ((param, grad), sq_sum) in param.iter_mut().zip(grad).zip(sq_sum.iter_mut()) {
+        *sq_sum = *sq_sum + *grad * *grad;
+        *param = *param - lr * *grad / (sq_sum.sqrt() + eps);
+    }
+}
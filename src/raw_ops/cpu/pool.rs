@@ -0,0 +1,163 @@
+use custos::number::Number;
+
+use crate::ConvConfig;
+
+/// Max pooling of a `channels` x (`img_dims.0` * `img_dims.1`) input.
+/// `out` and `indices` have the dimensions `channels` x (`out_rows` * `out_cols`),
+/// where (`out_rows`, `out_cols`) = `config.out_dims(img_dims, kernel_dims)`.
+///
+/// `indices` receives the position of every maximum inside its channel (`row * img_dims.1 + col`).
+/// Padding entries are never selected. If a value occurs more than once in a window, the first position is used.
+/// If no tap of a window lies inside the input, its output entry is zero and its index is `u32::MAX`,
+/// which [`max_pool2d_grad_mut`] skips.
+pub fn max_pool2d_mut<T: Number>(
+    x: &[T],
+    channels: usize,
+    img_dims: (usize, usize),
+    kernel_dims: (usize, usize),
+    config: &ConvConfig,
+    out: &mut [T],
+    indices: &mut [u32],
+) {
+    let (out_rows, out_cols) = config.out_dims(img_dims, kernel_dims);
+    let (img_rows, img_cols) = img_dims;
+    let patches = out_rows * out_cols;
+
+    for channel in 0..channels {
+        let img = &x[channel * img_rows * img_cols..(channel + 1) * img_rows * img_cols];
+        let out = &mut out[channel * patches..(channel + 1) * patches];
+        let indices = &mut indices[channel * patches..(channel + 1) * patches];
+
+        for y in 0..out_rows {
+            for x in 0..out_cols {
+                let mut max: Option<(T, usize)> = None;
+
+                for ky in 0..kernel_dims.0 {
+                    for kx in 0..kernel_dims.1 {
+                        if let Some((row, col)) = config.input_idx(img_dims, (y, x), (ky, kx)) {
+                            let idx = row * img_cols + col;
+
+                            match max {
+                                Some((value, _)) if img[idx] <= value => {}
+                                _ => max = Some((img[idx], idx)),
+                            }
+                        }
+                    }
+                }
+
+                let (value, idx) =
+                    max.map_or((T::default(), u32::MAX), |(value, idx)| (value, idx as u32));
+                out[y * out_cols + x] = value;
+                indices[y * out_cols + x] = idx;
+            }
+        }
+    }
+}
+
+/// Scatters the `channels` x patches `grads` of a max pooling back to the positions in `indices`.
+/// `out` has the dimensions `channels` x `img_len`. Gradients of overlapping windows are summed up,
+/// gradients of windows with the index `u32::MAX` are dropped.
+pub fn max_pool2d_grad_mut<T: Number>(
+    grads: &[T],
+    indices: &[u32],
+    channels: usize,
+    img_len: usize,
+    out: &mut [T],
+) {
+    for value in out.iter_mut() {
+        *value = T::default();
+    }
+
+    if channels == 0 {
+        return;
+    }
+    let patches = grads.len() / channels;
+
+    for channel in 0..channels {
+        let out = &mut out[channel * img_len..(channel + 1) * img_len];
+        let grads = &grads[channel * patches..(channel + 1) * patches];
+        let indices = &indices[channel * patches..(channel + 1) * patches];
+
+        for (grad, idx) in grads.iter().zip(indices) {
+            if *idx != u32::MAX {
+                out[*idx as usize] += *grad;
+            }
+        }
+    }
+}
+
+/// Average pooling of a `channels` x (`img_dims.0` * `img_dims.1`) input.
+/// Padding entries count as zeros, hence every window is divided by the kernel size.
+/// `out` has the dimensions `channels` x (`out_rows` * `out_cols`).
+pub fn avg_pool2d_mut<T: Number>(
+    x: &[T],
+    channels: usize,
+    img_dims: (usize, usize),
+    kernel_dims: (usize, usize),
+    config: &ConvConfig,
+    out: &mut [T],
+) {
+    let (out_rows, out_cols) = config.out_dims(img_dims, kernel_dims);
+    let (img_rows, img_cols) = img_dims;
+    let patches = out_rows * out_cols;
+    let kernel_len = T::from_usize(kernel_dims.0 * kernel_dims.1);
+
+    for channel in 0..channels {
+        let img = &x[channel * img_rows * img_cols..(channel + 1) * img_rows * img_cols];
+        let out = &mut out[channel * patches..(channel + 1) * patches];
+
+        for y in 0..out_rows {
+            for x in 0..out_cols {
+                let mut sum = T::default();
+
+                for ky in 0..kernel_dims.0 {
+                    for kx in 0..kernel_dims.1 {
+                        if let Some((row, col)) = config.input_idx(img_dims, (y, x), (ky, kx)) {
+                            sum += img[row * img_cols + col];
+                        }
+                    }
+                }
+                out[y * out_cols + x] = sum / kernel_len;
+            }
+        }
+    }
+}
+
+/// Distributes every gradient of an average pooling evenly over its window.
+/// `out` has the dimensions `channels` x (`img_dims.0` * `img_dims.1`). Padding entries are dropped.
+pub fn avg_pool2d_grad_mut<T: Number>(
+    grads: &[T],
+    channels: usize,
+    img_dims: (usize, usize),
+    kernel_dims: (usize, usize),
+    config: &ConvConfig,
+    out: &mut [T],
+) {
+    let (out_rows, out_cols) = config.out_dims(img_dims, kernel_dims);
+    let (img_rows, img_cols) = img_dims;
+    let patches = out_rows * out_cols;
+    let kernel_len = T::from_usize(kernel_dims.0 * kernel_dims.1);
+
+    for value in out.iter_mut() {
+        *value = T::default();
+    }
+
+    for channel in 0..channels {
+        let out = &mut out[channel * img_rows * img_cols..(channel + 1) * img_rows * img_cols];
+        let grads = &grads[channel * patches..(channel + 1) * patches];
+
+        for y in 0..out_rows {
+            for x in 0..out_cols {
+                let grad = grads[y * out_cols + x] / kernel_len;
+
+                for ky in 0..kernel_dims.0 {
+                    for kx in 0..kernel_dims.1 {
+                        if let Some((row, col)) = config.input_idx(img_dims, (y, x), (ky, kx)) {
+                            out[row * img_cols + col] += grad;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use custos::number::Float;
+
+use crate::machine_epsilon;
+
+/// Turns the column `col` of the row major `a` (with `cols` columns) into a Householder vector `v`,
+/// starting at row `start`, so that `(I - 2 * v * v^T / (v^T * v)) * x = alpha * e_1`,
+/// where `x` is the column before the call.
+/// Returns `(alpha, v^T * v)`. A zero column results in a zero vector.
+fn householder<T: Float>(
+    a: &mut [T],
+    rows: usize,
+    cols: usize,
+    start: usize,
+    col: usize,
+) -> (T, T) {
+    let norm = (start..rows)
+        .fold(T::default(), |sum, row| {
+            sum + a[row * cols + col] * a[row * cols + col]
+        })
+        .sqrt();
+
+    let x0 = a[start * cols + col];
+    let alpha = if x0 > T::default() {
+        T::default() - norm
+    } else {
+        norm
+    };
+    a[start * cols + col] = x0 - alpha;
+
+    let vv = (start..rows).fold(T::default(), |sum, row| {
+        sum + a[row * cols + col] * a[row * cols + col]
+    });
+    (alpha, vv)
+}
+
+/// Applies the Householder reflection with the vector `v` (the elements `rows` of the column `v_col` in `v`)
+/// to the elements `rows` of all columns in `targets` of the row major `x` with `x_cols` columns.
+#[allow(clippy::too_many_arguments)]
+fn reflect<T: Float>(
+    v: &[T],
+    v_cols: usize,
+    v_col: usize,
+    vv: T,
+    x: &mut [T],
+    x_cols: usize,
+    targets: core::ops::Range<usize>,
+    rows: core::ops::Range<usize>,
+) {
+    if vv == T::default() {
+        return;
+    }
+
+    for col in targets {
+        let dot = rows.clone().fold(T::default(), |dot, row| {
+            dot + v[row * v_cols + v_col] * x[row * x_cols + col]
+        });
+        let factor = T::two() * dot / vv;
+
+        for row in rows.clone() {
+            x[row * x_cols + col] = x[row * x_cols + col] - factor * v[row * v_cols + v_col];
+        }
+    }
+}
+
+/// Householder QR decomposition of the row major `m x n` matrix in `a`.
+///
+/// Writes the `m x q_cols` matrix `Q` into `q` and the `q_cols x n` matrix `R` into `r`.
+/// `q_cols = min(m, n)` results in the thin decomposition, `q_cols = m` in the full one.
+/// `a` is overwritten with the Householder vectors.
+pub fn qr_slice<T: Float>(
+    a: &mut [T],
+    m: usize,
+    n: usize,
+    q: &mut [T],
+    q_cols: usize,
+    r: &mut [T],
+) {
+    let k = m.min(n);
+
+    for value in r.iter_mut() {
+        *value = T::default();
+    }
+
+    for j in 0..k {
+        let (alpha, vv) = householder(a, m, n, j, j);
+        reflect_in_place(a, n, j, vv, j + 1..n, j..m);
+
+        r[j * n + j] = alpha;
+        for col in j + 1..n {
+            r[j * n + col] = a[j * n + col];
+        }
+    }
+
+    identity_rect(q, m, q_cols);
+    for j in (0..k).rev() {
+        let vv = (j..m).fold(T::default(), |sum, row| {
+            sum + a[row * n + j] * a[row * n + j]
+        });
+        reflect(a, n, j, vv, q, q_cols, 0..q_cols, j..m);
+    }
+}
+
+/// Like [`reflect`], but the Householder vector is the column `v_col` of `a` itself.
+fn reflect_in_place<T: Float>(
+    a: &mut [T],
+    cols: usize,
+    v_col: usize,
+    vv: T,
+    targets: core::ops::Range<usize>,
+    rows: core::ops::Range<usize>,
+) {
+    if vv == T::default() {
+        return;
+    }
+
+    for col in targets {
+        let dot = rows.clone().fold(T::default(), |dot, row| {
+            dot + a[row * cols + v_col] * a[row * cols + col]
+        });
+        let factor = T::two() * dot / vv;
+
+        for row in rows.clone() {
+            a[row * cols + col] = a[row * cols + col] - factor * a[row * cols + v_col];
+        }
+    }
+}
+
+fn identity_rect<T: Float>(out: &mut [T], rows: usize, cols: usize) {
+    for row in 0..rows {
+        for col in 0..cols {
+            out[row * cols + col] = if row == col { T::one() } else { T::default() };
+        }
+    }
+}
+
+/// Least squares solution of `a * x = b` for the row major `m x n` matrix `a` and the `m x nrhs` matrix `b`.
+///
+/// Columns of `a` that are (numerically) linearly dependent on the columns before them get a zero coefficient,
+/// i.e. rank deficient systems result in a basic solution.
+/// Writes the `n x nrhs` solution into `x` and the squared euclidean norms of the columns of `b - a * x` into `residuals`.
+/// `a` and `b` are overwritten and the rank of `a` is returned.
+pub fn lstsq_slice<T: Float>(
+    a: &mut [T],
+    m: usize,
+    n: usize,
+    b: &mut [T],
+    nrhs: usize,
+    x: &mut [T],
+    residuals: &mut [T],
+) -> usize {
+    let max_col_norm = (0..n)
+        .map(|col| {
+            (0..m)
+                .fold(T::default(), |sum, row| {
+                    sum + a[row * n + col] * a[row * n + col]
+                })
+                .sqrt()
+        })
+        .fold(
+            T::default(),
+            |max, norm| if norm > max { norm } else { max },
+        );
+    let tol = T::from_usize(m.max(n)) * machine_epsilon::<T>() * max_col_norm;
+
+    let mut rank = 0;
+    for j in 0..n {
+        if rank == m {
+            break;
+        }
+
+        let norm = (rank..m)
+            .fold(T::default(), |sum, row| {
+                sum + a[row * n + j] * a[row * n + j]
+            })
+            .sqrt();
+
+        // the column is (almost) a combination of the previous ones
+        if norm <= tol {
+            for row in rank..m {
+                a[row * n + j] = T::default();
+            }
+            continue;
+        }
+
+        let (alpha, vv) = householder(a, m, n, rank, j);
+        reflect_in_place(a, n, j, vv, j + 1..n, rank..m);
+        reflect(a, n, j, vv, b, nrhs, 0..nrhs, rank..m);
+
+        a[rank * n + j] = alpha;
+        for row in rank + 1..m {
+            a[row * n + j] = T::default();
+        }
+        rank += 1;
+    }
+
+    for value in x.iter_mut() {
+        *value = T::default();
+    }
+
+    // a is in row echelon form now, the first non-zero element of a row is its pivot
+    for row in (0..rank).rev() {
+        let pivot = (0..n)
+            .find(|col| a[row * n + col] != T::default())
+            .expect("every row above the rank has a pivot");
+
+        for rhs in 0..nrhs {
+            let mut value = b[row * nrhs + rhs];
+            for col in pivot + 1..n {
+                value = value - a[row * n + col] * x[col * nrhs + rhs];
+            }
+            x[pivot * nrhs + rhs] = value / a[row * n + pivot];
+        }
+    }
+
+    for (rhs, residual) in residuals.iter_mut().enumerate() {
+        *residual = (rank..m).fold(T::default(), |sum, row| {
+            sum + b[row * nrhs + rhs] * b[row * nrhs + rhs]
+        });
+    }
+    rank
+}
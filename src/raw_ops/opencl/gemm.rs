@@ -25,6 +25,24 @@ pub fn cl_gemm<'a, T: CDatatype>(
     lhs: &CLBuffer<T>,
     rhs: &CLBuffer<T>,
 ) -> Result<CLBuffer<'a, T>, Error> {
+    let (src, f, s) = gemm_src::<T>(m, k, n, "", false);
+    let gws = [f, s, 0];
+
+    let out: CLBuffer<T> = device.retrieve(n * m, (lhs.node.idx, rhs.node.idx));
+    enqueue_kernel(device, &src, gws, None, &[lhs, rhs, &out])?;
+    Ok(out)
+}
+
+/// Returns the source of the tiled gemm kernel and the global work size of the first two dimensions.
+/// `batch_offsets` is inserted at the start of the kernel and may move the A, B and C pointers.
+/// If `accumulate` is set, the kernel takes the additional arguments `alpha` and `beta` and stores `C = alpha * A * B + beta * C`.
+fn gemm_src<T: CDatatype>(
+    m: usize,
+    k: usize,
+    n: usize,
+    batch_offsets: &str,
+    accumulate: bool,
+) -> (String, usize, usize) {
     let mut mw = 1;
     for x in &[16, 8, 4, 2, 1] {
         if m % x == 0 {
@@ -63,6 +81,25 @@ pub fn cl_gemm<'a, T: CDatatype>(
 
     let dt = T::as_c_type_str();
 
+    let (acc_args, store) = if accumulate {
+        (
+            format!(", const {dt} alpha, const {dt} beta"),
+            "{
+                        floatMW ct = *( (floatMW*) CT[n]);
+                        size_t idx = (nc*NW + n)*MT + mt;
+                        if (beta == 0)
+                            C[idx] = alpha * ct;
+                        else
+                            C[idx] = alpha * ct + beta * C[idx];
+                    }",
+        )
+    } else {
+        (
+            String::new(),
+            "C[(nc*NW + n)*MT + mt] = *( (floatMW*) CT[n]);",
+        )
+    };
+
     let src = format!("
         #define K {k}
         #define N {n}
@@ -73,10 +110,11 @@ pub fn cl_gemm<'a, T: CDatatype>(
         #define KT {kt}  // KT is max for 'kt' (K tile count)
         #define floatMW {float_mw}
         #define floatKW {float_kw}
-        __kernel void GeMM(const __global floatMW* restrict A, const __global floatKW* restrict B, __global floatMW* C)
+        __kernel void GeMM(const __global floatMW* restrict A, const __global floatKW* restrict B, __global floatMW* C{acc_args})
             {{
                 size_t mt = get_global_id(0);    //global M-tile id
                 size_t nc = get_global_id(1);    //global N-tile id
+                {batch_offsets}
 
                 {dt} AT[KW][MW]; // sub tiles
                 {dt} BT[NW][KW];
@@ -107,12 +145,82 @@ pub fn cl_gemm<'a, T: CDatatype>(
 
                 #pragma unroll
                 for (uint n=0; n<NW; ++n)
-                    C[(nc*NW + n)*MT + mt] = *( (floatMW*) CT[n]);
+                    {store}
             }}");
 
+    (src, f, s)
+}
+
+/// Computes `out = alpha * lhs * rhs + beta * out`, where `lhs` is a m x k matrix and `rhs` is a k x n matrix.
+/// If `beta` is zero, `out` is not read.
+#[allow(clippy::too_many_arguments)]
+pub fn cl_gemm_acc<T: CDatatype>(
+    device: &OpenCL,
+    m: usize,
+    k: usize,
+    n: usize,
+    alpha: T,
+    lhs: &CLBuffer<T>,
+    rhs: &CLBuffer<T>,
+    beta: T,
+    out: &mut CLBuffer<T>,
+) -> Result<(), Error> {
+    // like in cl_gemm, the kernel computes the transposed product in column major order
+    let (src, f, s) = gemm_src::<T>(n, k, m, "", true);
     let gws = [f, s, 0];
 
-    let out: CLBuffer<T> = device.retrieve(n * m, (lhs.node.idx, rhs.node.idx));
-    enqueue_kernel(device, &src, gws, None, &[lhs, rhs, &out])?;
+    enqueue_kernel(device, &src, gws, None, &[rhs, lhs, out, &alpha, &beta])?;
+    Ok(())
+}
+
+/// OpenCL batched matrix multiplication.
+/// `lhs` stores `batch` m x k matrices, `rhs` stores either `batch` or a single k x n matrix.
+/// A single rhs matrix is broadcasted to every lhs matrix.
+/// The output stores `batch` m x n matrices.
+/// # Example
+/// ```
+/// use custos::{OpenCL, Buffer, Read};
+/// use custos_math::cl_batched_gemm;
+///
+/// fn main() -> Result<(), custos::Error> {
+///     let device = OpenCL::new(0)?;
+///     // two 1 x 2 matrices
+///     let lhs = Buffer::from((&device, [1f32, 2., 3., 4.]));
+///     // one 2 x 2 matrix
+///     let rhs = Buffer::from((&device, [1f32, 0., 0., 2.]));
+///     
+///     let out = cl_batched_gemm(&device, 2, 1, 2, 2, &lhs, &rhs, true)?;
+///     assert_eq!(device.read(&out), vec![1., 4., 3., 8.]);
+///     Ok(())
+/// }
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn cl_batched_gemm<'a, T: CDatatype>(
+    device: &'a OpenCL,
+    batch: usize,
+    m: usize,
+    k: usize,
+    n: usize,
+    lhs: &CLBuffer<T>,
+    rhs: &CLBuffer<T>,
+    broadcast_rhs: bool,
+) -> Result<CLBuffer<'a, T>, Error> {
+    let dt = T::as_c_type_str();
+    let rhs_stride = if broadcast_rhs { 0 } else { k * n };
+
+    // the kernel computes the transposed product in column major order, therefore A is rhs and B is lhs
+    let batch_offsets = format!(
+        "size_t batch = get_global_id(2);
+                A = (const __global floatMW*) ((const __global {dt}*) A + batch * {rhs_stride});
+                B = (const __global floatKW*) ((const __global {dt}*) B + batch * {lhs_stride});
+                C = (__global floatMW*) ((__global {dt}*) C + batch * {out_stride});",
+        lhs_stride = m * k,
+        out_stride = m * n,
+    );
+    let (src, f, s) = gemm_src::<T>(n, k, m, &batch_offsets, false);
+    let gws = [f, s, batch];
+
+    let out: CLBuffer<T> = device.retrieve(batch * m * n, (lhs.node.idx, rhs.node.idx));
+    enqueue_kernel(device, &src, gws, None, &[rhs, lhs, &out])?;
     Ok(out)
 }
@@ -0,0 +1,57 @@
+use custos::{opencl::enqueue_kernel, prelude::CLBuffer, CDatatype, OpenCL};
+
+/// OpenCL matrix multiplication `op(lhs) * op(rhs)`, where `op` transposes the operand if `trans_lhs` / `trans_rhs` is set.
+/// `op(lhs)` is a m x k matrix and `op(rhs)` is a k x n matrix.
+/// The operands are read in place, hence no transposed copy is created.
+/// # Example
+/// ```
+/// use custos::{OpenCL, Buffer, Read};
+/// use custos_math::cl_gemm_trans;
+///
+/// fn main() -> Result<(), custos::Error> {
+///     let device = OpenCL::new(0)?;
+///     // 3 x 2, used as 2 x 3
+///     let lhs = Buffer::from((&device, [1f32, 4., 2., 5., 3., 6.]));
+///     let rhs = Buffer::from((&device, [6f32, 5., 4., 3., 2., 1.]));
+///
+///     let out = cl_gemm_trans(&device, true, false, 2, 3, 2, &lhs, &rhs)?;
+///     assert_eq!(device.read(&out), vec![20., 14., 56., 41.]);
+///     Ok(())
+/// }
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn cl_gemm_trans<'a, T: CDatatype>(
+    device: &'a OpenCL,
+    trans_lhs: bool,
+    trans_rhs: bool,
+    m: usize,
+    k: usize,
+    n: usize,
+    lhs: &CLBuffer<T>,
+    rhs: &CLBuffer<T>,
+) -> custos::Result<CLBuffer<'a, T>> {
+    let (rs_lhs, cs_lhs) = if trans_lhs { (1, m) } else { (k, 1) };
+    let (rs_rhs, cs_rhs) = if trans_rhs { (1, k) } else { (n, 1) };
+
+    let src = format!(
+        "
+        #define K {k}
+        #define N {n}
+        __kernel void gemm_trans(__global const {datatype}* lhs, __global const {datatype}* rhs, __global {datatype}* out) {{
+            size_t row = get_global_id(0);
+            size_t col = get_global_id(1);
+
+            {datatype} acc = 0;
+            for (size_t elem = 0; elem < K; elem++) {{
+                acc += lhs[row * {rs_lhs} + elem * {cs_lhs}] * rhs[elem * {rs_rhs} + col * {cs_rhs}];
+            }}
+            out[row * N + col] = acc;
+        }}
+    ",
+        datatype = T::as_c_type_str()
+    );
+
+    let out: CLBuffer<T> = device.retrieve(m * n, (lhs.node.idx, rhs.node.idx));
+    enqueue_kernel(device, &src, [m, n, 0], None, &[lhs, rhs, &out])?;
+    Ok(out)
+}
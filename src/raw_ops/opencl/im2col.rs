@@ -0,0 +1,130 @@
+use custos::{opencl::enqueue_kernel, prelude::CLBuffer, CDatatype, OpenCL};
+
+use crate::ConvConfig;
+
+pub(crate) fn conv_defines(
+    img_dims: (usize, usize),
+    kernel_dims: (usize, usize),
+    config: &ConvConfig,
+) -> String {
+    let (out_rows, out_cols) = config.out_dims(img_dims, kernel_dims);
+    format!(
+        "
+        #define IMG_ROWS {}
+        #define IMG_COLS {}
+        #define KERNEL_ROWS {}
+        #define KERNEL_COLS {}
+        #define OUT_ROWS {out_rows}
+        #define OUT_COLS {out_cols}
+        #define STRIDE_ROWS {}
+        #define STRIDE_COLS {}
+        #define PAD_ROWS {}
+        #define PAD_COLS {}
+        #define DILATION_ROWS {}
+        #define DILATION_COLS {}
+        ",
+        img_dims.0,
+        img_dims.1,
+        kernel_dims.0,
+        kernel_dims.1,
+        config.stride.0,
+        config.stride.1,
+        config.padding.0,
+        config.padding.1,
+        config.dilation.0,
+        config.dilation.1,
+    )
+}
+
+/// OpenCL version of [`im2col_mut`](crate::im2col_mut).
+/// Every work item writes one entry of the (`channels` * kernel size) x patches output.
+pub fn cl_im2col<'a, T: CDatatype>(
+    device: &'a OpenCL,
+    x: &CLBuffer<T>,
+    channels: usize,
+    img_dims: (usize, usize),
+    kernel_dims: (usize, usize),
+    config: &ConvConfig,
+) -> custos::Result<CLBuffer<'a, T>> {
+    let (out_rows, out_cols) = config.out_dims(img_dims, kernel_dims);
+    let patches = out_rows * out_cols;
+    let col_rows = channels * kernel_dims.0 * kernel_dims.1;
+
+    let src = format!(
+        "{defines}
+        __kernel void im2col(__global const {datatype}* x, __global {datatype}* out) {{
+            size_t patch = get_global_id(0);
+            size_t row = get_global_id(1);
+
+            size_t kx = row % KERNEL_COLS;
+            size_t ky = (row / KERNEL_COLS) % KERNEL_ROWS;
+            size_t channel = row / (KERNEL_COLS * KERNEL_ROWS);
+
+            long img_row = (long) ((patch / OUT_COLS) * STRIDE_ROWS + ky * DILATION_ROWS) - PAD_ROWS;
+            long img_col = (long) ((patch % OUT_COLS) * STRIDE_COLS + kx * DILATION_COLS) - PAD_COLS;
+
+            {datatype} value = 0;
+            if (img_row >= 0 && img_row < IMG_ROWS && img_col >= 0 && img_col < IMG_COLS) {{
+                value = x[channel * IMG_ROWS * IMG_COLS + img_row * IMG_COLS + img_col];
+            }}
+            out[row * {patches} + patch] = value;
+        }}
+    ",
+        defines = conv_defines(img_dims, kernel_dims, config),
+        datatype = T::as_c_type_str()
+    );
+
+    let out: CLBuffer<T> = device.retrieve(col_rows * patches, x.node.idx);
+    enqueue_kernel(device, &src, [patches, col_rows, 0], None, &[x, &out])?;
+    Ok(out)
+}
+
+/// OpenCL version of [`col2im_mut`](crate::col2im_mut).
+/// Every work item gathers all column entries that belong to one input position.
+pub fn cl_col2im<'a, T: CDatatype>(
+    device: &'a OpenCL,
+    cols: &CLBuffer<T>,
+    channels: usize,
+    img_dims: (usize, usize),
+    kernel_dims: (usize, usize),
+    config: &ConvConfig,
+) -> custos::Result<CLBuffer<'a, T>> {
+    let (out_rows, out_cols) = config.out_dims(img_dims, kernel_dims);
+    let patches = out_rows * out_cols;
+    let img_len = img_dims.0 * img_dims.1;
+
+    let src = format!(
+        "{defines}
+        __kernel void col2im(__global const {datatype}* cols, __global {datatype}* out) {{
+            size_t pos = get_global_id(0);
+            size_t channel = get_global_id(1);
+
+            long img_row = pos / IMG_COLS + PAD_ROWS;
+            long img_col = pos % IMG_COLS + PAD_COLS;
+
+            {datatype} sum = 0;
+            for (size_t ky = 0; ky < KERNEL_ROWS; ky++) {{
+                long y = img_row - (long) (ky * DILATION_ROWS);
+                if (y < 0 || y % STRIDE_ROWS != 0 || y / STRIDE_ROWS >= OUT_ROWS) {{
+                    continue;
+                }}
+                for (size_t kx = 0; kx < KERNEL_COLS; kx++) {{
+                    long x = img_col - (long) (kx * DILATION_COLS);
+                    if (x < 0 || x % STRIDE_COLS != 0 || x / STRIDE_COLS >= OUT_COLS) {{
+                        continue;
+                    }}
+                    size_t row = (channel * KERNEL_ROWS + ky) * KERNEL_COLS + kx;
+                    sum += cols[row * {patches} + (y / STRIDE_ROWS) * OUT_COLS + x / STRIDE_COLS];
+                }}
+            }}
+            out[channel * IMG_ROWS * IMG_COLS + pos] = sum;
+        }}
+    ",
+        defines = conv_defines(img_dims, kernel_dims, config),
+        datatype = T::as_c_type_str()
+    );
+
+    let out: CLBuffer<T> = device.retrieve(channels * img_len, cols.node.idx);
+    enqueue_kernel(device, &src, [img_len, channels, 0], None, &[cols, &out])?;
+    Ok(out)
+}
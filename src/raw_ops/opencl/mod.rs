@@ -1,5 +1,10 @@
 mod diagflat;
 mod gemm;
+mod gemm_trans;
+mod im2col;
+mod norm;
+mod optim;
+mod pool;
 mod scalar_assign;
 mod scalar_op;
 mod str_op;
@@ -8,6 +13,11 @@ mod transpose;
 
 pub use diagflat::*;
 pub use gemm::*;
+pub use gemm_trans::*;
+pub use im2col::*;
+pub use norm::*;
+pub use optim::*;
+pub use pool::*;
 pub use scalar_assign::*;
 pub use scalar_op::*;
 pub use str_op::*;
@@ -0,0 +1,526 @@
+use custos::{number::Float, opencl::enqueue_kernel, prelude::CLBuffer, CDatatype, OpenCL, Read};
+
+use crate::{machine_epsilon, non_negative_pow, MAX_POWER_ITERATIONS};
+
+/// Work-group size of the reduction kernels. Must be a power of two.
+const GROUP_SIZE: usize = 256;
+
+/// Combines two values in [`cl_reduce`]. Both start at zero.
+#[derive(Clone, Copy)]
+enum Combine {
+    Sum,
+    /// Only valid for non-negative values.
+    Max,
+}
+
+/// The source of a reduction over `len` values, where `load` is the body of a function
+/// that returns the `i`-th value, given the input `x` and the parameter `p`.
+///
+/// Every work-group reduces a strided part of the values in local memory and writes one partial result.
+fn reduce_src<T: CDatatype>(len: usize, load: &str, combine: Combine) -> String {
+    let combine = match combine {
+        Combine::Sum => "a + b",
+        Combine::Max => "a > b ? a : b",
+    };
+
+    format!(
+        "
+        #define GROUP {GROUP_SIZE}
+
+        {datatype} load(__global const {datatype}* x, const {datatype} p, size_t i) {{
+            {load}
+        }}
+
+        {datatype} combine({datatype} a, {datatype} b) {{
+            return {combine};
+        }}
+
+        __kernel void norm_reduce(__global const {datatype}* x, const {datatype} p, __global {datatype}* partial) {{
+            __local {datatype} scratch[GROUP];
+            size_t lid = get_local_id(0);
+
+            {datatype} acc = 0;
+            for (size_t i = get_global_id(0); i < {len}; i += get_global_size(0)) {{
+                acc = combine(acc, load(x, p, i));
+            }}
+            scratch[lid] = acc;
+            barrier(CLK_LOCAL_MEM_FENCE);
+
+            for (size_t offset = GROUP / 2; offset > 0; offset /= 2) {{
+                if (lid < offset) {{
+                    scratch[lid] = combine(scratch[lid], scratch[lid + offset]);
+                }}
+                barrier(CLK_LOCAL_MEM_FENCE);
+            }}
+
+            if (lid == 0) {{
+                partial[get_group_id(0)] = scratch[0];
+            }}
+        }}
+    ",
+        datatype = T::as_c_type_str()
+    )
+}
+
+/// Reduces `len` values of `x` (see [`reduce_src`]) in two passes:
+/// up to [`GROUP_SIZE`] work-groups compute partial results, which are then combined by a single work-group.
+fn cl_reduce<T: CDatatype>(
+    device: &OpenCL,
+    x: &CLBuffer<T>,
+    len: usize,
+    p: T,
+    load: &str,
+    combine: Combine,
+) -> custos::Result<T> {
+    let groups = ((len + GROUP_SIZE - 1) / GROUP_SIZE).clamp(1, GROUP_SIZE);
+
+    let partial: CLBuffer<T> = device.retrieve(groups, x.node.idx);
+    enqueue_kernel(
+        device,
+        &reduce_src::<T>(len, load, combine),
+        [groups * GROUP_SIZE, 0, 0],
+        Some([GROUP_SIZE, 0, 0]),
+        &[x, &p, &partial],
+    )?;
+
+    let out: CLBuffer<T> = device.retrieve(1, partial.node.idx);
+    enqueue_kernel(
+        device,
+        &reduce_src::<T>(groups, "return x[i];", combine),
+        [GROUP_SIZE, 0, 0],
+        Some([GROUP_SIZE, 0, 0]),
+        &[&partial, &p, &out],
+    )?;
+    Ok(device.read(&out)[0])
+}
+
+/// OpenCL version of [`frobenius_norm_slice`](crate::frobenius_norm_slice).
+pub fn cl_frobenius_norm<T: CDatatype + Float>(
+    device: &OpenCL,
+    x: &CLBuffer<T>,
+) -> custos::Result<T> {
+    Ok(cl_reduce(
+        device,
+        x,
+        x.len(),
+        T::default(),
+        "return x[i] * x[i];",
+        Combine::Sum,
+    )?
+    .sqrt())
+}
+
+/// OpenCL version of [`elementwise_norm_slice`](crate::elementwise_norm_slice).
+pub fn cl_elementwise_norm<T: CDatatype + Float>(
+    device: &OpenCL,
+    x: &CLBuffer<T>,
+    p: T,
+) -> custos::Result<T> {
+    if p == T::one() {
+        return cl_reduce(device, x, x.len(), p, "return fabs(x[i]);", Combine::Sum);
+    }
+    if p == T::two() {
+        return cl_frobenius_norm(device, x);
+    }
+
+    let sum = cl_reduce(
+        device,
+        x,
+        x.len(),
+        p,
+        "return pow(fabs(x[i]), p);",
+        Combine::Sum,
+    )?;
+    Ok(non_negative_pow(sum, T::one() / p))
+}
+
+/// OpenCL version of [`l1_norm_slice`](crate::l1_norm_slice).
+/// Every work item sums up whole columns.
+pub fn cl_l1_norm<T: CDatatype + Float>(
+    device: &OpenCL,
+    x: &CLBuffer<T>,
+    rows: usize,
+    cols: usize,
+) -> custos::Result<T> {
+    let load = format!(
+        "{datatype} sum = 0;
+        for (size_t row = 0; row < {rows}; row++) {{
+            sum += fabs(x[row * {cols} + i]);
+        }}
+        return sum;",
+        datatype = T::as_c_type_str()
+    );
+    cl_reduce(device, x, cols, T::default(), &load, Combine::Max)
+}
+
+/// OpenCL version of [`inf_norm_slice`](crate::inf_norm_slice).
+/// Every work item sums up whole rows.
+pub fn cl_inf_norm<T: CDatatype + Float>(
+    device: &OpenCL,
+    x: &CLBuffer<T>,
+    rows: usize,
+    cols: usize,
+) -> custos::Result<T> {
+    let load = format!(
+        "{datatype} sum = 0;
+        for (size_t col = 0; col < {cols}; col++) {{
+            sum += fabs(x[i * {cols} + col]);
+        }}
+        return sum;",
+        datatype = T::as_c_type_str()
+    );
+    cl_reduce(device, x, rows, T::default(), &load, Combine::Max)
+}
+
+/// OpenCL version of [`trace_slice`](crate::trace_slice).
+pub fn cl_trace<T: CDatatype + Float>(
+    device: &OpenCL,
+    x: &CLBuffer<T>,
+    rows: usize,
+    cols: usize,
+) -> custos::Result<T> {
+    let load = format!("return x[i * {}];", cols + 1);
+    cl_reduce(device, x, rows.min(cols), T::default(), &load, Combine::Sum)
+}
+
+/// The source of `group_sum`, which sums up one value of every work item of a work-group of size `GROUP`
+/// in the local memory `scratch` and returns the sum to every work item.
+fn group_sum_src<T: CDatatype>() -> String {
+    format!(
+        "{datatype} group_sum(__local {datatype}* scratch, {datatype} value) {{
+            size_t lid = get_local_id(0);
+            scratch[lid] = value;
+            barrier(CLK_LOCAL_MEM_FENCE);
+
+            for (size_t offset = GROUP / 2; offset > 0; offset /= 2) {{
+                if (lid < offset) {{
+                    scratch[lid] += scratch[lid + offset];
+                }}
+                barrier(CLK_LOCAL_MEM_FENCE);
+            }}
+
+            {datatype} sum = scratch[0];
+            barrier(CLK_LOCAL_MEM_FENCE);
+            return sum;
+        }}",
+        datatype = T::as_c_type_str()
+    )
+}
+
+/// OpenCL version of [`spectral_norm_slice`](crate::spectral_norm_slice).
+///
+/// The whole power iteration runs in a single work-group, as every step depends on the complete previous vector.
+/// The matrix-vector products are split across the work items and the vector norms are reduced in local memory.
+pub fn cl_spectral_norm<T: CDatatype + Float>(
+    device: &OpenCL,
+    x: &CLBuffer<T>,
+    rows: usize,
+    cols: usize,
+) -> custos::Result<T> {
+    let src = format!(
+        "
+        #define GROUP {GROUP_SIZE}
+        #define ROWS {rows}
+        #define COLS {cols}
+
+        {group_sum}
+
+        __kernel void spectral_norm(__global const {datatype}* x, __global {datatype}* v, __global {datatype}* w,
+            const {datatype} tol, __global {datatype}* out) {{
+            __local {datatype} scratch[GROUP];
+            size_t lid = get_local_id(0);
+
+            // start vector: x^T * s with growing signs s
+            for (size_t col = lid; col < COLS; col += GROUP) {{
+                v[col] = 0;
+            }}
+            for (size_t row = 0; row < ROWS; row++) {{
+                {datatype} dot = 0;
+                for (size_t col = lid; col < COLS; col += GROUP) {{
+                    dot += v[col] * x[row * COLS + col];
+                }}
+                {datatype} sign = group_sum(scratch, dot) >= 0 ? 1 : -1;
+                for (size_t col = lid; col < COLS; col += GROUP) {{
+                    v[col] += sign * x[row * COLS + col];
+                }}
+            }}
+
+            {datatype} partial = 0;
+            for (size_t col = lid; col < COLS; col += GROUP) {{
+                partial += v[col] * v[col];
+            }}
+            {datatype} norm = sqrt(group_sum(scratch, partial));
+            if (norm == 0) {{
+                if (lid == 0) {{
+                    out[0] = 0;
+                }}
+                return;
+            }}
+            for (size_t col = lid; col < COLS; col += GROUP) {{
+                v[col] /= norm;
+            }}
+            barrier(CLK_GLOBAL_MEM_FENCE);
+
+            {datatype} sigma = 0;
+            for (size_t iter = 0; iter < {MAX_POWER_ITERATIONS}; iter++) {{
+                // w = x * v
+                partial = 0;
+                for (size_t row = lid; row < ROWS; row += GROUP) {{
+                    {datatype} sum = 0;
+                    for (size_t col = 0; col < COLS; col++) {{
+                        sum += x[row * COLS + col] * v[col];
+                    }}
+                    w[row] = sum;
+                    partial += sum * sum;
+                }}
+                {datatype} next = sqrt(group_sum(scratch, partial));
+                barrier(CLK_GLOBAL_MEM_FENCE);
+
+                // v = x^T * w
+                partial = 0;
+                for (size_t col = lid; col < COLS; col += GROUP) {{
+                    {datatype} sum = 0;
+                    for (size_t row = 0; row < ROWS; row++) {{
+                        sum += x[row * COLS + col] * w[row];
+                    }}
+                    v[col] = sum;
+                    partial += sum * sum;
+                }}
+                norm = sqrt(group_sum(scratch, partial));
+                if (norm == 0) {{
+                    sigma = next;
+                    break;
+                }}
+                for (size_t col = lid; col < COLS; col += GROUP) {{
+                    v[col] /= norm;
+                }}
+                barrier(CLK_GLOBAL_MEM_FENCE);
+
+                int converged = next - sigma <= tol * next;
+                sigma = next;
+                if (converged) {{
+                    break;
+                }}
+            }}
+
+            if (lid == 0) {{
+                out[0] = sigma;
+            }}
+        }}
+    ",
+        group_sum = group_sum_src::<T>(),
+        datatype = T::as_c_type_str()
+    );
+
+    let v: CLBuffer<T> = device.retrieve(cols, x.node.idx);
+    let w: CLBuffer<T> = device.retrieve(rows, x.node.idx);
+    let out: CLBuffer<T> = device.retrieve(1, x.node.idx);
+    let tol = T::from_usize(4) * machine_epsilon::<T>();
+
+    enqueue_kernel(
+        device,
+        &src,
+        [GROUP_SIZE, 0, 0],
+        Some([GROUP_SIZE, 0, 0]),
+        &[x, &v, &w, &tol, &out],
+    )?;
+    Ok(device.read(&out)[0])
+}
+
+/// Estimates the smallest singular value of the `rows` x `cols` matrix `x` in a single work-group.
+///
+/// A Householder QR decomposition reduces `x` (or its transpose, if `x` is wide) to the triangular `R`,
+/// which has the same singular values. Inverse iteration with `R^T * R` then converges to the right singular vector `v`
+/// of the smallest singular value, which is returned as `|R * v|`. Returns zero if a diagonal entry of `R` is zero.
+fn cl_min_singular_value<T: CDatatype + Float>(
+    device: &OpenCL,
+    x: &CLBuffer<T>,
+    rows: usize,
+    cols: usize,
+) -> custos::Result<T> {
+    // a = x or x^T, so that a has p >= k rows
+    let (p, k, load) = if rows >= cols {
+        (rows, cols, "x[row * K + col]")
+    } else {
+        (cols, rows, "x[col * P + row]")
+    };
+
+    let src = format!(
+        "
+        #define GROUP {GROUP_SIZE}
+        #define P {p}
+        #define K {k}
+
+        {group_sum}
+
+        __kernel void min_singular_value(__global const {datatype}* x, __global {datatype}* r, __global {datatype}* v,
+            __global {datatype}* b, const {datatype} tol, __global {datatype}* out) {{
+            __local {datatype} scratch[GROUP];
+            size_t lid = get_local_id(0);
+
+            for (size_t idx = lid; idx < P * K; idx += GROUP) {{
+                size_t row = idx / K;
+                size_t col = idx % K;
+                r[idx] = {load};
+            }}
+            barrier(CLK_GLOBAL_MEM_FENCE);
+
+            // Householder QR, afterwards the upper K x K part of r is R
+            for (size_t j = 0; j < K; j++) {{
+                {datatype} partial = 0;
+                for (size_t i = j + lid; i < P; i += GROUP) {{
+                    partial += r[i * K + j] * r[i * K + j];
+                }}
+                {datatype} norm = sqrt(group_sum(scratch, partial));
+                if (norm == 0) {{
+                    continue;
+                }}
+
+                // reflector u = (diag - alpha, r[j + 1.., j]) with u^T u = 2 * norm * (norm + |diag|)
+                {datatype} diag = r[j * K + j];
+                {datatype} alpha = diag >= 0 ? -norm : norm;
+                {datatype} u0 = diag - alpha;
+                {datatype} utu = 2 * norm * (norm + fabs(diag));
+
+                for (size_t col = j + 1 + lid; col < K; col += GROUP) {{
+                    {datatype} dot = u0 * r[j * K + col];
+                    for (size_t i = j + 1; i < P; i++) {{
+                        dot += r[i * K + j] * r[i * K + col];
+                    }}
+                    {datatype} f = 2 * dot / utu;
+                    r[j * K + col] -= f * u0;
+                    for (size_t i = j + 1; i < P; i++) {{
+                        r[i * K + col] -= f * r[i * K + j];
+                    }}
+                }}
+                barrier(CLK_GLOBAL_MEM_FENCE);
+                if (lid == 0) {{
+                    r[j * K + j] = alpha;
+                }}
+                barrier(CLK_GLOBAL_MEM_FENCE);
+            }}
+
+            for (size_t j = 0; j < K; j++) {{
+                if (r[j * K + j] == 0) {{
+                    if (lid == 0) {{
+                        out[0] = 0;
+                    }}
+                    return;
+                }}
+            }}
+
+            for (size_t i = lid; i < K; i += GROUP) {{
+                v[i] = 1 / sqrt(({datatype}) K);
+            }}
+            barrier(CLK_GLOBAL_MEM_FENCE);
+
+            {datatype} mu = 0;
+            for (size_t iter = 0; iter < {MAX_POWER_ITERATIONS}; iter++) {{
+                for (size_t i = lid; i < K; i += GROUP) {{
+                    b[i] = v[i];
+                }}
+                barrier(CLK_GLOBAL_MEM_FENCE);
+
+                // R^T * y = b, y is stored in v
+                for (size_t i = 0; i < K; i++) {{
+                    {datatype} y = b[i] / r[i * K + i];
+                    for (size_t row = lid; row < K; row += GROUP) {{
+                        if (row == i) {{
+                            v[row] = y;
+                        }} else if (row > i) {{
+                            b[row] -= r[i * K + row] * y;
+                        }}
+                    }}
+                    barrier(CLK_GLOBAL_MEM_FENCE);
+                }}
+
+                // R * z = y, z is stored in b
+                for (size_t i = K; i-- > 0;) {{
+                    {datatype} z = v[i] / r[i * K + i];
+                    for (size_t row = lid; row < K; row += GROUP) {{
+                        if (row == i) {{
+                            b[row] = z;
+                        }} else if (row < i) {{
+                            v[row] -= r[row * K + i] * z;
+                        }}
+                    }}
+                    barrier(CLK_GLOBAL_MEM_FENCE);
+                }}
+
+                // |z| converges to 1 / sigma_min^2
+                {datatype} partial = 0;
+                for (size_t i = lid; i < K; i += GROUP) {{
+                    partial += b[i] * b[i];
+                }}
+                {datatype} next = sqrt(group_sum(scratch, partial));
+                for (size_t i = lid; i < K; i += GROUP) {{
+                    v[i] = b[i] / next;
+                }}
+                barrier(CLK_GLOBAL_MEM_FENCE);
+
+                int converged = next - mu <= tol * next;
+                mu = next;
+                if (converged) {{
+                    break;
+                }}
+            }}
+
+            // sigma_min = |R * v|
+            {datatype} partial = 0;
+            for (size_t row = lid; row < K; row += GROUP) {{
+                {datatype} sum = 0;
+                for (size_t col = row; col < K; col++) {{
+                    sum += r[row * K + col] * v[col];
+                }}
+                partial += sum * sum;
+            }}
+            {datatype} sigma = sqrt(group_sum(scratch, partial));
+
+            if (lid == 0) {{
+                out[0] = sigma;
+            }}
+        }}
+    ",
+        group_sum = group_sum_src::<T>(),
+        datatype = T::as_c_type_str()
+    );
+
+    let r: CLBuffer<T> = device.retrieve(p * k, x.node.idx);
+    let v: CLBuffer<T> = device.retrieve(k, x.node.idx);
+    let b: CLBuffer<T> = device.retrieve(k, x.node.idx);
+    let out: CLBuffer<T> = device.retrieve(1, x.node.idx);
+    let tol = T::from_usize(4) * machine_epsilon::<T>();
+
+    enqueue_kernel(
+        device,
+        &src,
+        [GROUP_SIZE, 0, 0],
+        Some([GROUP_SIZE, 0, 0]),
+        &[x, &r, &v, &b, &tol, &out],
+    )?;
+    Ok(device.read(&out)[0])
+}
+
+/// The condition number of the `rows` x `cols` matrix `x` in the spectral norm.
+/// The largest singular value is estimated with [`cl_spectral_norm`], the smallest with inverse iteration on the `R` factor of `x`.
+/// As on the CPU, singular values below `max(rows, cols) * eps * sigma_max` count as zero, which results in infinity.
+pub fn cl_cond<T: CDatatype + Float>(
+    device: &OpenCL,
+    x: &CLBuffer<T>,
+    rows: usize,
+    cols: usize,
+) -> custos::Result<T> {
+    if rows == 0 || cols == 0 {
+        return Ok(T::default());
+    }
+
+    let sigma_max = cl_spectral_norm(device, x, rows, cols)?;
+    let sigma_min = cl_min_singular_value(device, x, rows, cols)?;
+
+    if sigma_min < T::from_usize(rows.max(cols)) * machine_epsilon::<T>() * sigma_max
+        || sigma_max == T::default()
+    {
+        return Ok(T::one() / T::default());
+    }
+    Ok(sigma_max / sigma_min)
+}
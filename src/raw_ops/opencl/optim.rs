@@ -0,0 +1,165 @@
+use custos::{number::Float, opencl::enqueue_kernel, prelude::CLBuffer, CDatatype, OpenCL};
+
+use crate::adam_bias_corrections;
+
+/// OpenCL version of [`sgd_momentum_step_slice`](crate::sgd_momentum_step_slice).
+pub fn cl_sgd_momentum_step<T: CDatatype>(
+    device: &OpenCL,
+    param: &mut CLBuffer<T>,
+    grad: &CLBuffer<T>,
+    velocity: &mut CLBuffer<T>,
+    lr: T,
+    mu: T,
+    nesterov: bool,
+) -> custos::Result<()> {
+    let step = if nesterov {
+        "grad[id] + mu * velocity[id]"
+    } else {
+        "velocity[id]"
+    };
+
+    let src = format!(
+        "
+        __kernel void sgd_momentum_step(__global {datatype}* param, __global const {datatype}* grad,
+            __global {datatype}* velocity, const {datatype} lr, const {datatype} mu) {{
+            size_t id = get_global_id(0);
+
+            velocity[id] = mu * velocity[id] + grad[id];
+            param[id] -= lr * ({step});
+        }}
+    ",
+        datatype = T::as_c_type_str()
+    );
+
+    enqueue_kernel(
+        device,
+        &src,
+        [param.len(), 0, 0],
+        None,
+        &[&*param, grad, &*velocity, &lr, &mu],
+    )
+}
+
+/// OpenCL version of [`adam_step_slice`](crate::adam_step_slice).
+/// The bias corrections are computed on the host.
+#[allow(clippy::too_many_arguments)]
+pub fn cl_adam_step<T: CDatatype + Float>(
+    device: &OpenCL,
+    param: &mut CLBuffer<T>,
+    grad: &CLBuffer<T>,
+    m: &mut CLBuffer<T>,
+    v: &mut CLBuffer<T>,
+    lr: T,
+    betas: (T, T),
+    eps: T,
+    t: usize,
+    weight_decay: T,
+) -> custos::Result<()> {
+    let (correction1, correction2) = adam_bias_corrections(betas, t);
+
+    let src = format!(
+        "
+        __kernel void adam_step(__global {datatype}* param, __global const {datatype}* grad,
+            __global {datatype}* m, __global {datatype}* v,
+            const {datatype} lr, const {datatype} beta1, const {datatype} beta2, const {datatype} eps,
+            const {datatype} correction1, const {datatype} correction2, const {datatype} weight_decay) {{
+            size_t id = get_global_id(0);
+
+            {datatype} g = grad[id];
+            m[id] = beta1 * m[id] + (1 - beta1) * g;
+            v[id] = beta2 * v[id] + (1 - beta2) * g * g;
+
+            {datatype} m_hat = m[id] / correction1;
+            {datatype} v_hat = v[id] / correction2;
+
+            param[id] -= lr * (weight_decay * param[id] + m_hat / (sqrt(v_hat) + eps));
+        }}
+    ",
+        datatype = T::as_c_type_str()
+    );
+
+    enqueue_kernel(
+        device,
+        &src,
+        [param.len(), 0, 0],
+        None,
+        &[
+            &*param,
+            grad,
+            &*m,
+            &*v,
+            &lr,
+            &betas.0,
+            &betas.1,
+            &eps,
+            &correction1,
+            &correction2,
+            &weight_decay,
+        ],
+    )
+}
+
+/// OpenCL version of [`rmsprop_step_slice`](crate::rmsprop_step_slice).
+pub fn cl_rmsprop_step<T: CDatatype>(
+    device: &OpenCL,
+    param: &mut CLBuffer<T>,
+    grad: &CLBuffer<T>,
+    sq_avg: &mut CLBuffer<T>,
+    lr: T,
+    alpha: T,
+    eps: T,
+) -> custos::Result<()> {
+    let src = format!(
+        "
+        __kernel void rmsprop_step(__global {datatype}* param, __global const {datatype}* grad,
+            __global {datatype}* sq_avg, const {datatype} lr, const {datatype} alpha, const {datatype} eps) {{
+            size_t id = get_global_id(0);
+
+            {datatype} g = grad[id];
+            sq_avg[id] = alpha * sq_avg[id] + (1 - alpha) * g * g;
+            param[id] -= lr * g / (sqrt(sq_avg[id]) + eps);
+        }}
+    ",
+        datatype = T::as_c_type_str()
+    );
+
+    enqueue_kernel(
+        device,
+        &src,
+        [param.len(), 0, 0],
+        None,
+        &[&*param, grad, &*sq_avg, &lr, &alpha, &eps],
+    )
+}
+
+/// OpenCL version of [`adagrad_step_slice`](crate::adagrad_step_slice).
+pub fn cl_adagrad_step<T: CDatatype>(
+    device: &OpenCL,
+    param: &mut CLBuffer<T>,
+    grad: &CLBuffer<T>,
+    sq_sum: &mut CLBuffer<T>,
+    lr: T,
+    eps: T,
+) -> custos::Result<()> {
+    let src = format!(
+        "
+        __kernel void adagrad_step(__global {datatype}* param, __global const {datatype}* grad,
+            __global {datatype}* sq_sum, const {datatype} lr, const {datatype} eps) {{
+            size_t id = get_global_id(0);
+
+            {datatype} g = grad[id];
+            sq_sum[id] += g * g;
+            param[id] -= lr * g / (sqrt(sq_sum[id]) + eps);
+        }}
+    ",
+        datatype = T::as_c_type_str()
+    );
+
+    enqueue_kernel(
+        device,
+        &src,
+        [param.len(), 0, 0],
+        None,
+        &[&*param, grad, &*sq_sum, &lr, &eps],
+    )
+}
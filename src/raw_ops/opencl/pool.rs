@@ -0,0 +1,209 @@
+use custos::{opencl::enqueue_kernel, prelude::CLBuffer, CDatatype, OpenCL};
+
+use super::im2col::conv_defines;
+use crate::ConvConfig;
+
+/// OpenCL version of [`max_pool2d_mut`](crate::max_pool2d_mut).
+/// Every work item computes one output entry and its index.
+pub fn cl_max_pool2d<'a, T: CDatatype>(
+    device: &'a OpenCL,
+    x: &CLBuffer<T>,
+    channels: usize,
+    img_dims: (usize, usize),
+    kernel_dims: (usize, usize),
+    config: &ConvConfig,
+) -> custos::Result<(CLBuffer<'a, T>, CLBuffer<'a, u32>)> {
+    let (out_rows, out_cols) = config.out_dims(img_dims, kernel_dims);
+    let patches = out_rows * out_cols;
+
+    let src = format!(
+        "{defines}
+        __kernel void max_pool2d(__global const {datatype}* x, __global {datatype}* out, __global uint* indices) {{
+            size_t patch = get_global_id(0);
+            size_t channel = get_global_id(1);
+
+            __global const {datatype}* img = x + channel * IMG_ROWS * IMG_COLS;
+
+            int found = 0;
+            {datatype} max = 0;
+            uint idx = UINT_MAX;
+
+            for (size_t ky = 0; ky < KERNEL_ROWS; ky++) {{
+                long img_row = (long) ((patch / OUT_COLS) * STRIDE_ROWS + ky * DILATION_ROWS) - PAD_ROWS;
+                if (img_row < 0 || img_row >= IMG_ROWS) {{
+                    continue;
+                }}
+                for (size_t kx = 0; kx < KERNEL_COLS; kx++) {{
+                    long img_col = (long) ((patch % OUT_COLS) * STRIDE_COLS + kx * DILATION_COLS) - PAD_COLS;
+                    if (img_col < 0 || img_col >= IMG_COLS) {{
+                        continue;
+                    }}
+                    uint pos = img_row * IMG_COLS + img_col;
+                    if (!found || img[pos] > max) {{
+                        max = img[pos];
+                        idx = pos;
+                        found = 1;
+                    }}
+                }}
+            }}
+            out[channel * {patches} + patch] = max;
+            indices[channel * {patches} + patch] = idx;
+        }}
+    ",
+        defines = conv_defines(img_dims, kernel_dims, config),
+        datatype = T::as_c_type_str()
+    );
+
+    let out: CLBuffer<T> = device.retrieve(channels * patches, x.node.idx);
+    let indices: CLBuffer<u32> = device.retrieve(channels * patches, x.node.idx);
+    enqueue_kernel(
+        device,
+        &src,
+        [patches, channels, 0],
+        None,
+        &[x, &out, &indices],
+    )?;
+    Ok((out, indices))
+}
+
+/// OpenCL version of [`max_pool2d_grad_mut`](crate::max_pool2d_grad_mut).
+/// Windows may overlap, therefore every work item scatters the gradients of a whole channel.
+pub fn cl_max_pool2d_grad<'a, T: CDatatype>(
+    device: &'a OpenCL,
+    grads: &CLBuffer<T>,
+    indices: &CLBuffer<u32>,
+    channels: usize,
+    img_len: usize,
+) -> custos::Result<CLBuffer<'a, T>> {
+    assert!(
+        channels > 0,
+        "The gradients must have at least one channel."
+    );
+    let patches = grads.len() / channels;
+
+    let src = format!(
+        "
+        __kernel void max_pool2d_grad(__global const {datatype}* grads, __global const uint* indices, __global {datatype}* out) {{
+            size_t channel = get_global_id(0);
+
+            __global {datatype}* img = out + channel * {img_len};
+            for (size_t pos = 0; pos < {img_len}; pos++) {{
+                img[pos] = 0;
+            }}
+
+            for (size_t patch = channel * {patches}; patch < (channel + 1) * {patches}; patch++) {{
+                if (indices[patch] != UINT_MAX) {{
+                    img[indices[patch]] += grads[patch];
+                }}
+            }}
+        }}
+    ",
+        datatype = T::as_c_type_str()
+    );
+
+    let out: CLBuffer<T> = device.retrieve(channels * img_len, (grads.node.idx, indices.node.idx));
+    enqueue_kernel(
+        device,
+        &src,
+        [channels, 0, 0],
+        None,
+        &[grads, indices, &out],
+    )?;
+    Ok(out)
+}
+
+/// OpenCL version of [`avg_pool2d_mut`](crate::avg_pool2d_mut).
+/// Every work item computes one output entry.
+pub fn cl_avg_pool2d<'a, T: CDatatype>(
+    device: &'a OpenCL,
+    x: &CLBuffer<T>,
+    channels: usize,
+    img_dims: (usize, usize),
+    kernel_dims: (usize, usize),
+    config: &ConvConfig,
+) -> custos::Result<CLBuffer<'a, T>> {
+    let (out_rows, out_cols) = config.out_dims(img_dims, kernel_dims);
+    let patches = out_rows * out_cols;
+
+    let src = format!(
+        "{defines}
+        __kernel void avg_pool2d(__global const {datatype}* x, __global {datatype}* out) {{
+            size_t patch = get_global_id(0);
+            size_t channel = get_global_id(1);
+
+            __global const {datatype}* img = x + channel * IMG_ROWS * IMG_COLS;
+
+            {datatype} sum = 0;
+            for (size_t ky = 0; ky < KERNEL_ROWS; ky++) {{
+                long img_row = (long) ((patch / OUT_COLS) * STRIDE_ROWS + ky * DILATION_ROWS) - PAD_ROWS;
+                if (img_row < 0 || img_row >= IMG_ROWS) {{
+                    continue;
+                }}
+                for (size_t kx = 0; kx < KERNEL_COLS; kx++) {{
+                    long img_col = (long) ((patch % OUT_COLS) * STRIDE_COLS + kx * DILATION_COLS) - PAD_COLS;
+                    if (img_col < 0 || img_col >= IMG_COLS) {{
+                        continue;
+                    }}
+                    sum += img[img_row * IMG_COLS + img_col];
+                }}
+            }}
+            out[channel * {patches} + patch] = sum / (KERNEL_ROWS * KERNEL_COLS);
+        }}
+    ",
+        defines = conv_defines(img_dims, kernel_dims, config),
+        datatype = T::as_c_type_str()
+    );
+
+    let out: CLBuffer<T> = device.retrieve(channels * patches, x.node.idx);
+    enqueue_kernel(device, &src, [patches, channels, 0], None, &[x, &out])?;
+    Ok(out)
+}
+
+/// OpenCL version of [`avg_pool2d_grad_mut`](crate::avg_pool2d_grad_mut).
+/// Every work item gathers the gradients of all windows that contain one input position.
+pub fn cl_avg_pool2d_grad<'a, T: CDatatype>(
+    device: &'a OpenCL,
+    grads: &CLBuffer<T>,
+    channels: usize,
+    img_dims: (usize, usize),
+    kernel_dims: (usize, usize),
+    config: &ConvConfig,
+) -> custos::Result<CLBuffer<'a, T>> {
+    let (out_rows, out_cols) = config.out_dims(img_dims, kernel_dims);
+    let patches = out_rows * out_cols;
+    let img_len = img_dims.0 * img_dims.1;
+
+    let src = format!(
+        "{defines}
+        __kernel void avg_pool2d_grad(__global const {datatype}* grads, __global {datatype}* out) {{
+            size_t pos = get_global_id(0);
+            size_t channel = get_global_id(1);
+
+            long img_row = pos / IMG_COLS + PAD_ROWS;
+            long img_col = pos % IMG_COLS + PAD_COLS;
+
+            {datatype} sum = 0;
+            for (size_t ky = 0; ky < KERNEL_ROWS; ky++) {{
+                long y = img_row - (long) (ky * DILATION_ROWS);
+                if (y < 0 || y % STRIDE_ROWS != 0 || y / STRIDE_ROWS >= OUT_ROWS) {{
+                    continue;
+                }}
+                for (size_t kx = 0; kx < KERNEL_COLS; kx++) {{
+                    long x = img_col - (long) (kx * DILATION_COLS);
+                    if (x < 0 || x % STRIDE_COLS != 0 || x / STRIDE_COLS >= OUT_COLS) {{
+                        continue;
+                    }}
+                    sum += grads[channel * {patches} + (y / STRIDE_ROWS) * OUT_COLS + x / STRIDE_COLS];
+                }}
+            }}
+            out[channel * IMG_ROWS * IMG_COLS + pos] = sum / (KERNEL_ROWS * KERNEL_COLS);
+        }}
+    ",
+        defines = conv_defines(img_dims, kernel_dims, config),
+        datatype = T::as_c_type_str()
+    );
+
+    let out: CLBuffer<T> = device.retrieve(channels * img_len, grads.node.idx);
+    enqueue_kernel(device, &src, [img_len, channels, 0], None, &[grads, &out])?;
+    Ok(out)
+}
@@ -34,3 +34,36 @@ pub fn cl_str_op_mut<'a, T: CDatatype>(
     cl_str_op(device, x, x, op)?;
     Ok(())
 }
+
+/// Like [`cl_str_op`], but `op` may additionally use the scalar `p`, which is passed as a kernel argument.
+pub fn cl_str_param_op<'a, T: CDatatype>(
+    device: &'a OpenCL,
+    x: &CLBuffer<T>,
+    out: &CLBuffer<T>,
+    p: T,
+    op: &str,
+) -> custos::Result<()> {
+    let src = format!(
+        "
+        __kernel void str_param_op(__global const {datatype}* lhs, const {datatype} p, __global {datatype}* out) {{
+            size_t id = get_global_id(0);
+            {datatype} x = lhs[id];
+            out[id] = {op};
+        }}
+    ",
+        datatype = T::as_c_type_str()
+    );
+
+    enqueue_kernel(device, &src, [x.len(), 0, 0], None, &[x, &p, out])?;
+    Ok(())
+}
+
+#[inline]
+pub fn cl_str_param_op_mut<'a, T: CDatatype>(
+    device: &'a OpenCL,
+    x: &mut CLBuffer<T>,
+    p: T,
+    op: &str,
+) -> custos::Result<()> {
+    cl_str_param_op(device, x, x, p, op)
+}
@@ -1,7 +1,7 @@
 pub use crate::{assign_to_lhs, assign_to_lhs_scalar, scalar_apply, slice_transpose};
 
 #[cfg(feature = "fastrand")]
-pub use crate::rand_slice;
+pub use crate::{rand_slice, rand_slice_with, randn_slice, truncated_normal_slice};
 
 #[cfg(feature = "opencl")]
 pub use crate::{
@@ -1,24 +1,46 @@
-use custos::Matrix;
+use crate::Matrix;
+use custos::Device;
 
-#[derive(Debug, Clone, Copy)]
+/// The axis a reduction runs along.
+///
+/// `Rows` collapses the rows of a matrix (rows x cols -> 1 x cols), like `sum_rows`.
+/// `Cols` collapses the columns (rows x cols -> rows x 1), like `sum_cols`.
+/// `All` reduces every element into a 1 x 1 matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Axis {
     Rows,
     Cols,
-    All
+    All,
 }
 
-pub trait GetAxis<T> {
-    fn get_axis(&self) -> (Matrix<T>, Axis);
+impl Axis {
+    /// Returns the dimensions of a matrix with dimensions `dims` after it was reduced along this axis.
+    #[inline]
+    pub fn reduced_dims(self, dims: (usize, usize)) -> (usize, usize) {
+        match self {
+            Axis::Rows => (1, dims.1),
+            Axis::Cols => (dims.0, 1),
+            Axis::All => (1, 1),
+        }
+    }
+}
+
+/// Pairs a matrix with the axis an operation should run along.
+/// A plain matrix reference uses [`Axis::All`].
+pub trait GetAxis<'a, T, D: Device> {
+    fn get_axis(&self) -> (&Matrix<'a, T, D>, Axis);
 }
 
-impl <T: Copy>GetAxis<T> for (Matrix<T>, Axis) {
-    fn get_axis(&self) -> (Matrix<T>, Axis) {
-        *self
+impl<'a, T, D: Device> GetAxis<'a, T, D> for (&Matrix<'a, T, D>, Axis) {
+    #[inline]
+    fn get_axis(&self) -> (&Matrix<'a, T, D>, Axis) {
+        (self.0, self.1)
     }
 }
 
-impl <T: Copy>GetAxis<T> for Matrix<T> {
-    fn get_axis(&self) -> (Matrix<T>, Axis) {
+impl<'a, T, D: Device> GetAxis<'a, T, D> for &Matrix<'a, T, D> {
+    #[inline]
+    fn get_axis(&self) -> (&Matrix<'a, T, D>, Axis) {
         (*self, Axis::All)
     }
-}
\ No newline at end of file
+}
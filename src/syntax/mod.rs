@@ -1 +1,3 @@
-//mod axis;
+mod axis;
+
+pub use axis::*;
@@ -76,3 +76,195 @@ fn test_relu_cuda() {
     let res = device.relu_grad(&x);
     assert_eq!(res.read(), [0., 1., 0., 1., 1.]);
 }
+
+#[cfg(feature = "cpu")]
+pub fn roughly_equals(lhs: &[f64], rhs: &[f64], diff: f64) {
+    for (a, b) in lhs.iter().zip(rhs) {
+        let abs = (*a - *b).abs();
+        if abs > diff {
+            panic!(
+                "\n left: '{:?}',\n right: '{:?}', \n left elem.: {} != right elem. {}",
+                lhs, rhs, a, b
+            )
+        }
+    }
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_extended_activations_cpu() {
+    use custos_math::nn::ActivationOps;
+
+    let device = custos::CPU::new();
+    let x = Matrix::from((&device, (1, 5), [-2., -0.5, 0., 0.5, 2.]));
+
+    roughly_equals(&x.leaky_relu(0.1), &[-0.2, -0.05, 0., 0.5, 2.], 1e-6);
+    roughly_equals(
+        &device.elu(&x, 1.),
+        &[-0.864665, -0.393469, 0., 0.5, 2.],
+        1e-6,
+    );
+    roughly_equals(
+        &x.selu(),
+        &[-1.520166, -0.691758, 0., 0.52535, 2.101402],
+        1e-6,
+    );
+    roughly_equals(&x.gelu(), &[-0.0455, -0.154269, 0., 0.345731, 1.9545], 1e-6);
+    roughly_equals(
+        &x.gelu_tanh(),
+        &[-0.045402, -0.154286, 0., 0.345714, 1.954598],
+        1e-6,
+    );
+    roughly_equals(
+        &x.silu(),
+        &[-0.238406, -0.18877, 0., 0.31123, 1.761594],
+        1e-6,
+    );
+    roughly_equals(
+        &x.softplus(),
+        &[0.126928, 0.474077, 0.693147, 0.974077, 2.126928],
+        1e-6,
+    );
+    roughly_equals(
+        &x.mish(),
+        &[-0.252501, -0.220744, 0., 0.375245, 1.943959],
+        1e-6,
+    );
+    roughly_equals(
+        &x.hard_sigmoid(),
+        &[0.166667, 0.416667, 0.5, 0.583333, 0.833333],
+        1e-6,
+    );
+
+    // softplus does not overflow
+    let large = Matrix::from((&device, (1, 2), [1000., -1000.]));
+    assert_eq!(large.softplus().read(), [1000., 0.]);
+
+    let mut inplace = x.clone();
+    inplace.gelu_mut();
+    assert_eq!(inplace.read(), x.gelu().read());
+
+    let mut inplace = x.clone();
+    inplace.leaky_relu_mut(0.1);
+    assert_eq!(inplace.read(), x.leaky_relu(0.1).read());
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_extended_activation_grads_cpu() {
+    let device = custos::CPU::new();
+
+    // no values at the kinks of the piecewise defined activations
+    let xs = [-3.5, -2.5, -0.7, 0.3, 1.1, 2.4, 4.];
+    let h = 1e-6;
+
+    macro_rules! check_grad {
+        ($act:ident, $grad:ident $(, $param:expr)?) => {
+            let x = Matrix::from((&device, (1, xs.len()), xs));
+            let grad = x.$grad($($param)?).read();
+
+            let numeric = xs
+                .iter()
+                .map(|x| {
+                    let plus = Matrix::from((&device, (1, 1), [x + h])).$act($($param)?).read()[0];
+                    let minus = Matrix::from((&device, (1, 1), [x - h])).$act($($param)?).read()[0];
+                    (plus - minus) / (2. * h)
+                })
+                .collect::<Vec<f64>>();
+
+            roughly_equals(&grad, &numeric, 1e-5);
+        };
+    }
+
+    check_grad!(leaky_relu, leaky_relu_grad, 0.1);
+    check_grad!(elu, elu_grad, 1.5);
+    check_grad!(selu, selu_grad);
+    check_grad!(gelu, gelu_grad);
+    check_grad!(gelu_tanh, gelu_tanh_grad);
+    check_grad!(silu, silu_grad);
+    check_grad!(softplus, softplus_grad);
+    check_grad!(mish, mish_grad);
+    check_grad!(hard_sigmoid, hard_sigmoid_grad);
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_extended_activations_stack() {
+    use custos::{Buffer, Dim1, Stack};
+    use custos_math::nn::ActivationOps;
+
+    let data = Buffer::<_, _, Dim1<4>>::from((&Stack, [-2., -0.5, 0.5, 2.]));
+    let mut x = Matrix { data, dims: (2, 2) };
+
+    let out = Stack.elu(&x, 1.);
+    roughly_equals(out.as_slice(), &[-0.864665, -0.393469, 0.5, 2.], 1e-6);
+
+    let out = Stack.silu_grad(&x);
+    let expected = Matrix::from((&custos::CPU::new(), (2, 2), [-2., -0.5, 0.5, 2.])).silu_grad();
+    roughly_equals(out.as_slice(), &expected, 1e-12);
+
+    Stack.hard_sigmoid_mut(&mut x);
+    roughly_equals(
+        x.as_slice(),
+        &[0.166667, 0.416667, 0.583333, 0.833333],
+        1e-6,
+    );
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_extended_activations_cl() -> custos::Result<()> {
+    use custos_math::nn::ActivationOps;
+
+    let cpu = custos::CPU::new();
+    let device = custos::OpenCL::new(0)?;
+
+    let xs = [-3.5f32, -2., -0.5, 0., 0.5, 2., 3.5];
+    let cpu_x = Matrix::from((&cpu, (1, xs.len()), xs));
+    let x = Matrix::from((&device, (1, xs.len()), xs));
+
+    macro_rules! compare {
+        ($($act:ident $(($param:expr))?),*) => {
+            $(
+                let expected = cpu.$act(&cpu_x $(, $param)?).read();
+                let out = device.$act(&x $(, $param)?).read();
+                for (out, expected) in out.iter().zip(&expected) {
+                    assert!(
+                        (out - expected).abs() < 1e-5,
+                        "{}: {out} != {expected}",
+                        stringify!($act)
+                    );
+                }
+            )*
+        };
+    }
+
+    compare!(
+        leaky_relu(0.1),
+        leaky_relu_grad(0.1),
+        elu(1.),
+        elu_grad(1.),
+        selu,
+        selu_grad,
+        gelu,
+        gelu_grad,
+        gelu_tanh,
+        gelu_tanh_grad,
+        silu,
+        silu_grad,
+        softplus,
+        softplus_grad,
+        mish,
+        mish_grad,
+        hard_sigmoid,
+        hard_sigmoid_grad
+    );
+
+    let mut inplace = Matrix::from((&device, (1, xs.len()), xs));
+    device.mish_mut(&mut inplace);
+    let expected = cpu.mish(&cpu_x).read();
+    for (out, expected) in inplace.read().iter().zip(&expected) {
+        assert!((out - expected).abs() < 1e-5);
+    }
+    Ok(())
+}
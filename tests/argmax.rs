@@ -0,0 +1,52 @@
+use custos::CPU;
+use custos_math::{ArgMaxOps, Matrix};
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_argmax_ops() {
+    let device = CPU::new();
+    let a = Matrix::from((&device, (3, 3), [1., 9., 3., 4., -5., 6., 7., 8., -9.]));
+
+    assert_eq!(device.argmax(&a), 1);
+    assert_eq!(device.argmin(&a), 8);
+
+    let res = device.argmax_cols(&a);
+    assert_eq!(res.dims(), (3, 1));
+    assert_eq!(res.read(), vec![1, 2, 1]);
+
+    let res = device.argmax_rows(&a);
+    assert_eq!(res.dims(), (1, 3));
+    assert_eq!(res.read(), vec![2, 0, 1]);
+
+    let res = device.argmin_cols(&a);
+    assert_eq!(res.read(), vec![0, 1, 2]);
+
+    let res = device.argmin_rows(&a);
+    assert_eq!(res.read(), vec![0, 1, 2]);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_argmax_first_index() {
+    let device = CPU::new();
+    let a = Matrix::from((&device, (2, 3), [2, 2, 1, 0, 0, 0]));
+
+    assert_eq!(a.argmax(), 0);
+    assert_eq!(a.argmax_cols::<()>().read(), vec![0, 0]);
+    assert_eq!(a.argmin_cols::<()>().read(), vec![2, 0]);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_argmax_cl() -> custos::Result<()> {
+    let device = custos::OpenCL::new(0)?;
+    let a = Matrix::from((&device, (3, 3), [1f32, 9., 3., 4., -5., 6., 7., 8., -9.]));
+
+    assert_eq!(device.argmax(&a), 1);
+    assert_eq!(device.argmin(&a), 8);
+
+    assert_eq!(device.argmax_cols(&a).read(), vec![1, 2, 1]);
+    assert_eq!(device.argmax_rows(&a).read(), vec![2, 0, 1]);
+    assert_eq!(device.argmin_cols(&a).read(), vec![0, 1, 2]);
+    Ok(())
+}
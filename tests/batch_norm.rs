@@ -0,0 +1,212 @@
+use custos::CPU;
+use custos_math::{nn::BatchNormOps, Matrix};
+
+#[cfg(feature = "cpu")]
+pub fn roughly_equals(lhs: &[f64], rhs: &[f64], diff: f64) {
+    for (a, b) in lhs.iter().zip(rhs) {
+        let abs = (*a - *b).abs();
+        if abs > diff {
+            panic!(
+                "\n left: '{:?}',\n right: '{:?}', \n left elem.: {} != right elem. {}",
+                lhs, rhs, a, b
+            )
+        }
+    }
+}
+
+const X: [f64; 12] = [1., 2., 3., 4., -1., 0.5, 2., 0., -2., 3., 1., 1.5];
+const GAMMA: [f64; 3] = [0.5, 1., 2.];
+const BETA: [f64; 3] = [0.1, -0.2, 0.3];
+const EPS: f64 = 1e-5;
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_batch_norm_train_cpu() {
+    let device = CPU::new();
+
+    let x = Matrix::from((&device, (4, 3), X));
+    let gamma = Matrix::from((&device, (1, 3), GAMMA));
+    let beta = Matrix::from((&device, (1, 3), BETA));
+
+    let (out, mean, inv_std) = x.batch_norm_train(&gamma, &beta, EPS);
+
+    assert_eq!(mean.read(), vec![2.5, 0.5, 0.75]);
+    roughly_equals(&inv_std, &[0.894424, 0.894424, 0.549441], 1e-6);
+    roughly_equals(
+        &out,
+        &[
+            -0.570818, 1.141635, 2.772486, 0.770818, -1.541635, 0.025279, -0.123606, -0.647212,
+            -2.721928, 0.323606, 0.247212, 1.124162,
+        ],
+        1e-6,
+    );
+
+    // with the batch statistics as running statistics, inference matches training
+    let running_var = Matrix::from((
+        &device,
+        (1, 3),
+        inv_std
+            .iter()
+            .map(|inv_std| inv_std.powi(-2) - EPS)
+            .collect::<Vec<_>>(),
+    ));
+    let infer = device.batch_norm_infer(&x, &gamma, &beta, &mean, &running_var, EPS);
+    roughly_equals(&infer, &out, 1e-9);
+}
+
+#[cfg(feature = "cpu")]
+fn weighted_loss(device: &CPU, x: &[f64], gamma: &[f64], beta: &[f64], weights: &[f64]) -> f64 {
+    let x = Matrix::from((device, (4, 3), x.to_vec()));
+    let gamma = Matrix::from((device, (1, 3), gamma.to_vec()));
+    let beta = Matrix::from((device, (1, 3), beta.to_vec()));
+
+    let (out, _, _) = device.batch_norm_train(&x, &gamma, &beta, EPS);
+    out.iter().zip(weights).map(|(out, w)| out * w).sum()
+}
+
+#[cfg(feature = "cpu")]
+fn numeric_grad(values: &[f64], loss: impl Fn(&[f64]) -> f64) -> Vec<f64> {
+    let h = 1e-6;
+    (0..values.len())
+        .map(|idx| {
+            let mut plus = values.to_vec();
+            plus[idx] += h;
+            let mut minus = values.to_vec();
+            minus[idx] -= h;
+            (loss(&plus) - loss(&minus)) / (2. * h)
+        })
+        .collect()
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_batch_norm_grad_cpu() {
+    let device = CPU::new();
+
+    // the loss is sum(out * weights), hence the output gradients are the weights
+    let weights = [
+        0.3, -1.2, 0.7, 2., 0.1, -0.4, -0.9, 1.5, 0.2, 0.6, -0.3, 1.1,
+    ];
+
+    let x = Matrix::from((&device, (4, 3), X));
+    let gamma = Matrix::from((&device, (1, 3), GAMMA));
+    let beta = Matrix::from((&device, (1, 3), BETA));
+    let grads = Matrix::from((&device, (4, 3), weights));
+
+    let (_, mean, inv_std) = x.batch_norm_train(&gamma, &beta, EPS);
+    let (dx, dgamma, dbeta) = grads.batch_norm_grad(&x, &gamma, &mean, &inv_std);
+
+    let expected_dx = numeric_grad(&X, |x| weighted_loss(&device, x, &GAMMA, &BETA, &weights));
+    let expected_dgamma = numeric_grad(&GAMMA, |gamma| {
+        weighted_loss(&device, &X, gamma, &BETA, &weights)
+    });
+    let expected_dbeta = numeric_grad(&BETA, |beta| {
+        weighted_loss(&device, &X, &GAMMA, beta, &weights)
+    });
+
+    roughly_equals(&dx, &expected_dx, 1e-6);
+    roughly_equals(&dgamma, &expected_dgamma, 1e-6);
+    roughly_equals(&dbeta, &expected_dbeta, 1e-6);
+
+    // the normalisation removes the mean, hence the gradients of every feature sum up to zero
+    roughly_equals(&dx.sum_rows::<()>(), &[0.; 3], 1e-12);
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_batch_norm_stack() {
+    use custos::{Buffer, Dim1, Stack};
+
+    let x = Matrix {
+        data: Buffer::<_, _, Dim1<12>>::from((&Stack, X)),
+        dims: (4, 3),
+    };
+    let grads = Matrix {
+        data: Buffer::<_, _, Dim1<12>>::from((
+            &Stack,
+            [1., -1., 0.5, 2., 0., 1., -3., 1., 1., 0., 2., -1.],
+        )),
+        dims: (4, 3),
+    };
+    let gamma = Matrix {
+        data: Buffer::<_, _, Dim1<3>>::from((&Stack, GAMMA)),
+        dims: (1, 3),
+    };
+    let beta = Matrix {
+        data: Buffer::<_, _, Dim1<3>>::from((&Stack, BETA)),
+        dims: (1, 3),
+    };
+
+    let (out, mean, inv_std) = Stack.batch_norm_train(&x, &gamma, &beta, EPS);
+    let (dx, dgamma, dbeta) = Stack.batch_norm_grad(&x, &grads, &gamma, &mean, &inv_std);
+
+    let device = CPU::new();
+    let cpu_x = Matrix::from((&device, (4, 3), X));
+    let cpu_grads = Matrix::from((
+        &device,
+        (4, 3),
+        [1., -1., 0.5, 2., 0., 1., -3., 1., 1., 0., 2., -1.],
+    ));
+    let cpu_gamma = Matrix::from((&device, (1, 3), GAMMA));
+    let cpu_beta = Matrix::from((&device, (1, 3), BETA));
+
+    let (expected_out, expected_mean, expected_inv_std) =
+        cpu_x.batch_norm_train(&cpu_gamma, &cpu_beta, EPS);
+    let (expected_dx, expected_dgamma, expected_dbeta) =
+        cpu_grads.batch_norm_grad(&cpu_x, &cpu_gamma, &expected_mean, &expected_inv_std);
+
+    assert_eq!(out.as_slice(), &*expected_out);
+    assert_eq!(mean.as_slice(), &*expected_mean);
+    assert_eq!(inv_std.as_slice(), &*expected_inv_std);
+    assert_eq!(dx.as_slice(), &*expected_dx);
+    assert_eq!(dgamma.as_slice(), &*expected_dgamma);
+    assert_eq!(dbeta.as_slice(), &*expected_dbeta);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_batch_norm_cl() -> custos::Result<()> {
+    use custos::OpenCL;
+
+    let to_f32 = |values: &[f64]| values.iter().map(|x| *x as f32).collect::<Vec<_>>();
+    let grads = [1f32, -1., 0.5, 2., 0., 1., -3., 1., 1., 0., 2., -1.];
+
+    let device = OpenCL::new(0)?;
+    let x = Matrix::from((&device, (4, 3), to_f32(&X)));
+    let gamma = Matrix::from((&device, (1, 3), to_f32(&GAMMA)));
+    let beta = Matrix::from((&device, (1, 3), to_f32(&BETA)));
+    let cl_grads = Matrix::from((&device, (4, 3), grads));
+
+    let (out, mean, inv_std) = x.batch_norm_train(&gamma, &beta, 1e-5);
+    let (dx, dgamma, dbeta) = cl_grads.batch_norm_grad(&x, &gamma, &mean, &inv_std);
+    let infer = x.batch_norm_infer(&gamma, &beta, &mean, &gamma, 1e-5);
+
+    let cpu = CPU::new();
+    let cpu_x = Matrix::from((&cpu, (4, 3), to_f32(&X)));
+    let cpu_gamma = Matrix::from((&cpu, (1, 3), to_f32(&GAMMA)));
+    let cpu_beta = Matrix::from((&cpu, (1, 3), to_f32(&BETA)));
+    let cpu_grads = Matrix::from((&cpu, (4, 3), grads));
+
+    let (expected_out, expected_mean, expected_inv_std) =
+        cpu_x.batch_norm_train(&cpu_gamma, &cpu_beta, 1e-5);
+    let (expected_dx, expected_dgamma, expected_dbeta) =
+        cpu_grads.batch_norm_grad(&cpu_x, &cpu_gamma, &expected_mean, &expected_inv_std);
+    let expected_infer =
+        cpu_x.batch_norm_infer(&cpu_gamma, &cpu_beta, &expected_mean, &cpu_gamma, 1e-5);
+
+    // OpenCL is not required to round divisions and powers correctly
+    let roughly_equals = |lhs: Vec<f32>, rhs: Vec<f32>| {
+        for (a, b) in lhs.iter().zip(&rhs) {
+            assert!((a - b).abs() < 1e-5, "{lhs:?} != {rhs:?}");
+        }
+    };
+
+    roughly_equals(out.read(), expected_out.read());
+    roughly_equals(mean.read(), expected_mean.read());
+    roughly_equals(inv_std.read(), expected_inv_std.read());
+    roughly_equals(dx.read(), expected_dx.read());
+    roughly_equals(dgamma.read(), expected_dgamma.read());
+    roughly_equals(dbeta.read(), expected_dbeta.read());
+    roughly_equals(infer.read(), expected_infer.read());
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use custos::CPU;
+use custos_math::{BatchedGemm, Matrix};
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_batched_gemm() {
+    let device = CPU::new();
+
+    // three 2 x 3 matrices
+    let lhs = Matrix::from((
+        &device,
+        (6, 3),
+        [
+            1., 2., 3., 4., 5., 6., 0., 1., 0., 1., 0., 1., -1., 2., -3., 4., -5., 6.,
+        ],
+    ));
+    // three 3 x 2 matrices
+    let rhs = Matrix::from((
+        &device,
+        (9, 2),
+        [
+            6., 5., 4., 3., 2., 1., 1., 0., 0., 1., 1., 1., 2., 2., 2., 2., 2., 2.,
+        ],
+    ));
+
+    let out: Matrix = device.batched_gemm(3, &lhs, &rhs);
+    assert_eq!(out.dims(), (6, 2));
+    assert_eq!(
+        out.read(),
+        vec![20., 14., 56., 41., 0., 1., 2., 1., -4., -4., 10., 10.]
+    );
+
+    for idx in 0..3 {
+        let lhs = Matrix::from((&device, (2, 3), lhs[idx * 6..idx * 6 + 6].to_vec()));
+        let rhs = Matrix::from((&device, (3, 2), rhs[idx * 6..idx * 6 + 6].to_vec()));
+        let single: Matrix = lhs.gemm(&rhs);
+        assert_eq!(&out[idx * 4..idx * 4 + 4], single.as_slice());
+    }
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_batched_gemm_broadcast() {
+    let device = CPU::new();
+
+    let lhs = Matrix::from((
+        &device,
+        (6, 3),
+        [
+            1., 2., 3., 4., 5., 6., 0., 1., 0., 1., 0., 1., -1., 2., -3., 4., -5., 6.,
+        ],
+    ));
+    let rhs = Matrix::from((&device, (3, 2), [6., 5., 4., 3., 2., 1.]));
+
+    let out: Matrix = lhs.batched_gemm(3, &rhs);
+    assert_eq!(out.dims(), (6, 2));
+
+    // the broadcasted rhs equals a plain gemm of the stacked lhs matrices
+    let expected: Matrix = lhs.gemm(&rhs);
+    assert_eq!(out.read(), expected.read());
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_batched_gemm_cl() -> custos::Result<()> {
+    let cpu = CPU::new();
+    let device = custos::OpenCL::new(0)?;
+
+    let (batch, m, k, n) = (3, 4, 8, 16);
+
+    let lhs_data = (0..batch * m * k)
+        .map(|x| (x % 7) as f32 - 3.)
+        .collect::<Vec<_>>();
+    let rhs_data = (0..batch * k * n)
+        .map(|x| (x % 5) as f32 - 2.)
+        .collect::<Vec<_>>();
+
+    let lhs = Matrix::from((&device, (batch * m, k), lhs_data.clone()));
+    let rhs = Matrix::from((&device, (batch * k, n), rhs_data.clone()));
+    let cpu_lhs = Matrix::from((&cpu, (batch * m, k), lhs_data));
+    let cpu_rhs = Matrix::from((&cpu, (batch * k, n), rhs_data));
+
+    let out = device.batched_gemm(batch, &lhs, &rhs);
+    let expected: Matrix<f32> = cpu.batched_gemm(batch, &cpu_lhs, &cpu_rhs);
+    assert_eq!(out.dims(), (batch * m, n));
+    assert_eq!(out.read(), expected.read());
+
+    // broadcast the first rhs matrix
+    let rhs = Matrix::from((&device, (k, n), cpu_rhs[..k * n].to_vec()));
+    let cpu_rhs = Matrix::from((&cpu, (k, n), cpu_rhs[..k * n].to_vec()));
+
+    let out = device.batched_gemm(batch, &lhs, &rhs);
+    let expected: Matrix<f32> = cpu.batched_gemm(batch, &cpu_lhs, &cpu_rhs);
+    assert_eq!(out.read(), expected.read());
+    Ok(())
+}
@@ -0,0 +1,205 @@
+use custos::CPU;
+use custos_math::Matrix;
+
+#[cfg(feature = "cpu")]
+pub fn roughly_equals(lhs: &[f64], rhs: &[f64], diff: f64) {
+    for (a, b) in lhs.iter().zip(rhs) {
+        let abs = (*a - *b).abs();
+        if abs > diff {
+            panic!(
+                "\n left: '{:?}',\n right: '{:?}', \n left elem.: {} != right elem. {}",
+                lhs, rhs, a, b
+            )
+        }
+    }
+}
+
+const PREDS: [f64; 6] = [0.9, 0.2, 0.6, 0.05, 0.7, 0.4];
+const LOGITS: [f64; 6] = [2.1, -0.3, 0.8, -4., 1.2, -0.6];
+const TARGETS: [f64; 6] = [1., 0., 1., 1., 0., 0.];
+
+#[cfg(feature = "cpu")]
+fn numeric_grad(values: &[f64], loss: impl Fn(&[f64]) -> f64) -> Vec<f64> {
+    let h = 1e-6;
+    (0..values.len())
+        .map(|idx| {
+            let mut plus = values.to_vec();
+            plus[idx] += h;
+            let mut minus = values.to_vec();
+            minus[idx] -= h;
+            (loss(&plus) - loss(&minus)) / (2. * h)
+        })
+        .collect()
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_bce_cpu() {
+    let device = CPU::new();
+
+    let preds = Matrix::from((&device, (2, 3), PREDS));
+    let targets = Matrix::from((&device, (2, 3), TARGETS));
+
+    let (loss, grad) = preds.bce(&targets);
+    assert!((loss - 0.924977).abs() < 1e-6);
+
+    let numeric = numeric_grad(&PREDS, |preds| {
+        let preds = Matrix::from((&device, (2, 3), preds.to_vec()));
+        preds.bce_loss(&targets)
+    });
+    roughly_equals(&grad, &numeric, 1e-6);
+
+    // probabilities of 0 and 1 are clipped
+    let preds = Matrix::from((&device, (1, 2), [0., 1.]));
+    let targets = Matrix::from((&device, (1, 2), [1., 0.]));
+    let (loss, grad) = preds.bce(&targets);
+    assert!(loss.is_finite());
+    assert!(grad.iter().all(|grad| grad.is_finite()));
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_bce_with_logits_cpu() {
+    let device = CPU::new();
+
+    let logits = Matrix::from((&device, (2, 3), LOGITS));
+    let targets = Matrix::from((&device, (2, 3), TARGETS));
+
+    let (loss, grad) = logits.bce_with_logits(&targets);
+
+    let probs = logits.sigmoid();
+    assert!((loss - probs.bce_loss(&targets)).abs() < 1e-12);
+
+    // (sigmoid(logits) - targets) / len
+    let expected_grad = probs
+        .iter()
+        .zip(TARGETS)
+        .map(|(prob, target)| (prob - target) / 6.)
+        .collect::<Vec<_>>();
+    roughly_equals(&grad, &expected_grad, 1e-12);
+
+    let numeric = numeric_grad(&LOGITS, |logits| {
+        let logits = Matrix::from((&device, (2, 3), logits.to_vec()));
+        logits.bce_with_logits_loss(&targets)
+    });
+    roughly_equals(&grad, &numeric, 1e-6);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_bce_with_logits_large_logits() {
+    let device = CPU::new();
+
+    let logits = Matrix::from((&device, (1, 4), [1000., -1000., 1000., -1000.]));
+    let targets = Matrix::from((&device, (1, 4), [1., 0., 0., 1.]));
+
+    // bce would clip the probabilities and report a loss of about -ln(1e-7) for the wrong predictions
+    let (loss, grad) = logits.bce_with_logits(&targets);
+    assert_eq!(loss, 500.);
+    assert_eq!(grad.read(), vec![0., 0., 0.25, -0.25]);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_focal_cpu() {
+    let device = CPU::new();
+
+    let preds = Matrix::from((&device, (2, 3), PREDS));
+    let targets = Matrix::from((&device, (2, 3), TARGETS));
+
+    let (loss, grad) = preds.focal(&targets, 0.25, 2.);
+    assert!((loss - 0.201177).abs() < 1e-6);
+
+    let numeric = numeric_grad(&PREDS, |preds| {
+        let preds = Matrix::from((&device, (2, 3), preds.to_vec()));
+        preds.focal_loss(&targets, 0.25, 2.)
+    });
+    roughly_equals(&grad, &numeric, 1e-6);
+
+    // without focusing and class weighting, the focal loss is half of the bce loss
+    let (focal_loss, focal_grad) = preds.focal(&targets, 0.5, 0.);
+    let (bce_loss, bce_grad) = preds.bce(&targets);
+    assert!((focal_loss * 2. - bce_loss).abs() < 1e-12);
+    roughly_equals(&focal_grad.muls(2.), &bce_grad, 1e-12);
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_bce_stack() {
+    use custos::{Buffer, Dim1, Stack};
+    use custos_math::nn::{BCEOp, BCEWithLogitsOp, FocalOp};
+
+    let preds = Matrix {
+        data: Buffer::<_, _, Dim1<6>>::from((&Stack, PREDS)),
+        dims: (2, 3),
+    };
+    let logits = Matrix {
+        data: Buffer::<_, _, Dim1<6>>::from((&Stack, LOGITS)),
+        dims: (2, 3),
+    };
+    let targets = Matrix {
+        data: Buffer::<_, _, Dim1<6>>::from((&Stack, TARGETS)),
+        dims: (2, 3),
+    };
+
+    let device = CPU::new();
+    let cpu_preds = Matrix::from((&device, (2, 3), PREDS));
+    let cpu_logits = Matrix::from((&device, (2, 3), LOGITS));
+    let cpu_targets = Matrix::from((&device, (2, 3), TARGETS));
+
+    let (loss, grad) = Stack.bce(&preds, &targets);
+    let (expected_loss, expected_grad) = cpu_preds.bce(&cpu_targets);
+    assert_eq!(loss, expected_loss);
+    assert_eq!(grad.as_slice(), &*expected_grad);
+
+    let (loss, grad) = Stack.bce_with_logits(&logits, &targets);
+    let (expected_loss, expected_grad) = cpu_logits.bce_with_logits(&cpu_targets);
+    assert_eq!(loss, expected_loss);
+    assert_eq!(grad.as_slice(), &*expected_grad);
+
+    let (loss, grad) = Stack.focal(&preds, &targets, 0.25, 2.);
+    let (expected_loss, expected_grad) = cpu_preds.focal(&cpu_targets, 0.25, 2.);
+    assert_eq!(loss, expected_loss);
+    assert_eq!(grad.as_slice(), &*expected_grad);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_bce_cl() -> custos::Result<()> {
+    use custos::OpenCL;
+
+    let to_f32 = |values: &[f64]| values.iter().map(|x| *x as f32).collect::<Vec<_>>();
+
+    let device = OpenCL::new(0)?;
+    let preds = Matrix::from((&device, (2, 3), to_f32(&PREDS)));
+    let logits = Matrix::from((&device, (2, 3), to_f32(&LOGITS)));
+    let targets = Matrix::from((&device, (2, 3), to_f32(&TARGETS)));
+
+    let cpu = CPU::new();
+    let cpu_preds = Matrix::from((&cpu, (2, 3), to_f32(&PREDS)));
+    let cpu_logits = Matrix::from((&cpu, (2, 3), to_f32(&LOGITS)));
+    let cpu_targets = Matrix::from((&cpu, (2, 3), to_f32(&TARGETS)));
+
+    // OpenCL is not required to round exp, ln and pow correctly
+    let roughly_equals = |lhs: Vec<f32>, rhs: Vec<f32>| {
+        for (a, b) in lhs.iter().zip(&rhs) {
+            assert!((a - b).abs() < 1e-5, "{lhs:?} != {rhs:?}");
+        }
+    };
+
+    let (loss, grad) = preds.bce(&targets);
+    let (expected_loss, expected_grad) = cpu_preds.bce(&cpu_targets);
+    roughly_equals(vec![loss], vec![expected_loss]);
+    roughly_equals(grad.read(), expected_grad.read());
+
+    let (loss, grad) = logits.bce_with_logits(&targets);
+    let (expected_loss, expected_grad) = cpu_logits.bce_with_logits(&cpu_targets);
+    roughly_equals(vec![loss], vec![expected_loss]);
+    roughly_equals(grad.read(), expected_grad.read());
+
+    let (loss, grad) = preds.focal(&targets, 0.25, 2.);
+    let (expected_loss, expected_grad) = cpu_preds.focal(&cpu_targets, 0.25, 2.);
+    roughly_equals(vec![loss], vec![expected_loss]);
+    roughly_equals(grad.read(), expected_grad.read());
+    Ok(())
+}
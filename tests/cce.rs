@@ -13,3 +13,114 @@ fn test_cce() {
 
     println!("res: {:?}", res);
 }
+
+#[cfg(feature = "cpu")]
+pub fn roughly_equals(lhs: &[f64], rhs: &[f64], diff: f64) {
+    for (a, b) in lhs.iter().zip(rhs) {
+        let abs = (*a - *b).abs();
+        if abs > diff {
+            panic!(
+                "\n left: '{:?}',\n right: '{:?}', \n left elem.: {} != right elem. {}",
+                lhs, rhs, a, b
+            )
+        }
+    }
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_softmax_cce_cpu() {
+    use custos_math::nn::SoftmaxCCEOp;
+
+    let device = CPU::new();
+
+    let logits = Matrix::from((
+        &device,
+        (3, 3),
+        [2., 1., 0.1, 0.5, 2.5, -1., 1000., 0., -1000.],
+    ));
+    let one_hot = Matrix::from((&device, (3, 3), [1., 0., 0., 0., 1., 0., 0., 0., 1.]));
+    let idx = Matrix::from((&device, (3, 1), [0u32, 1, 2]));
+
+    let expected_grad = [
+        -0.113666, 0.080811, 0.032855, 0.038705, -0.047341, 0.008636, 0.333333, 0., -0.333333,
+    ];
+
+    // the log probability of the last target is -2000, which would be clipped by cce
+    let (loss, grad) = device.softmax_cce(&logits, &one_hot);
+    assert!((loss - 666.856736).abs() < 1e-5);
+    roughly_equals(&grad, &expected_grad, 1e-6);
+
+    let (idx_loss, idx_grad) = logits.softmax_cce_idx(&idx);
+    assert_eq!(loss, idx_loss);
+    assert_eq!(grad.read(), idx_grad.read());
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_softmax_cce_matches_cce() {
+    let device = CPU::new();
+
+    let logits = Matrix::from((&device, (2, 3), [0.3, -0.2, 1.4, 2., 0.1, 0.7]));
+    let targets = Matrix::from((&device, (2, 3), [0., 0., 1., 0., 1., 0.]));
+
+    let (loss, grad) = logits.softmax_cce(&targets);
+
+    let probs = logits.softmax();
+    assert!((loss - probs.cce_loss(&targets)).abs() < 1e-12);
+
+    let chained = probs.cce_grad(&targets).softmax_grad(&probs);
+    roughly_equals(&grad, &chained, 1e-12);
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_softmax_cce_stack() {
+    use custos::{Buffer, Dim1, Stack};
+    use custos_math::nn::SoftmaxCCEOp;
+
+    let logits = Matrix {
+        data: Buffer::<_, _, Dim1<4>>::from((&Stack, [1., 2., 3., 0.])),
+        dims: (2, 2),
+    };
+    let idx = Matrix {
+        data: Buffer::<_, _, Dim1<2>>::from((&Stack, [1u32, 0])),
+        dims: (2, 1),
+    };
+
+    let (loss, grad) = Stack.softmax_cce_idx(&logits, &idx);
+
+    let device = CPU::new();
+    let cpu_logits = Matrix::from((&device, (2, 2), [1., 2., 3., 0.]));
+    let cpu_idx = Matrix::from((&device, (2, 1), [1u32, 0]));
+    let (expected_loss, expected_grad) = cpu_logits.softmax_cce_idx(&cpu_idx);
+
+    assert_eq!(loss, expected_loss);
+    assert_eq!(grad.as_slice(), &*expected_grad);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_softmax_cce_cl() -> custos::Result<()> {
+    use custos::OpenCL;
+
+    let device = OpenCL::new(0)?;
+
+    let logits = Matrix::from((&device, (2, 3), [0.3f32, -0.2, 1.4, 2., 0.1, 0.7]));
+    let one_hot = Matrix::from((&device, (2, 3), [0f32, 0., 1., 0., 1., 0.]));
+    let idx = Matrix::from((&device, (2, 1), [2u32, 1]));
+
+    let (loss, grad) = logits.softmax_cce(&one_hot);
+    let (idx_loss, idx_grad) = logits.softmax_cce_idx(&idx);
+
+    let cpu = CPU::new();
+    let cpu_logits = Matrix::from((&cpu, (2, 3), [0.3f32, -0.2, 1.4, 2., 0.1, 0.7]));
+    let cpu_one_hot = Matrix::from((&cpu, (2, 3), [0f32, 0., 1., 0., 1., 0.]));
+    let (expected_loss, expected_grad) = cpu_logits.softmax_cce(&cpu_one_hot);
+
+    assert_eq!(loss, expected_loss);
+    assert_eq!(idx_loss, expected_loss);
+    assert_eq!(grad.read(), expected_grad.read());
+    assert_eq!(idx_grad.read(), expected_grad.read());
+    Ok(())
+}
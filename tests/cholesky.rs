@@ -0,0 +1,141 @@
+use custos::CPU;
+use custos_math::{LinalgError, Matrix};
+
+#[cfg(feature = "cpu")]
+pub fn roughly_equals(lhs: &[f64], rhs: &[f64], diff: f64) {
+    for (a, b) in lhs.iter().zip(rhs) {
+        let abs = (*a - *b).abs();
+        if abs > diff {
+            panic!(
+                "\n left: '{:?}',\n right: '{:?}', \n left elem.: {} != right elem. {}",
+                lhs, rhs, a, b
+            )
+        }
+    }
+}
+
+const SPD: [f64; 9] = [4., 12., -16., 12., 37., -43., -16., -43., 98.];
+const B: [f64; 6] = [44., -4., 129., -6., -200., 55.];
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_cholesky_cpu() -> Result<(), LinalgError> {
+    let device = CPU::new();
+
+    let a = Matrix::from((&device, (3, 3), SPD));
+    let l = a.cholesky()?;
+    assert_eq!(l.read(), vec![2., 0., 0., 6., 1., 0., -8., 5., 3.]);
+
+    let reconstructed: Matrix = l.gemm(&l.T::<()>());
+    roughly_equals(&reconstructed, &SPD, 1e-12);
+
+    let b = Matrix::from((&device, (3, 2), B));
+    let x = l.cholesky_solve(&b)?;
+    roughly_equals(&x, &[1., 0., 2., 1., -1., 1.], 1e-12);
+    roughly_equals(&x, &a.solve(&b)?, 1e-12);
+
+    assert!((a.cholesky_logdet()? - 36f64.ln()).abs() < 1e-12);
+    assert!((a.cholesky_logdet()? - a.det()?.ln()).abs() < 1e-12);
+    Ok(())
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_cholesky_errors_cpu() {
+    let device = CPU::new();
+
+    let indefinite = Matrix::from((&device, (3, 3), [4., 2., 0., 2., 1., 0., 0., 0., 1.]));
+    assert_eq!(
+        indefinite.cholesky().unwrap_err(),
+        LinalgError::NotPositiveDefinite { pivot: 1 }
+    );
+    assert_eq!(
+        indefinite.cholesky_logdet(),
+        Err(LinalgError::NotPositiveDefinite { pivot: 1 })
+    );
+
+    let negative = Matrix::from((&device, (2, 2), [-1., 0., 0., 1.]));
+    assert_eq!(
+        negative.cholesky().unwrap_err(),
+        LinalgError::NotPositiveDefinite { pivot: 0 }
+    );
+
+    let nan = Matrix::from((&device, (2, 2), [1., 0., 0., f64::NAN]));
+    assert_eq!(
+        nan.cholesky().unwrap_err(),
+        LinalgError::NotPositiveDefinite { pivot: 1 }
+    );
+
+    let rect = Matrix::from((&device, (2, 3), [1., 2., 3., 4., 5., 6.]));
+    assert_eq!(
+        rect.cholesky().unwrap_err(),
+        LinalgError::NotSquare { rows: 2, cols: 3 }
+    );
+
+    let l = Matrix::from((&device, (2, 2), [2., 0., 1., 2.]));
+    let b = Matrix::from((&device, (3, 1), [1., 2., 3.]));
+    assert!(matches!(
+        l.cholesky_solve(&b),
+        Err(LinalgError::DimsMismatch { .. })
+    ));
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_cholesky_stack() -> Result<(), LinalgError> {
+    use custos::{Buffer, Dim1, Stack};
+    use custos_math::{CholeskyOps, CholeskySolveOp};
+
+    let a = Matrix {
+        data: Buffer::<_, _, Dim1<9>>::from((&Stack, SPD)),
+        dims: (3, 3),
+    };
+    let b = Matrix {
+        data: Buffer::<_, _, Dim1<6>>::from((&Stack, B)),
+        dims: (3, 2),
+    };
+
+    let l = Stack.cholesky(&a)?;
+    assert_eq!(l.as_slice(), &[2., 0., 0., 6., 1., 0., -8., 5., 3.]);
+
+    let x = Stack.cholesky_solve(&l, &b)?;
+    let device = CPU::new();
+    let cpu_l = Matrix::from((&device, (3, 3), SPD)).cholesky()?;
+    let cpu_x = cpu_l.cholesky_solve(&Matrix::from((&device, (3, 2), B)))?;
+    assert_eq!(x.as_slice(), &*cpu_x);
+
+    assert!((Stack.cholesky_logdet(&a)? - 36f64.ln()).abs() < 1e-12);
+    Ok(())
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_cholesky_cl() -> custos::Result<()> {
+    use custos::OpenCL;
+
+    let to_f32 = |values: &[f64]| values.iter().map(|x| *x as f32).collect::<Vec<_>>();
+
+    let device = OpenCL::new(0)?;
+    let a = Matrix::from((&device, (3, 3), to_f32(&SPD)));
+    let b = Matrix::from((&device, (3, 2), to_f32(&B)));
+
+    let cpu = CPU::new();
+    let cpu_a = Matrix::from((&cpu, (3, 3), to_f32(&SPD)));
+    let cpu_b = Matrix::from((&cpu, (3, 2), to_f32(&B)));
+
+    let l = a.cholesky()?;
+    let cpu_l = cpu_a.cholesky()?;
+    assert_eq!(l.read(), cpu_l.read());
+    assert_eq!(
+        l.cholesky_solve(&b)?.read(),
+        cpu_l.cholesky_solve(&cpu_b)?.read()
+    );
+    assert_eq!(a.cholesky_logdet()?, cpu_a.cholesky_logdet()?);
+
+    let indefinite = Matrix::from((&device, (2, 2), [1f32, 2., 2., 1.]));
+    assert!(matches!(
+        indefinite.cholesky(),
+        Err(LinalgError::NotPositiveDefinite { pivot: 1 })
+    ));
+    Ok(())
+}
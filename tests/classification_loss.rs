@@ -0,0 +1,224 @@
+use custos::CPU;
+use custos_math::Matrix;
+
+#[cfg(feature = "cpu")]
+pub fn roughly_equals(lhs: &[f64], rhs: &[f64], diff: f64) {
+    for (a, b) in lhs.iter().zip(rhs) {
+        let abs = (*a - *b).abs();
+        if abs > diff {
+            panic!(
+                "\n left: '{:?}',\n right: '{:?}', \n left elem.: {} != right elem. {}",
+                lhs, rhs, a, b
+            )
+        }
+    }
+}
+
+#[cfg(feature = "cpu")]
+fn numeric_grad(values: &[f64], loss: impl Fn(&[f64]) -> f64) -> Vec<f64> {
+    let h = 1e-6;
+    (0..values.len())
+        .map(|idx| {
+            let mut plus = values.to_vec();
+            plus[idx] += h;
+            let mut minus = values.to_vec();
+            minus[idx] -= h;
+            (loss(&plus) - loss(&minus)) / (2. * h)
+        })
+        .collect()
+}
+
+const Q: [f64; 6] = [0.1, 0.6, 0.3, 0., 0.5, 0.5];
+
+const SCORES: [f64; 12] = [1., 3., 0.5, 1.2, 0.2, -1., 0.9, 0.8, 2., 2., 2., -3.];
+const CLASSES: [u32; 3] = [1, 2, 0];
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_kl_div_cpu() {
+    let device = CPU::new();
+
+    let log_p = Matrix::from((&device, (2, 3), [0.2f64, 0.3, 0.5, 0.6, 0.3, 0.1])).ln();
+    let q = Matrix::from((&device, (2, 3), Q));
+
+    let (loss, grad) = log_p.kl_div(&q);
+    assert!((loss - 0.626729).abs() < 1e-6);
+
+    let numeric = numeric_grad(&log_p.read(), |log_p| {
+        let log_p = Matrix::from((&device, (2, 3), log_p.to_vec()));
+        log_p.kl_div_loss(&q)
+    });
+    roughly_equals(&grad, &numeric, 1e-6);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_kl_div_is_cce_minus_entropy() {
+    let device = CPU::new();
+
+    let logits = Matrix::from((&device, (2, 3), [0.3, -0.2, 1.4, 2., 0.1, 0.7]));
+    let q = Matrix::from((&device, (2, 3), Q));
+
+    let entropy = -Q
+        .iter()
+        .filter(|q| **q > 0.)
+        .map(|q| q * q.ln())
+        .sum::<f64>()
+        / 2.;
+
+    let (cce_loss, _) = logits.softmax_cce(&q);
+    let kl_div_loss = logits.log_softmax().kl_div_loss(&q);
+    assert!((kl_div_loss - (cce_loss - entropy)).abs() < 1e-12);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_nll_cpu() {
+    let device = CPU::new();
+
+    let logits = Matrix::from((&device, (3, 4), SCORES));
+    let classes = Matrix::from((&device, (3, 1), CLASSES));
+
+    let log_probs = logits.log_softmax();
+    let (loss, grad) = log_probs.nll(&classes);
+
+    let (expected_loss, _) = logits.softmax_cce_idx(&classes);
+    assert!((loss - expected_loss).abs() < 1e-12);
+
+    let numeric = numeric_grad(&log_probs.read(), |log_probs| {
+        let log_probs = Matrix::from((&device, (3, 4), log_probs.to_vec()));
+        log_probs.nll(&classes).0
+    });
+    roughly_equals(&grad, &numeric, 1e-6);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+#[should_panic]
+fn test_nll_class_out_of_range() {
+    let device = CPU::new();
+
+    let log_probs = Matrix::from((&device, (1, 2), [-0.5, -1.]));
+    let classes = Matrix::from((&device, (1, 1), [2u32]));
+    log_probs.nll(&classes);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_hinge_cpu() {
+    let device = CPU::new();
+
+    let scores = Matrix::from((&device, (3, 4), SCORES));
+    let classes = Matrix::from((&device, (3, 1), CLASSES));
+
+    let (loss, grad) = scores.hinge(&classes);
+    assert!((loss - 3.2 / 3.).abs() < 1e-12);
+
+    let numeric = numeric_grad(&SCORES, |scores| {
+        let scores = Matrix::from((&device, (3, 4), scores.to_vec()));
+        scores.hinge(&classes).0
+    });
+    roughly_equals(&grad, &numeric, 1e-6);
+
+    let (loss, grad) = scores.squared_hinge(&classes);
+    assert!((loss - 2.9 / 3.).abs() < 1e-12);
+
+    let numeric = numeric_grad(&SCORES, |scores| {
+        let scores = Matrix::from((&device, (3, 4), scores.to_vec()));
+        scores.squared_hinge(&classes).0
+    });
+    roughly_equals(&grad, &numeric, 1e-6);
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_classification_loss_stack() {
+    use custos::{Buffer, Dim1, Stack};
+    use custos_math::nn::{HingeOp, KLDivOp, NLLOp};
+
+    let scores = Matrix {
+        data: Buffer::<_, _, Dim1<12>>::from((&Stack, SCORES)),
+        dims: (3, 4),
+    };
+    let classes = Matrix {
+        data: Buffer::<_, _, Dim1<3>>::from((&Stack, CLASSES)),
+        dims: (3, 1),
+    };
+    let log_p = Matrix {
+        data: Buffer::<_, _, Dim1<6>>::from((&Stack, [-1.5, -0.7, -1.2, -0.5, -1., -2.5])),
+        dims: (2, 3),
+    };
+    let q = Matrix {
+        data: Buffer::<_, _, Dim1<6>>::from((&Stack, Q)),
+        dims: (2, 3),
+    };
+
+    let device = CPU::new();
+    let cpu_scores = Matrix::from((&device, (3, 4), SCORES));
+    let cpu_classes = Matrix::from((&device, (3, 1), CLASSES));
+    let cpu_log_p = Matrix::from((&device, (2, 3), [-1.5, -0.7, -1.2, -0.5, -1., -2.5]));
+    let cpu_q = Matrix::from((&device, (2, 3), Q));
+
+    let (loss, grad) = Stack.kl_div(&log_p, &q);
+    let (expected_loss, expected_grad) = cpu_log_p.kl_div(&cpu_q);
+    assert_eq!(loss, expected_loss);
+    assert_eq!(grad.as_slice(), &*expected_grad);
+
+    let (loss, grad) = Stack.nll(&scores, &classes);
+    let (expected_loss, expected_grad) = cpu_scores.nll(&cpu_classes);
+    assert_eq!(loss, expected_loss);
+    assert_eq!(grad.as_slice(), &*expected_grad);
+
+    let (loss, grad) = Stack.hinge(&scores, &classes);
+    let (expected_loss, expected_grad) = cpu_scores.hinge(&cpu_classes);
+    assert_eq!(loss, expected_loss);
+    assert_eq!(grad.as_slice(), &*expected_grad);
+
+    let (loss, grad) = Stack.squared_hinge(&scores, &classes);
+    let (expected_loss, expected_grad) = cpu_scores.squared_hinge(&cpu_classes);
+    assert_eq!(loss, expected_loss);
+    assert_eq!(grad.as_slice(), &*expected_grad);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_classification_loss_cl() -> custos::Result<()> {
+    use custos::OpenCL;
+
+    let to_f32 = |values: &[f64]| values.iter().map(|x| *x as f32).collect::<Vec<_>>();
+
+    let device = OpenCL::new(0)?;
+    let scores = Matrix::from((&device, (3, 4), to_f32(&SCORES)));
+    let classes = Matrix::from((&device, (3, 1), CLASSES));
+    let q = Matrix::from((&device, (2, 3), to_f32(&Q)));
+
+    let cpu = CPU::new();
+    let cpu_scores = Matrix::from((&cpu, (3, 4), to_f32(&SCORES)));
+    let cpu_classes = Matrix::from((&cpu, (3, 1), CLASSES));
+    let cpu_q = Matrix::from((&cpu, (2, 3), to_f32(&Q)));
+
+    let log_p = Matrix::from((&device, (2, 3), to_f32(&SCORES[..6])));
+    let cpu_log_p = Matrix::from((&cpu, (2, 3), to_f32(&SCORES[..6])));
+
+    // OpenCL is not required to round ln correctly
+    let (loss, grad) = log_p.kl_div(&q);
+    let (expected_loss, expected_grad) = cpu_log_p.kl_div(&cpu_q);
+    assert!((loss - expected_loss).abs() < 1e-5);
+    assert_eq!(grad.read(), expected_grad.read());
+
+    let (loss, grad) = scores.nll(&classes);
+    let (expected_loss, expected_grad) = cpu_scores.nll(&cpu_classes);
+    assert_eq!(loss, expected_loss);
+    assert_eq!(grad.read(), expected_grad.read());
+
+    let (loss, grad) = scores.hinge(&classes);
+    let (expected_loss, expected_grad) = cpu_scores.hinge(&cpu_classes);
+    assert_eq!(loss, expected_loss);
+    assert_eq!(grad.read(), expected_grad.read());
+
+    let (loss, grad) = scores.squared_hinge(&classes);
+    let (expected_loss, expected_grad) = cpu_scores.squared_hinge(&cpu_classes);
+    assert_eq!(loss, expected_loss);
+    assert_eq!(grad.read(), expected_grad.read());
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use custos::CPU;
+use custos_math::{ConvConfig, ConvOps, Matrix};
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_correlate_valid_full() {
+    let device = CPU::new();
+
+    let x = Matrix::from((
+        &device,
+        (4, 4),
+        [
+            1., 2., 3., 4., 5., 6., 7., 8., 9., 1., 2., 3., 4., 5., 6., 7.,
+        ],
+    ));
+    let kernel = Matrix::from((&device, (3, 3), [1., 2., 3., 4., 5., 6., 7., 8., 9.]));
+
+    let out = x.correlate_valid(&kernel);
+    assert_eq!(out.dims(), (2, 2));
+    assert_eq!(out.read(), vec![195., 177., 213., 222.]);
+
+    let out = device.correlate_full(&x, &kernel);
+    assert_eq!(out.dims(), (6, 6));
+    assert_eq!(
+        out.read(),
+        vec![
+            9., 26., 50., 74., 53., 28., 51., 111., 178., 217., 145., 72., 114., 150., 195., 177.,
+            117., 57., 105., 156., 213., 222., 144., 69., 51., 71., 94., 106., 67., 31., 12., 23.,
+            32., 38., 20., 7.
+        ]
+    );
+
+    let out = x.convolve(&kernel, ConvConfig::default());
+    assert_eq!(out.read(), vec![165., 183., 237., 228.]);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_correlate_config() {
+    let device = CPU::new();
+
+    let x = Matrix::from((
+        &device,
+        (4, 4),
+        [
+            1., 2., 3., 4., 5., 6., 7., 8., 9., 1., 2., 3., 4., 5., 6., 7.,
+        ],
+    ));
+    let kernel = Matrix::from((&device, (2, 2), [1., 0., 0., -1.]));
+
+    let config = ConvConfig::new((2, 2), (1, 1), (1, 1));
+    assert_eq!(config.out_dims(x.dims(), kernel.dims()), (3, 3));
+
+    let out = x.correlate(&kernel, config);
+    assert_eq!(out.read(), vec![-1., -3., 0., -9., 4., 8., 0., 5., 7.]);
+
+    let config = ConvConfig::new((1, 1), (0, 0), (2, 2));
+    let out = device.correlate(&x, &kernel, config);
+    assert_eq!(out.dims(), (2, 2));
+    assert_eq!(out.read(), vec![-1., -1., -1., -1.]);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+#[should_panic]
+fn test_correlate_zero_stride() {
+    let device = CPU::new();
+
+    let x = Matrix::from((&device, (2, 2), [1., 2., 3., 4.]));
+    let kernel = Matrix::from((&device, (1, 1), [1.]));
+
+    let config = ConvConfig {
+        stride: (0, 1),
+        ..Default::default()
+    };
+    x.correlate(&kernel, config);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_correlate_grads() {
+    let device = CPU::new();
+
+    let x = Matrix::from((
+        &device,
+        (4, 4),
+        [
+            1., 2., 3., 4., 5., 6., 7., 8., 9., 1., 2., 3., 4., 5., 6., 7.,
+        ],
+    ));
+    let kernel = Matrix::from((&device, (3, 3), [1., 2., 3., 4., 5., 6., 7., 8., 9.]));
+    let grad = Matrix::from((&device, (2, 2), [1., 2., 3., 4.]));
+
+    let config = ConvConfig::default();
+
+    let kernel_grad = device.correlate_grad_kernel(&x, &grad, kernel.dims(), config);
+    assert_eq!(kernel_grad.dims(), (3, 3));
+    assert_eq!(
+        kernel_grad.read(),
+        vec![44., 54., 64., 48., 31., 41., 43., 44., 54.]
+    );
+
+    let input_grad = device.correlate_grad_input(&grad, &kernel, x.dims(), config);
+    assert_eq!(input_grad.dims(), (4, 4));
+    assert_eq!(
+        input_grad.read(),
+        vec![1., 4., 7., 6., 7., 23., 33., 24., 19., 53., 63., 42., 21., 52., 59., 36.]
+    );
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_correlate_cl() -> custos::Result<()> {
+    let device = custos::OpenCL::new(0)?;
+
+    let x = Matrix::from((
+        &device,
+        (4, 4),
+        [
+            1f32, 2., 3., 4., 5., 6., 7., 8., 9., 1., 2., 3., 4., 5., 6., 7.,
+        ],
+    ));
+    let kernel = Matrix::from((&device, (3, 3), [1f32, 2., 3., 4., 5., 6., 7., 8., 9.]));
+
+    let out = x.correlate_valid(&kernel);
+    assert_eq!(out.read(), vec![195., 177., 213., 222.]);
+
+    let out = device.correlate_full(&x, &kernel);
+    assert_eq!(out.dims(), (6, 6));
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use custos::CPU;
+use custos_math::{nn::DropoutOps, Matrix};
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_dropout_cpu() {
+    let device = CPU::new();
+
+    let x = Matrix::from((&device, (10, 100), vec![3f32; 1000]));
+
+    let (out, mask) = device.dropout(&x, 0.25, 7);
+
+    let dropped = mask.iter().filter(|mask| **mask == 0.).count();
+    assert!(dropped > 150 && dropped < 350, "dropped: {dropped}");
+
+    for ((out, mask), x) in out.iter().zip(mask.iter()).zip(x.iter()) {
+        assert!(*mask == 0. || (*mask - 1. / 0.75).abs() < 1e-6);
+        assert_eq!(*out, *x * *mask);
+    }
+
+    // reproducible for the same seed
+    let (same_out, same_mask) = x.dropout(0.25, 7);
+    assert_eq!(out.read(), same_out.read());
+    assert_eq!(mask.read(), same_mask.read());
+
+    let (_, other_mask) = x.dropout(0.25, 8);
+    assert_ne!(mask.read(), other_mask.read());
+
+    let grads = Matrix::from((&device, (10, 100), vec![2f32; 1000]));
+    let grad = grads.dropout_grad(&mask);
+
+    for (grad, mask) in grad.iter().zip(mask.iter()) {
+        assert_eq!(*grad, 2. * *mask);
+    }
+
+    let (out, mask) = x.dropout(0., 1);
+    assert_eq!(out.read(), x.read());
+    assert!(mask.iter().all(|mask| *mask == 1.));
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+#[should_panic]
+fn test_dropout_p_one() {
+    let device = CPU::new();
+
+    let x = Matrix::from((&device, (1, 3), [1., 2., 3.]));
+    x.dropout(1., 0);
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_dropout_stack() {
+    use custos::{Buffer, Dim1, Stack};
+
+    let data = Buffer::<_, _, Dim1<8>>::from((&Stack, [1., 2., 3., 4., 5., 6., 7., 8.]));
+    let x = Matrix { data, dims: (2, 4) };
+
+    let (out, mask) = Stack.dropout(&x, 0.5, 3);
+
+    let device = CPU::new();
+    let cpu_x = Matrix::from((&device, (2, 4), [1., 2., 3., 4., 5., 6., 7., 8.]));
+    let (cpu_out, cpu_mask) = device.dropout(&cpu_x, 0.5, 3);
+
+    assert_eq!(out.as_slice(), &*cpu_out);
+    assert_eq!(mask.as_slice(), &*cpu_mask);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_dropout_cl() -> custos::Result<()> {
+    let device = custos::OpenCL::new(0)?;
+    let cpu = CPU::new();
+
+    let data = (0..64).map(|x| x as f32).collect::<Vec<_>>();
+
+    let x = Matrix::from((&device, (8, 8), data.clone()));
+    let cpu_x = Matrix::from((&cpu, (8, 8), data));
+
+    let (out, mask) = device.dropout(&x, 0.3, 1234);
+    let (cpu_out, cpu_mask) = cpu.dropout(&cpu_x, 0.3, 1234);
+
+    assert_eq!(mask.read(), cpu_mask.read());
+    assert_eq!(out.read(), cpu_out.read());
+
+    let grads = Matrix::from((&device, (8, 8), vec![0.5f32; 64]));
+    let cpu_grads = Matrix::from((&cpu, (8, 8), vec![0.5f32; 64]));
+
+    assert_eq!(
+        device.dropout_grad(&grads, &mask).read(),
+        cpu.dropout_grad(&cpu_grads, &cpu_mask).read()
+    );
+    Ok(())
+}
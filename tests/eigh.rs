@@ -0,0 +1,116 @@
+use custos::CPU;
+use custos_math::{LinalgError, Matrix};
+
+#[cfg(feature = "cpu")]
+pub fn roughly_equals(lhs: &[f64], rhs: &[f64], diff: f64) {
+    assert_eq!(lhs.len(), rhs.len());
+    for (a, b) in lhs.iter().zip(rhs) {
+        let abs = (*a - *b).abs();
+        if abs > diff {
+            panic!(
+                "\n left: '{:?}',\n right: '{:?}', \n left elem.: {} != right elem. {}",
+                lhs, rhs, a, b
+            )
+        }
+    }
+}
+
+const SYM: [f64; 16] = [
+    4., 1., -2., 2., 1., 2., 0., 1., -2., 0., 3., -2., 2., 1., -2., -1.,
+];
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_eigh_cpu() -> Result<(), LinalgError> {
+    let device = CPU::new();
+
+    let a = Matrix::from((&device, (3, 3), [2., -1., 0., -1., 2., -1., 0., -1., 2.]));
+    let (w, v): (Matrix, Matrix) = a.eigh()?;
+    let sqrt2 = 2f64.sqrt();
+    roughly_equals(&w, &[2. - sqrt2, 2., 2. + sqrt2], 1e-12);
+
+    // the eigenvector of 2 is (1, 0, -1) / sqrt(2), up to the sign
+    roughly_equals(&[v[4], v[1].abs()], &[0., 0.5f64.sqrt()], 1e-12);
+    roughly_equals(&[v[1] + v[7]], &[0.], 1e-12);
+
+    let a = Matrix::from((&device, (4, 4), SYM));
+    let (w, v): (Matrix, Matrix) = a.eigh()?;
+    assert_eq!(w.dims(), (1, 4));
+    assert_eq!(v.dims(), (4, 4));
+    assert!(w.windows(2).all(|pair| pair[0] <= pair[1]));
+
+    // a * v = v * diag(w)
+    let av: Matrix = a.gemm(&v);
+    let scaled = (0..16).map(|idx| v[idx] * w[idx % 4]).collect::<Vec<_>>();
+    roughly_equals(&av, &scaled, 1e-12);
+
+    let vtv: Matrix = v.T::<()>().gemm(&v);
+    roughly_equals(
+        &vtv,
+        &[
+            1., 0., 0., 0., 0., 1., 0., 0., 0., 0., 1., 0., 0., 0., 0., 1.,
+        ],
+        1e-12,
+    );
+    Ok(())
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_eigh_lower_triangle_cpu() -> Result<(), LinalgError> {
+    let device = CPU::new();
+
+    // the upper triangle is ignored
+    let a = Matrix::from((&device, (2, 2), [2., 100., 1., 2.]));
+    let (w, _v): (Matrix, Matrix) = a.eigh()?;
+    roughly_equals(&w, &[1., 3.], 1e-12);
+
+    let diag = Matrix::from((&device, (3, 3), [3., 0., 0., 0., -1., 0., 0., 0., 2.]));
+    let (w, v): (Matrix, Matrix) = diag.eigh()?;
+    assert_eq!(w.read(), vec![-1., 2., 3.]);
+    assert_eq!(v.read(), vec![0., 0., 1., 1., 0., 0., 0., 1., 0.]);
+
+    let rect = Matrix::from((&device, (2, 3), [1., 2., 3., 4., 5., 6.]));
+    assert!(matches!(
+        rect.eigh::<()>(),
+        Err(LinalgError::NotSquare { rows: 2, cols: 3 })
+    ));
+    Ok(())
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_eigh_stack() -> Result<(), LinalgError> {
+    use custos::{Buffer, Dim1, Stack};
+    use custos_math::EighOps;
+
+    let a = Matrix {
+        data: Buffer::<_, _, Dim1<16>>::from((&Stack, SYM)),
+        dims: (4, 4),
+    };
+
+    let (w, v): (Matrix<_, _, Dim1<4>>, _) = Stack.eigh(&a)?;
+
+    let device = CPU::new();
+    let (cpu_w, cpu_v): (Matrix, Matrix) = Matrix::from((&device, (4, 4), SYM)).eigh()?;
+    assert_eq!(w.as_slice(), &*cpu_w);
+    assert_eq!(v.as_slice(), &*cpu_v);
+    Ok(())
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_eigh_cl() -> custos::Result<()> {
+    use custos::OpenCL;
+
+    let sym = SYM.map(|x| x as f32);
+
+    let device = OpenCL::new(0)?;
+    let (w, v): (Matrix<f32, _>, Matrix<f32, _>) = Matrix::from((&device, (4, 4), sym)).eigh()?;
+
+    let cpu = CPU::new();
+    let (cpu_w, cpu_v): (Matrix<f32>, Matrix<f32>) = Matrix::from((&cpu, (4, 4), sym)).eigh()?;
+    assert_eq!(w.read(), cpu_w.read());
+    assert_eq!(v.read(), cpu_v.read());
+    Ok(())
+}
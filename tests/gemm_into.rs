@@ -0,0 +1,50 @@
+use custos::CPU;
+use custos_math::{GemmInto, Matrix};
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_gemm_acc() {
+    let device = CPU::new();
+
+    let a = Matrix::from((&device, (2, 3), [1., 2., 3., 4., 5., 6.]));
+    let b = Matrix::from((&device, (3, 2), [6., 5., 4., 3., 2., 1.]));
+    let mut grad = Matrix::from((&device, (2, 2), [0.; 4]));
+
+    for _ in 0..3 {
+        device.gemm_acc(1., &a, &b, 1., &mut grad);
+    }
+    assert_eq!(grad.read(), vec![60., 42., 168., 123.]);
+
+    grad.gemm_acc(-1., &a, &b, 0.5);
+    assert_eq!(grad.read(), vec![10., 7., 28., 20.5]);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_gemm_acc_beta_zero_ignores_out() {
+    let device = CPU::new();
+
+    let a = Matrix::from((&device, (2, 3), [1., 2., 3., 4., 5., 6.]));
+    let b = Matrix::from((&device, (3, 1), [1., 0., -1.]));
+    let mut out = Matrix::from((&device, (2, 1), [f64::NAN, f64::NAN]));
+
+    device.gemm_acc(2., &a, &b, 0., &mut out);
+    assert_eq!(out.read(), vec![-4., -4.]);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_gemm_acc_cl() -> custos::Result<()> {
+    let device = custos::OpenCL::new(0)?;
+
+    let a = Matrix::from((&device, (2, 3), [1f32, 2., 3., 4., 5., 6.]));
+    let b = Matrix::from((&device, (3, 2), [6f32, 5., 4., 3., 2., 1.]));
+    let mut grad = Matrix::from((&device, (2, 2), [1f32; 4]));
+
+    device.gemm_acc(2., &a, &b, 1., &mut grad);
+    assert_eq!(grad.read(), vec![41., 29., 113., 83.]);
+
+    grad.gemm_acc(1., &a, &b, 0.);
+    assert_eq!(grad.read(), vec![20., 14., 56., 41.]);
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use std::time::Instant;
 
 use custos::{range, Cache, GenericBlas, CPU};
-use custos_math::Matrix;
+use custos_math::{GemmTrans, Matrix};
 
 #[cfg(feature = "blas")]
 #[test]
@@ -57,6 +57,73 @@ fn test_gemm_trans_perf() {
     assert_eq!(out_t.as_slice(), out.as_slice());
 }
 
+#[cfg(feature = "cpu")]
+#[test]
+fn test_gemm_trans_ops() {
+    let device = CPU::new();
+
+    // 4 x 3
+    let a = Matrix::<f32>::from((
+        &device,
+        4,
+        3,
+        [1., 4., 6., 3., 1., 7., 9., 4., 1., 5., 4., 3.],
+    ));
+    // 4 x 2
+    let b = Matrix::<f32>::from((&device, 4, 2, [2., 1., 0., 3., 5., 2., 1., 1.]));
+    // 2 x 3
+    let c = Matrix::<f32>::from((&device, 2, 3, [1., 2., 3., 4., 5., 6.]));
+    // 2 x 4
+    let d = Matrix::<f32>::from((&device, 2, 4, [1., 2., 3., 4., 5., 6., 7., 8.]));
+
+    let out: Matrix = a.gemm_tn(&b);
+    let expected: Matrix = a.T::<()>().gemm(&b);
+    assert_eq!(out.dims(), (3, 2));
+    assert_eq!(out.read(), expected.read());
+
+    let out: Matrix = device.gemm_nt(&a, &c);
+    let expected: Matrix = a.gemm(&c.T::<()>());
+    assert_eq!(out.dims(), (4, 2));
+    assert_eq!(out.read(), expected.read());
+
+    let out: Matrix = a.gemm_tt(&d);
+    let expected: Matrix = a.T::<()>().gemm(&d.T::<()>());
+    assert_eq!(out.dims(), (3, 2));
+    assert_eq!(out.read(), vec![54., 126., 34., 86., 35., 103.]);
+    assert_eq!(out.read(), expected.read());
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_gemm_trans_cl() -> custos::Result<()> {
+    let device = custos::OpenCL::new(0)?;
+
+    let a = Matrix::<f32, _>::from((
+        &device,
+        4,
+        3,
+        [1., 4., 6., 3., 1., 7., 9., 4., 1., 5., 4., 3.],
+    ));
+    let b = Matrix::<f32, _>::from((&device, 4, 2, [2., 1., 0., 3., 5., 2., 1., 1.]));
+    let c = Matrix::<f32, _>::from((&device, 2, 3, [1., 2., 3., 4., 5., 6.]));
+    let d = Matrix::<f32, _>::from((&device, 2, 4, [1., 2., 3., 4., 5., 6., 7., 8.]));
+
+    let out = device.gemm_tn(&a, &b);
+    let expected: Matrix<f32, _> = a.T::<()>().gemm(&b);
+    assert_eq!(out.dims(), (3, 2));
+    assert_eq!(out.read(), expected.read());
+
+    let out = device.gemm_nt(&a, &c);
+    let expected: Matrix<f32, _> = a.gemm(&c.T::<()>());
+    assert_eq!(out.dims(), (4, 2));
+    assert_eq!(out.read(), expected.read());
+
+    let out = device.gemm_tt(&a, &d);
+    assert_eq!(out.dims(), (3, 2));
+    assert_eq!(out.read(), vec![54., 126., 34., 86., 35., 103.]);
+    Ok(())
+}
+
 // TODO: does not work
 /*#[test]
 fn test_trans_gemm() {
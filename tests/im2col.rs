@@ -0,0 +1,115 @@
+use custos::CPU;
+use custos_math::{ConvConfig, Im2ColOps, Matrix};
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_im2col_cpu() {
+    let device = CPU::new();
+
+    let x = Matrix::from((&device, (1, 9), [1., 2., 3., 4., 5., 6., 7., 8., 9.]));
+    let cols = device.im2col(&x, (3, 3), (2, 2), ConvConfig::default());
+
+    assert_eq!(cols.dims(), (4, 4));
+    assert_eq!(
+        cols.read(),
+        vec![1., 2., 4., 5., 2., 3., 5., 6., 4., 5., 7., 8., 5., 6., 8., 9.]
+    );
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_im2col_gemm_channels() {
+    let device = CPU::new();
+
+    let x = Matrix::from((
+        &device,
+        (2, 9),
+        [
+            1., 2., 3., 4., 5., 6., 7., 8., 9., 9., 8., 7., 6., 5., 4., 3., 2., 1.,
+        ],
+    ));
+    let kernels = Matrix::from((&device, (1, 8), [1., 2., 3., 4., -1., 0., 0., 1.]));
+
+    let cols = x.im2col((3, 3), (2, 2), ConvConfig::default());
+    assert_eq!(cols.dims(), (8, 4));
+    assert_eq!(kernels.gemm(&cols).read(), vec![33., 43., 63., 73.]);
+
+    let config = ConvConfig::new((2, 2), (1, 1), (1, 1));
+    let cols = x.im2col((3, 3), (2, 2), config);
+    assert_eq!(cols.dims(), (8, 4));
+    assert_eq!(kernels.gemm(&cols).read(), vec![13., 25., 39., 73.]);
+
+    // compare with the single channel correlation
+    let channel = Matrix::from((&device, (3, 3), [1., 2., 3., 4., 5., 6., 7., 8., 9.]));
+    let kernel = Matrix::from((&device, (2, 2), [1., 2., 3., 4.]));
+    let kernel_row = Matrix::from((&device, (1, 4), [1., 2., 3., 4.]));
+
+    let cols = device.im2col(
+        &Matrix::from((&device, (1, 9), channel.read())),
+        (3, 3),
+        (2, 2),
+        config,
+    );
+    assert_eq!(
+        kernel_row.gemm(&cols).read(),
+        channel.correlate(&kernel, config).read()
+    );
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_col2im_cpu() {
+    let device = CPU::new();
+
+    let cols = Matrix::from((&device, (4, 4), [1.; 16]));
+    let img = device.col2im(&cols, (3, 3), (2, 2), ConvConfig::default());
+
+    assert_eq!(img.dims(), (1, 9));
+    assert_eq!(img.read(), vec![1., 2., 1., 2., 4., 2., 1., 2., 1.]);
+
+    // non overlapping patches are restored exactly
+    let x = Matrix::from((
+        &device,
+        (2, 16),
+        [
+            1., 2., 3., 4., 5., 6., 7., 8., 9., 10., 11., 12., 13., 14., 15., 16., 16., 15., 14.,
+            13., 12., 11., 10., 9., 8., 7., 6., 5., 4., 3., 2., 1.,
+        ],
+    ));
+    let config = ConvConfig::new((2, 2), (0, 0), (1, 1));
+    let cols = x.im2col((4, 4), (2, 2), config);
+    let img = cols.col2im((4, 4), (2, 2), config);
+    assert_eq!(img.dims(), (2, 16));
+    assert_eq!(img.read(), x.read());
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_im2col_cl() -> custos::Result<()> {
+    let cpu = CPU::new();
+    let device = custos::OpenCL::new(0)?;
+
+    let data = [
+        1f32, 2., 3., 4., 5., 6., 7., 8., 9., 10., 11., 12., 13., 14., 15., 16., 16., 15., 14.,
+        13., 12., 11., 10., 9., 8., 7., 6., 5., 4., 3., 2., 1.,
+    ];
+    let x = Matrix::from((&device, (2, 16), data));
+    let cpu_x = Matrix::from((&cpu, (2, 16), data));
+
+    for config in [
+        ConvConfig::default(),
+        ConvConfig::new((2, 2), (1, 1), (1, 1)),
+        ConvConfig::new((1, 2), (2, 1), (2, 1)),
+    ] {
+        let cols = x.im2col((4, 4), (3, 2), config);
+        let cpu_cols = cpu_x.im2col((4, 4), (3, 2), config);
+        assert_eq!(cols.dims(), cpu_cols.dims());
+        assert_eq!(cols.read(), cpu_cols.read());
+
+        let img = cols.col2im((4, 4), (3, 2), config);
+        let cpu_img = cpu_cols.col2im((4, 4), (3, 2), config);
+        assert_eq!(img.dims(), (2, 16));
+        assert_eq!(img.read(), cpu_img.read());
+    }
+    Ok(())
+}
@@ -0,0 +1,236 @@
+use custos::CPU;
+use custos_math::{nn::LayerNormOps, Matrix};
+
+#[cfg(feature = "cpu")]
+pub fn roughly_equals(lhs: &[f64], rhs: &[f64], diff: f64) {
+    for (a, b) in lhs.iter().zip(rhs) {
+        let abs = (*a - *b).abs();
+        if abs > diff {
+            panic!(
+                "\n left: '{:?}',\n right: '{:?}', \n left elem.: {} != right elem. {}",
+                lhs, rhs, a, b
+            )
+        }
+    }
+}
+
+const X: [f64; 12] = [1., 2., 3., 4., -1., 0.5, 2., 0., -2., 3., 1., 1.5];
+const GRADS: [f64; 12] = [
+    0.3, -1.2, 0.7, 2., 0.1, -0.4, -0.9, 1.5, 0.2, 0.6, -0.3, 1.1,
+];
+const GAMMA: [f64; 3] = [0.5, 1., 2.];
+const BETA: [f64; 3] = [0.1, -0.2, 0.3];
+const EPS: f64 = 1e-5;
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_layer_norm_cpu() {
+    let device = CPU::new();
+
+    let x = Matrix::from((&device, (4, 3), X));
+    let gamma = Matrix::from((&device, (1, 3), GAMMA));
+    let beta = Matrix::from((&device, (1, 3), BETA));
+
+    let out = x.layer_norm(&gamma, &beta, EPS);
+    roughly_equals(
+        &out,
+        &[
+            -0.512368, -0.2, 2.749471, 0.776223, -1.234223, -0.336445, 0.712371, -0.2, -2.149485,
+            0.786402, -1.180574, -0.484459,
+        ],
+        1e-6,
+    );
+
+    let out = x.rms_norm(&gamma, EPS);
+    roughly_equals(
+        &out,
+        &[
+            0.231455, 0.925819, 2.777457, 0.834057, -0.417028, 0.417028, 0.612371, 0., -2.449485,
+            0.742307, 0.494871, 1.484613,
+        ],
+        1e-6,
+    );
+}
+
+#[cfg(feature = "cpu")]
+fn numeric_grad(values: &[f64], loss: impl Fn(&[f64]) -> f64) -> Vec<f64> {
+    let h = 1e-6;
+    (0..values.len())
+        .map(|idx| {
+            let mut plus = values.to_vec();
+            plus[idx] += h;
+            let mut minus = values.to_vec();
+            minus[idx] -= h;
+            (loss(&plus) - loss(&minus)) / (2. * h)
+        })
+        .collect()
+}
+
+/// The loss is `sum(out * GRADS)`, hence the output gradients are `GRADS`.
+#[cfg(feature = "cpu")]
+fn weighted_loss(out: Matrix<f64>) -> f64 {
+    out.iter().zip(GRADS).map(|(out, grad)| out * grad).sum()
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_layer_norm_grad_cpu() {
+    let device = CPU::new();
+
+    let layer_norm = |x: &[f64], gamma: &[f64], beta: &[f64]| {
+        let x = Matrix::from((&device, (4, 3), x.to_vec()));
+        let gamma = Matrix::from((&device, (1, 3), gamma.to_vec()));
+        let beta = Matrix::from((&device, (1, 3), beta.to_vec()));
+        weighted_loss(x.layer_norm(&gamma, &beta, EPS))
+    };
+
+    let x = Matrix::from((&device, (4, 3), X));
+    let gamma = Matrix::from((&device, (1, 3), GAMMA));
+    let grads = Matrix::from((&device, (4, 3), GRADS));
+
+    let (dx, dgamma, dbeta) = grads.layer_norm_grad(&x, &gamma, EPS);
+
+    roughly_equals(
+        &dx,
+        &numeric_grad(&X, |x| layer_norm(x, &GAMMA, &BETA)),
+        1e-6,
+    );
+    roughly_equals(
+        &dgamma,
+        &numeric_grad(&GAMMA, |gamma| layer_norm(&X, gamma, &BETA)),
+        1e-6,
+    );
+    roughly_equals(
+        &dbeta,
+        &numeric_grad(&BETA, |beta| layer_norm(&X, &GAMMA, beta)),
+        1e-6,
+    );
+
+    // the normalisation removes the mean, hence the gradients of every row sum up to zero
+    roughly_equals(&dx.sum_cols::<()>(), &[0.; 4], 1e-12);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_rms_norm_grad_cpu() {
+    let device = CPU::new();
+
+    let rms_norm = |x: &[f64], gamma: &[f64]| {
+        let x = Matrix::from((&device, (4, 3), x.to_vec()));
+        let gamma = Matrix::from((&device, (1, 3), gamma.to_vec()));
+        weighted_loss(x.rms_norm(&gamma, EPS))
+    };
+
+    let x = Matrix::from((&device, (4, 3), X));
+    let gamma = Matrix::from((&device, (1, 3), GAMMA));
+    let grads = Matrix::from((&device, (4, 3), GRADS));
+
+    let (dx, dgamma) = device.rms_norm_grad(&x, &grads, &gamma, EPS);
+
+    roughly_equals(&dx, &numeric_grad(&X, |x| rms_norm(x, &GAMMA)), 1e-6);
+    roughly_equals(
+        &dgamma,
+        &numeric_grad(&GAMMA, |gamma| rms_norm(&X, gamma)),
+        1e-6,
+    );
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_layer_norm_stack() {
+    use custos::{Buffer, Dim1, Stack};
+
+    let x = Matrix {
+        data: Buffer::<_, _, Dim1<12>>::from((&Stack, X)),
+        dims: (4, 3),
+    };
+    let grads = Matrix {
+        data: Buffer::<_, _, Dim1<12>>::from((&Stack, GRADS)),
+        dims: (4, 3),
+    };
+    let gamma = Matrix {
+        data: Buffer::<_, _, Dim1<3>>::from((&Stack, GAMMA)),
+        dims: (1, 3),
+    };
+    let beta = Matrix {
+        data: Buffer::<_, _, Dim1<3>>::from((&Stack, BETA)),
+        dims: (1, 3),
+    };
+
+    let device = CPU::new();
+    let cpu_x = Matrix::from((&device, (4, 3), X));
+    let cpu_grads = Matrix::from((&device, (4, 3), GRADS));
+    let cpu_gamma = Matrix::from((&device, (1, 3), GAMMA));
+    let cpu_beta = Matrix::from((&device, (1, 3), BETA));
+
+    let out = Stack.layer_norm(&x, &gamma, &beta, EPS);
+    assert_eq!(
+        out.as_slice(),
+        &*cpu_x.layer_norm(&cpu_gamma, &cpu_beta, EPS)
+    );
+
+    let (dx, dgamma, dbeta) = Stack.layer_norm_grad(&x, &grads, &gamma, EPS);
+    let (expected_dx, expected_dgamma, expected_dbeta) =
+        cpu_grads.layer_norm_grad(&cpu_x, &cpu_gamma, EPS);
+    assert_eq!(dx.as_slice(), &*expected_dx);
+    assert_eq!(dgamma.as_slice(), &*expected_dgamma);
+    assert_eq!(dbeta.as_slice(), &*expected_dbeta);
+
+    let out = Stack.rms_norm(&x, &gamma, EPS);
+    assert_eq!(out.as_slice(), &*cpu_x.rms_norm(&cpu_gamma, EPS));
+
+    let (dx, dgamma) = Stack.rms_norm_grad(&x, &grads, &gamma, EPS);
+    let (expected_dx, expected_dgamma) = cpu_grads.rms_norm_grad(&cpu_x, &cpu_gamma, EPS);
+    assert_eq!(dx.as_slice(), &*expected_dx);
+    assert_eq!(dgamma.as_slice(), &*expected_dgamma);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_layer_norm_cl() -> custos::Result<()> {
+    use custos::OpenCL;
+
+    let to_f32 = |values: &[f64]| values.iter().map(|x| *x as f32).collect::<Vec<_>>();
+
+    let device = OpenCL::new(0)?;
+    let x = Matrix::from((&device, (4, 3), to_f32(&X)));
+    let grads = Matrix::from((&device, (4, 3), to_f32(&GRADS)));
+    let gamma = Matrix::from((&device, (1, 3), to_f32(&GAMMA)));
+    let beta = Matrix::from((&device, (1, 3), to_f32(&BETA)));
+
+    let cpu = CPU::new();
+    let cpu_x = Matrix::from((&cpu, (4, 3), to_f32(&X)));
+    let cpu_grads = Matrix::from((&cpu, (4, 3), to_f32(&GRADS)));
+    let cpu_gamma = Matrix::from((&cpu, (1, 3), to_f32(&GAMMA)));
+    let cpu_beta = Matrix::from((&cpu, (1, 3), to_f32(&BETA)));
+
+    // OpenCL is not required to round divisions and square roots correctly
+    let roughly_equals = |lhs: Vec<f32>, rhs: Vec<f32>| {
+        for (a, b) in lhs.iter().zip(&rhs) {
+            assert!((a - b).abs() < 1e-5, "{lhs:?} != {rhs:?}");
+        }
+    };
+
+    roughly_equals(
+        x.layer_norm(&gamma, &beta, 1e-5).read(),
+        cpu_x.layer_norm(&cpu_gamma, &cpu_beta, 1e-5).read(),
+    );
+
+    let (dx, dgamma, dbeta) = grads.layer_norm_grad(&x, &gamma, 1e-5);
+    let (expected_dx, expected_dgamma, expected_dbeta) =
+        cpu_grads.layer_norm_grad(&cpu_x, &cpu_gamma, 1e-5);
+    roughly_equals(dx.read(), expected_dx.read());
+    roughly_equals(dgamma.read(), expected_dgamma.read());
+    roughly_equals(dbeta.read(), expected_dbeta.read());
+
+    roughly_equals(
+        x.rms_norm(&gamma, 1e-5).read(),
+        cpu_x.rms_norm(&cpu_gamma, 1e-5).read(),
+    );
+
+    let (dx, dgamma) = grads.rms_norm_grad(&x, &gamma, 1e-5);
+    let (expected_dx, expected_dgamma) = cpu_grads.rms_norm_grad(&cpu_x, &cpu_gamma, 1e-5);
+    roughly_equals(dx.read(), expected_dx.read());
+    roughly_equals(dgamma.read(), expected_dgamma.read());
+    Ok(())
+}
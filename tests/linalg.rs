@@ -0,0 +1,187 @@
+use custos::CPU;
+use custos_math::{LinalgError, Matrix};
+
+#[cfg(feature = "cpu")]
+pub fn roughly_equals(lhs: &[f64], rhs: &[f64], diff: f64) {
+    for (a, b) in lhs.iter().zip(rhs) {
+        let abs = (*a - *b).abs();
+        if abs > diff {
+            panic!(
+                "\n left: '{:?}',\n right: '{:?}', \n left elem.: {} != right elem. {}",
+                lhs, rhs, a, b
+            )
+        }
+    }
+}
+
+const A: [f64; 9] = [2., 1., 1., 4., -6., 0., -2., 7., 2.];
+const B: [f64; 6] = [5., 1., -2., 0., 9., 3.];
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_lu_cpu() -> Result<(), LinalgError> {
+    let device = CPU::new();
+
+    let a = Matrix::from((&device, (3, 3), A));
+    let (p, l, u) = a.lu()?;
+
+    assert_eq!(p.read(), vec![0., 1., 0., 1., 0., 0., 0., 0., 1.]);
+    assert_eq!(l.read(), vec![1., 0., 0., 0.5, 1., 0., -0.5, 1., 1.]);
+    assert_eq!(u.read(), vec![4., -6., 0., 0., 4., 1., 0., 0., 1.]);
+
+    let lu: Matrix = l.gemm(&u);
+    let reconstructed: Matrix = p.gemm(&lu);
+    roughly_equals(&reconstructed, &A, 1e-12);
+    Ok(())
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_solve_inv_det_cpu() -> Result<(), LinalgError> {
+    let device = CPU::new();
+
+    let a = Matrix::from((&device, (3, 3), A));
+    let b = Matrix::from((&device, (3, 2), B));
+
+    let x = a.solve(&b)?;
+    roughly_equals(&x, &[1., -0.375, 1., -0.25, 2., 2.], 1e-12);
+    let ax: Matrix = a.gemm(&x);
+    roughly_equals(&ax, &B, 1e-12);
+
+    let inv = a.inv()?;
+    roughly_equals(
+        &inv,
+        &[0.75, -0.3125, -0.375, 0.5, -0.375, -0.25, -1., 1., 1.],
+        1e-12,
+    );
+    let identity: Matrix = a.gemm(&inv);
+    roughly_equals(&identity, &[1., 0., 0., 0., 1., 0., 0., 0., 1.], 1e-12);
+
+    assert!((a.det()? + 16.).abs() < 1e-12);
+
+    let a = Matrix::from((&device, (2, 2), [0., 2., 3., 0.]));
+    assert!((a.det()? + 6.).abs() < 1e-12);
+    Ok(())
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_linalg_scaled_diagonal_cpu() -> Result<(), LinalgError> {
+    let device = CPU::new();
+
+    let a = Matrix::from((&device, (2, 2), [1e10, 0., 0., 1e-10]));
+    assert_eq!(a.det()?, 1.);
+    assert_eq!(a.inv()?.read(), vec![1e-10, 0., 0., 1e10]);
+
+    let b = Matrix::from((&device, (2, 1), [1e10, 1e-10]));
+    assert_eq!(a.solve(&b)?.read(), vec![1., 1.]);
+
+    let (_, _, u) = a.lu()?;
+    assert_eq!(u.read(), vec![1e10, 0., 0., 1e-10]);
+    Ok(())
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_linalg_errors_cpu() {
+    let device = CPU::new();
+
+    let singular = Matrix::from((&device, (3, 3), [1., 2., 3., 4., 5., 6., 7., 8., 9.]));
+    let b = Matrix::from((&device, (3, 2), B));
+
+    assert!(matches!(
+        singular.lu(),
+        Err(LinalgError::Singular { pivot: 2 })
+    ));
+    assert!(matches!(
+        singular.solve(&b),
+        Err(LinalgError::Singular { .. })
+    ));
+    assert!(matches!(singular.inv(), Err(LinalgError::Singular { .. })));
+    // the rounded last pivot is not exactly zero
+    assert!(singular.det().unwrap().abs() < 1e-12);
+
+    let singular = Matrix::from((&device, (2, 2), [1., 2., 2., 4.]));
+    assert_eq!(singular.det(), Ok(0.));
+
+    let zeros = Matrix::from((&device, (2, 2), [0.; 4]));
+    assert_eq!(zeros.inv().unwrap_err(), LinalgError::Singular { pivot: 0 });
+
+    let rect = Matrix::from((&device, (2, 3), [1., 2., 3., 4., 5., 6.]));
+    assert_eq!(rect.det(), Err(LinalgError::NotSquare { rows: 2, cols: 3 }));
+
+    let a = Matrix::from((&device, (3, 3), A));
+    let b = Matrix::from((&device, (2, 3), B));
+    assert_eq!(
+        a.solve(&b).unwrap_err(),
+        LinalgError::DimsMismatch {
+            lhs: (3, 3),
+            rhs: (2, 3)
+        }
+    );
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_linalg_stack() -> Result<(), LinalgError> {
+    use custos::{Buffer, Dim1, Stack};
+    use custos_math::{LinalgOps, SolveOp};
+
+    let a = Matrix {
+        data: Buffer::<_, _, Dim1<9>>::from((&Stack, A)),
+        dims: (3, 3),
+    };
+    let b = Matrix {
+        data: Buffer::<_, _, Dim1<6>>::from((&Stack, B)),
+        dims: (3, 2),
+    };
+
+    let device = CPU::new();
+    let cpu_a = Matrix::from((&device, (3, 3), A));
+    let cpu_b = Matrix::from((&device, (3, 2), B));
+
+    let (p, l, u) = Stack.lu(&a)?;
+    let (cpu_p, cpu_l, cpu_u) = cpu_a.lu()?;
+    assert_eq!(p.as_slice(), &*cpu_p);
+    assert_eq!(l.as_slice(), &*cpu_l);
+    assert_eq!(u.as_slice(), &*cpu_u);
+
+    let x = Stack.solve(&a, &b)?;
+    assert_eq!(x.as_slice(), &*cpu_a.solve(&cpu_b)?);
+
+    let inv = Stack.inv(&a)?;
+    assert_eq!(inv.as_slice(), &*cpu_a.inv()?);
+
+    assert_eq!(Stack.det(&a)?, cpu_a.det()?);
+    Ok(())
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_linalg_cl() -> custos::Result<()> {
+    use custos::OpenCL;
+
+    let to_f32 = |values: &[f64]| values.iter().map(|x| *x as f32).collect::<Vec<_>>();
+
+    let device = OpenCL::new(0)?;
+    let a = Matrix::from((&device, (3, 3), to_f32(&A)));
+    let b = Matrix::from((&device, (3, 2), to_f32(&B)));
+
+    let cpu = CPU::new();
+    let cpu_a = Matrix::from((&cpu, (3, 3), to_f32(&A)));
+    let cpu_b = Matrix::from((&cpu, (3, 2), to_f32(&B)));
+
+    let (p, l, u) = a.lu()?;
+    let (cpu_p, cpu_l, cpu_u) = cpu_a.lu()?;
+    assert_eq!(p.read(), cpu_p.read());
+    assert_eq!(l.read(), cpu_l.read());
+    assert_eq!(u.read(), cpu_u.read());
+
+    assert_eq!(a.solve(&b)?.read(), cpu_a.solve(&cpu_b)?.read());
+    assert_eq!(a.inv()?.read(), cpu_a.inv()?.read());
+    assert_eq!(a.det()?, cpu_a.det()?);
+
+    let singular = Matrix::from((&device, (2, 2), [1f32, 2., 2., 4.]));
+    assert!(singular.inv().is_err());
+    Ok(())
+}
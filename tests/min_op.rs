@@ -0,0 +1,63 @@
+use custos::CPU;
+use custos_math::{Matrix, MinOps};
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_min_ops() {
+    let device = CPU::new();
+    let a = Matrix::from((
+        &device,
+        (3, 3),
+        [-10., -2., -3., -4., -5., -6., -7., -8., -9.],
+    ));
+
+    let res = device.min(&a);
+    assert!(res == -10.);
+
+    let res = device.min_cols(&a);
+    assert_eq!(res.read(), vec![-10., -6., -9.]);
+
+    let res = device.min_rows(&a);
+    assert_eq!(res.read(), vec![-10., -8., -9.]);
+
+    assert!(a.min() == -10.);
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_min_ops_stack() {
+    use custos::{Buffer, Dim1, Stack};
+
+    let data = Buffer::<_, _, Dim1<6>>::from((&Stack, [3., 1., 5., 2., 7., -1.]));
+    let a = Matrix { data, dims: (2, 3) };
+
+    assert!(a.min() == -1.);
+
+    let res: Matrix<_, _, Dim1<3>> = Stack.min_rows(&a);
+    assert_eq!(res.as_slice(), &[2., 1., -1.]);
+
+    let res: Matrix<_, _, Dim1<2>> = Stack.min_cols(&a);
+    assert_eq!(res.as_slice(), &[1., -1.]);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_min_cl() -> custos::Result<()> {
+    let device = custos::OpenCL::new(0)?;
+
+    let a = Matrix::from((
+        &device,
+        (3, 3),
+        [-10f32, -2., -3., -4., -5., -6., -7., -8., -9.],
+    ));
+
+    let res = device.min(&a);
+    assert!(res == -10.);
+
+    let res = device.min_cols(&a);
+    assert_eq!(res.read(), vec![-10., -6., -9.]);
+
+    let res = device.min_rows(&a);
+    assert_eq!(res.read(), vec![-10., -8., -9.]);
+    Ok(())
+}
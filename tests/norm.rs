@@ -0,0 +1,144 @@
+use custos::CPU;
+use custos_math::{Matrix, Norm};
+
+const X: [f64; 6] = [1., -2., 3., 4., 5., -6.];
+const TALL: [f64; 12] = [1., 2., 3., 4., 5., 6., 7., 8., 10., 1., 0., 1.];
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_norms_cpu() {
+    let device = CPU::new();
+    let x = Matrix::from((&device, (2, 3), X));
+
+    assert_eq!(x.norm(Norm::L1), 9.);
+    assert_eq!(x.norm(Norm::Inf), 15.);
+    assert_eq!(x.norm(Norm::Frobenius), 91f64.sqrt());
+    assert_eq!(x.norm(Norm::Elementwise(1.)), 21.);
+    assert_eq!(x.norm(Norm::Elementwise(2.)), 91f64.sqrt());
+    assert!((x.norm(Norm::Elementwise(3.)) - 441f64.cbrt()).abs() < 1e-12);
+    assert!((x.norm(Norm::Spectral) - 9.225027715260929).abs() < 1e-12);
+
+    // the spectral norm is the largest singular value
+    let tall = Matrix::from((&device, (4, 3), TALL));
+    let (_u, sigma, _vt): (Matrix, Matrix, Matrix) = tall.svd();
+    assert!((tall.norm(Norm::Spectral) - sigma[0]).abs() < 1e-12);
+    assert!((tall.T::<()>().norm(Norm::Spectral) - sigma[0]).abs() < 1e-12);
+
+    let row = Matrix::from((&device, (1, 2), [3., -4.]));
+    assert_eq!(row.norm(Norm::Spectral), 5.);
+
+    // the columns are orthogonal, which would stall a power iteration started with the largest row
+    let orthogonal = Matrix::from((&device, (3, 2), [0., 2.1, 1.5, 0., 1.5, 0.]));
+    assert!((orthogonal.norm(Norm::Spectral) - 4.5f64.sqrt()).abs() < 1e-10);
+
+    // a start vector of ones lies in the null space of this matrix
+    let null = Matrix::from((&device, (2, 2), [1., -1., -1., 1.]));
+    assert!((null.norm(Norm::Spectral) - 2.).abs() < 1e-12);
+
+    let zeros = Matrix::from((&device, (2, 2), [0.; 4]));
+    for norm in [
+        Norm::Frobenius,
+        Norm::L1,
+        Norm::Inf,
+        Norm::Spectral,
+        Norm::Elementwise(3.),
+    ] {
+        assert_eq!(zeros.norm(norm), 0.);
+    }
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_trace_cond_cpu() {
+    let device = CPU::new();
+
+    assert_eq!(Matrix::from((&device, (2, 3), X)).trace(), 6.);
+    assert_eq!(Matrix::from((&device, (4, 3), TALL)).trace(), 16.);
+
+    let a = Matrix::from((&device, (2, 2), [1., 2., 3., 4.]));
+    assert!((a.cond() - 14.933034373659256).abs() < 1e-10);
+
+    let identity = Matrix::from((&device, (3, 3), [1., 0., 0., 0., 1., 0., 0., 0., 1.]));
+    assert_eq!(identity.cond(), 1.);
+
+    let singular = Matrix::from((&device, (2, 2), [1., 2., 2., 4.]));
+    assert_eq!(singular.cond(), f64::INFINITY);
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_norms_stack() {
+    use custos::{Buffer, Dim1, Stack};
+    use custos_math::NormOps;
+
+    let x = Matrix {
+        data: Buffer::<_, _, Dim1<12>>::from((&Stack, TALL)),
+        dims: (4, 3),
+    };
+
+    let device = CPU::new();
+    let cpu_x = Matrix::from((&device, (4, 3), TALL));
+
+    for norm in [
+        Norm::Frobenius,
+        Norm::L1,
+        Norm::Inf,
+        Norm::Spectral,
+        Norm::Elementwise(3.),
+    ] {
+        assert_eq!(Stack.norm(&x, norm), cpu_x.norm(norm));
+    }
+    assert_eq!(Stack.trace(&x), 16.);
+    assert_eq!(Stack.cond(&x), cpu_x.cond());
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_norms_cl() -> custos::Result<()> {
+    use custos::OpenCL;
+
+    let device = OpenCL::new(0)?;
+    let cpu = CPU::new();
+
+    // enough values for more work-groups than a single reduction pass handles
+    let (rows, cols) = (1000, 70);
+    let values = (0..rows * cols)
+        .map(|idx| ((idx * 7919) % 200) as f32 / 100. - 1.)
+        .collect::<Vec<_>>();
+
+    for (dims, values) in [
+        ((2, 3), X.map(|x| x as f32).to_vec()),
+        ((4, 3), TALL.map(|x| x as f32).to_vec()),
+        ((rows, cols), values),
+    ] {
+        let x = Matrix::from((&device, dims, values.clone()));
+        let cpu_x = Matrix::from((&cpu, dims, values));
+
+        for norm in [
+            Norm::Frobenius,
+            Norm::L1,
+            Norm::Inf,
+            Norm::Spectral,
+            Norm::Elementwise(1.),
+            Norm::Elementwise(3.),
+        ] {
+            let expected = cpu_x.norm(norm);
+            assert!(
+                (x.norm(norm) - expected).abs() <= 1e-4 * expected,
+                "{norm:?}: {} != {expected}",
+                x.norm(norm)
+            );
+        }
+
+        let expected = cpu_x.trace();
+        assert!((x.trace() - expected).abs() <= 1e-4 * expected.abs().max(1.));
+
+        let expected = cpu_x.cond();
+        assert!(
+            (x.cond() - expected).abs() <= 1e-3 * expected,
+            "{} != {expected}",
+            x.cond()
+        );
+    }
+    Ok(())
+}
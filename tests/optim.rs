@@ -0,0 +1,246 @@
+use custos::CPU;
+use custos_math::Matrix;
+
+#[cfg(feature = "cpu")]
+pub fn roughly_equals(lhs: &[f64], rhs: &[f64], diff: f64) {
+    for (a, b) in lhs.iter().zip(rhs) {
+        let abs = (*a - *b).abs();
+        if abs > diff {
+            panic!(
+                "\n left: '{:?}',\n right: '{:?}', \n left elem.: {} != right elem. {}",
+                lhs, rhs, a, b
+            )
+        }
+    }
+}
+
+const PARAM: [f64; 4] = [1., -2., 0.5, 3.];
+const GRAD: [f64; 4] = [0.5, -1., 0.25, -0.75];
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_sgd_momentum_cpu() {
+    let device = CPU::new();
+
+    let grad = Matrix::from((&device, (2, 2), GRAD));
+
+    let mut param = Matrix::from((&device, (2, 2), PARAM));
+    let mut velocity = Matrix::from((&device, (2, 2), [0.; 4]));
+    for _ in 0..2 {
+        param.sgd_momentum_step(&grad, &mut velocity, 0.1, 0.9, false);
+    }
+    roughly_equals(&velocity, &[0.95, -1.9, 0.475, -1.425], 1e-12);
+    roughly_equals(&param, &[0.855, -1.71, 0.4275, 3.2175], 1e-12);
+
+    let mut param = Matrix::from((&device, (2, 2), PARAM));
+    let mut velocity = Matrix::from((&device, (2, 2), [0.; 4]));
+    for _ in 0..2 {
+        param.sgd_momentum_step(&grad, &mut velocity, 0.1, 0.9, true);
+    }
+    roughly_equals(&velocity, &[0.95, -1.9, 0.475, -1.425], 1e-12);
+    roughly_equals(&param, &[0.7695, -1.539, 0.38475, 3.34575], 1e-12);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_adam_cpu() {
+    let device = CPU::new();
+
+    let grad = Matrix::from((&device, (2, 2), GRAD));
+    let mut param = Matrix::from((&device, (2, 2), PARAM));
+    let mut m = Matrix::from((&device, (2, 2), [0.; 4]));
+    let mut v = Matrix::from((&device, (2, 2), [0.; 4]));
+
+    for t in 1..=3 {
+        param.adam_step(&grad, &mut m, &mut v, 0.01, (0.9, 0.999), 1e-8, t, 0.1);
+    }
+
+    roughly_equals(
+        &param,
+        &[0.96703299, -1.96403599, 0.46853149, 3.02097901],
+        1e-8,
+    );
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+#[should_panic]
+fn test_adam_step_zero() {
+    let device = CPU::new();
+
+    let grad = Matrix::from((&device, (2, 2), GRAD));
+    let mut param = Matrix::from((&device, (2, 2), PARAM));
+    let mut m = Matrix::from((&device, (2, 2), [0.; 4]));
+    let mut v = Matrix::from((&device, (2, 2), [0.; 4]));
+
+    param.adam_step(&grad, &mut m, &mut v, 0.01, (0.9, 0.999), 1e-8, 0, 0.);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_rmsprop_adagrad_cpu() {
+    let device = CPU::new();
+
+    let grad = Matrix::from((&device, (2, 2), GRAD));
+
+    let mut param = Matrix::from((&device, (2, 2), PARAM));
+    let mut sq_avg = Matrix::from((&device, (2, 2), [0.; 4]));
+    for _ in 0..2 {
+        param.rmsprop_step(&grad, &mut sq_avg, 0.01, 0.9, 1e-8);
+    }
+    roughly_equals(
+        &param,
+        &[0.94543565, -1.94543565, 0.44543566, 3.05456435],
+        1e-8,
+    );
+
+    let mut param = Matrix::from((&device, (2, 2), PARAM));
+    let mut sq_sum = Matrix::from((&device, (2, 2), [0.; 4]));
+    for _ in 0..2 {
+        param.adagrad_step(&grad, &mut sq_sum, 0.1, 1e-10);
+    }
+    roughly_equals(&sq_sum, &[0.5, 2., 0.125, 1.125], 1e-12);
+    roughly_equals(
+        &param,
+        &[0.82928932, -1.82928932, 0.32928932, 3.17071068],
+        1e-8,
+    );
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+#[should_panic]
+fn test_optim_state_dims_mismatch() {
+    let device = CPU::new();
+
+    let grad = Matrix::from((&device, (2, 2), GRAD));
+    let mut param = Matrix::from((&device, (2, 2), PARAM));
+    let mut velocity = Matrix::from((&device, (1, 4), [0.; 4]));
+
+    param.sgd_momentum_step(&grad, &mut velocity, 0.1, 0.9, false);
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_optim_stack() {
+    use custos::{Buffer, Dim1, Stack};
+    use custos_math::nn::OptimOps;
+
+    let grad = Matrix {
+        data: Buffer::<_, _, Dim1<4>>::from((&Stack, GRAD)),
+        dims: (2, 2),
+    };
+    let mut param = Matrix {
+        data: Buffer::<_, _, Dim1<4>>::from((&Stack, PARAM)),
+        dims: (2, 2),
+    };
+    let mut m = Matrix {
+        data: Buffer::<_, _, Dim1<4>>::from((&Stack, [0.; 4])),
+        dims: (2, 2),
+    };
+    let mut v = Matrix {
+        data: Buffer::<_, _, Dim1<4>>::from((&Stack, [0.; 4])),
+        dims: (2, 2),
+    };
+
+    let device = CPU::new();
+    let cpu_grad = Matrix::from((&device, (2, 2), GRAD));
+    let mut cpu_param = Matrix::from((&device, (2, 2), PARAM));
+    let mut cpu_m = Matrix::from((&device, (2, 2), [0.; 4]));
+    let mut cpu_v = Matrix::from((&device, (2, 2), [0.; 4]));
+
+    for t in 1..=3 {
+        Stack.adam_step(
+            &mut param,
+            &grad,
+            &mut m,
+            &mut v,
+            0.01,
+            (0.9, 0.999),
+            1e-8,
+            t,
+            0.1,
+        );
+        cpu_param.adam_step(
+            &cpu_grad,
+            &mut cpu_m,
+            &mut cpu_v,
+            0.01,
+            (0.9, 0.999),
+            1e-8,
+            t,
+            0.1,
+        );
+    }
+    assert_eq!(param.as_slice(), &*cpu_param);
+
+    Stack.sgd_momentum_step(&mut param, &grad, &mut m, 0.1, 0.9, true);
+    cpu_param.sgd_momentum_step(&cpu_grad, &mut cpu_m, 0.1, 0.9, true);
+    assert_eq!(param.as_slice(), &*cpu_param);
+
+    Stack.rmsprop_step(&mut param, &grad, &mut v, 0.01, 0.9, 1e-8);
+    cpu_param.rmsprop_step(&cpu_grad, &mut cpu_v, 0.01, 0.9, 1e-8);
+    assert_eq!(param.as_slice(), &*cpu_param);
+
+    Stack.adagrad_step(&mut param, &grad, &mut v, 0.1, 1e-10);
+    cpu_param.adagrad_step(&cpu_grad, &mut cpu_v, 0.1, 1e-10);
+    assert_eq!(param.as_slice(), &*cpu_param);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_optim_cl() -> custos::Result<()> {
+    use custos::OpenCL;
+
+    let to_f32 = |values: &[f64]| values.iter().map(|x| *x as f32).collect::<Vec<_>>();
+    let assert_close = |lhs: Vec<f32>, rhs: &[f32]| {
+        for (a, b) in lhs.iter().zip(rhs) {
+            assert!((a - b).abs() < 1e-5, "{lhs:?} != {rhs:?}");
+        }
+    };
+
+    let device = OpenCL::new(0)?;
+    let grad = Matrix::from((&device, (2, 2), to_f32(&GRAD)));
+    let mut param = Matrix::from((&device, (2, 2), to_f32(&PARAM)));
+    let mut m = Matrix::from((&device, (2, 2), [0f32; 4]));
+    let mut v = Matrix::from((&device, (2, 2), [0f32; 4]));
+
+    let cpu = CPU::new();
+    let cpu_grad = Matrix::from((&cpu, (2, 2), to_f32(&GRAD)));
+    let mut cpu_param = Matrix::from((&cpu, (2, 2), to_f32(&PARAM)));
+    let mut cpu_m = Matrix::from((&cpu, (2, 2), [0f32; 4]));
+    let mut cpu_v = Matrix::from((&cpu, (2, 2), [0f32; 4]));
+
+    for t in 1..=3 {
+        param.adam_step(&grad, &mut m, &mut v, 0.01, (0.9, 0.999), 1e-8, t, 0.1);
+        cpu_param.adam_step(
+            &cpu_grad,
+            &mut cpu_m,
+            &mut cpu_v,
+            0.01,
+            (0.9, 0.999),
+            1e-8,
+            t,
+            0.1,
+        );
+    }
+    assert_close(param.read(), &cpu_param);
+    assert_close(m.read(), &cpu_m);
+    assert_close(v.read(), &cpu_v);
+
+    for nesterov in [false, true] {
+        param.sgd_momentum_step(&grad, &mut m, 0.1, 0.9, nesterov);
+        cpu_param.sgd_momentum_step(&cpu_grad, &mut cpu_m, 0.1, 0.9, nesterov);
+        assert_close(param.read(), &cpu_param);
+    }
+
+    param.rmsprop_step(&grad, &mut v, 0.01, 0.9, 1e-8);
+    cpu_param.rmsprop_step(&cpu_grad, &mut cpu_v, 0.01, 0.9, 1e-8);
+    assert_close(param.read(), &cpu_param);
+
+    param.adagrad_step(&grad, &mut v, 0.1, 1e-10);
+    cpu_param.adagrad_step(&cpu_grad, &mut cpu_v, 0.1, 1e-10);
+    assert_close(param.read(), &cpu_param);
+    assert_close(v.read(), &cpu_v);
+    Ok(())
+}
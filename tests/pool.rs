@@ -0,0 +1,198 @@
+use custos::CPU;
+use custos_math::{max_pool2d_grad_mut, max_pool2d_mut, ConvConfig, Matrix, PoolOps};
+
+/// Two channels of a 3x3 image: 1..=9 and -1..=-9.
+const X: [f64; 18] = [
+    1., 2., 3., 4., 5., 6., 7., 8., 9., -1., -2., -3., -4., -5., -6., -7., -8., -9.,
+];
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_max_pool2d_padded_cpu() {
+    let device = CPU::new();
+
+    let x = Matrix::from((&device, (2, 9), X));
+
+    // overlapping windows
+    let config = ConvConfig::new((1, 1), (1, 1), (1, 1));
+    let (out, indices) = x.max_pool2d::<()>((3, 3), (2, 2), config);
+
+    assert_eq!(out.dims(), (2, 16));
+    assert_eq!(
+        out.read(),
+        vec![
+            1., 2., 3., 3., 4., 5., 6., 6., 7., 8., 9., 9., 7., 8., 9., 9., -1., -1., -2., -3.,
+            -1., -1., -2., -3., -4., -4., -5., -6., -7., -7., -8., -9.,
+        ]
+    );
+    assert_eq!(
+        indices.read(),
+        vec![
+            0, 1, 2, 2, 3, 4, 5, 5, 6, 7, 8, 8, 6, 7, 8, 8, 0, 0, 1, 2, 0, 0, 1, 2, 3, 3, 4, 5, 6,
+            6, 7, 8,
+        ]
+    );
+
+    // the gradients of windows that share a maximum are summed up
+    let grads = Matrix::from((&device, (2, 16), [1.; 32]));
+    let dx = grads.max_pool2d_grad::<()>(&indices, (3, 3));
+    assert_eq!(dx.dims(), (2, 9));
+    assert_eq!(
+        dx.read(),
+        vec![1., 1., 2., 1., 1., 2., 2., 2., 4., 4., 2., 2., 2., 1., 1., 2., 1., 1.]
+    );
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_avg_pool2d_padded_cpu() {
+    let device = CPU::new();
+
+    let x = Matrix::from((&device, (2, 9), X));
+
+    let config = ConvConfig::new((1, 1), (1, 1), (1, 1));
+    let out: Matrix<f64> = device.avg_pool2d(&x, (3, 3), (2, 2), config);
+    assert_eq!(out.dims(), (2, 16));
+
+    let out = out.read();
+    let expected = [
+        0.25, 0.75, 1.25, 0.75, 1.25, 3., 4., 2.25, 2.75, 6., 7., 3.75, 1.75, 3.75, 4.25, 2.25,
+    ];
+    assert_eq!(out[..16], expected);
+    assert_eq!(out[16..], expected.map(|x| -x));
+}
+
+/// Both poolings are linear around `x`, hence `<pool(x), grads> = <x, pool_grad(grads)>`.
+#[cfg(feature = "cpu")]
+#[test]
+fn test_pool2d_grad_adjoint_cpu() {
+    let device = CPU::new();
+
+    let x = Matrix::from((&device, (2, 9), X));
+
+    for config in [
+        ConvConfig::default(),
+        ConvConfig::new((2, 2), (1, 1), (1, 1)),
+        ConvConfig::new((1, 2), (1, 0), (1, 1)),
+    ] {
+        let (out_rows, out_cols) = config.out_dims((3, 3), (2, 2));
+        let grads = (0..2 * out_rows * out_cols)
+            .map(|idx| (idx % 5) as f64 - 1.5)
+            .collect::<Vec<_>>();
+        let grads = Matrix::from((&device, (2, out_rows * out_cols), grads));
+
+        let dot = |lhs: &[f64], rhs: &[f64]| lhs.iter().zip(rhs).map(|(a, b)| a * b).sum::<f64>();
+
+        let (out, indices) = x.max_pool2d::<()>((3, 3), (2, 2), config);
+        let dx: Matrix<f64> = device.max_pool2d_grad(&grads, &indices, (3, 3));
+        assert_eq!(dot(&out, &grads), dot(&x, &dx));
+
+        let out = x.avg_pool2d::<()>((3, 3), (2, 2), config);
+        let dx: Matrix<f64> = device.avg_pool2d_grad(&grads, (3, 3), (2, 2), config);
+        assert!((dot(&out, &grads) - dot(&x, &dx)).abs() < 1e-12);
+    }
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+#[should_panic]
+fn test_max_pool2d_padding_larger_than_kernel() {
+    let device = CPU::new();
+
+    let x = Matrix::from((&device, (2, 9), X));
+    x.max_pool2d::<()>((3, 3), (2, 2), ConvConfig::new((1, 1), (2, 2), (1, 1)));
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+#[should_panic]
+fn test_max_pool2d_dilated_window_outside() {
+    let device = CPU::new();
+
+    // the taps of the only window are at -1 and 1
+    let x = Matrix::from((&device, (1, 1), [1.]));
+    x.max_pool2d::<()>((1, 1), (2, 2), ConvConfig::new((1, 1), (1, 1), (2, 2)));
+}
+
+#[test]
+fn test_max_pool2d_mut_window_outside() {
+    let config = ConvConfig::new((1, 1), (1, 1), (2, 2));
+
+    let mut out = [1.];
+    let mut indices = [0];
+    max_pool2d_mut(&[3.], 1, (1, 1), (2, 2), &config, &mut out, &mut indices);
+    assert_eq!(out, [0.]);
+    assert_eq!(indices, [u32::MAX]);
+
+    let mut grad = [1.];
+    max_pool2d_grad_mut(&[5.], &indices, 1, 1, &mut grad);
+    assert_eq!(grad, [0.]);
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_pool2d_stack() {
+    use custos::{Buffer, Dim1, Stack};
+
+    let x = Matrix {
+        data: Buffer::<_, _, Dim1<18>>::from((&Stack, X)),
+        dims: (2, 9),
+    };
+    let config = ConvConfig::new((1, 1), (1, 1), (1, 1));
+
+    let (out, indices): (Matrix<_, _, Dim1<32>>, _) = Stack.max_pool2d(&x, (3, 3), (2, 2), config);
+    let dx: Matrix<_, _, Dim1<18>> = Stack.max_pool2d_grad(&out, &indices, (3, 3));
+    let avg: Matrix<_, _, Dim1<32>> = Stack.avg_pool2d(&x, (3, 3), (2, 2), config);
+    let avg_dx: Matrix<_, _, Dim1<18>> = Stack.avg_pool2d_grad(&avg, (3, 3), (2, 2), config);
+
+    let device = CPU::new();
+    let cpu_x = Matrix::from((&device, (2, 9), X));
+
+    let (expected_out, expected_indices) = cpu_x.max_pool2d::<()>((3, 3), (2, 2), config);
+    let expected_dx = expected_out.max_pool2d_grad::<()>(&expected_indices, (3, 3));
+    let expected_avg = cpu_x.avg_pool2d::<()>((3, 3), (2, 2), config);
+    let expected_avg_dx = expected_avg.avg_pool2d_grad::<()>((3, 3), (2, 2), config);
+
+    assert_eq!(out.as_slice(), &*expected_out);
+    assert_eq!(indices.as_slice(), &*expected_indices);
+    assert_eq!(dx.as_slice(), &*expected_dx);
+    assert_eq!(avg.as_slice(), &*expected_avg);
+    assert_eq!(avg_dx.as_slice(), &*expected_avg_dx);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_pool2d_cl() -> custos::Result<()> {
+    use custos::OpenCL;
+
+    let device = OpenCL::new(0)?;
+    let cpu = CPU::new();
+
+    let values = X.iter().map(|x| *x as f32).collect::<Vec<_>>();
+    let x = Matrix::from((&device, (2, 9), values.clone()));
+    let cpu_x = Matrix::from((&cpu, (2, 9), values));
+
+    for config in [
+        ConvConfig::default(),
+        ConvConfig::new((1, 1), (1, 1), (1, 1)),
+        ConvConfig::new((2, 1), (1, 0), (1, 2)),
+    ] {
+        let (out, indices) = x.max_pool2d((3, 3), (2, 2), config);
+        let (expected_out, expected_indices) = cpu_x.max_pool2d::<()>((3, 3), (2, 2), config);
+        assert_eq!(out.read(), expected_out.read());
+        assert_eq!(indices.read(), expected_indices.read());
+
+        let dx = out.max_pool2d_grad(&indices, (3, 3));
+        let expected_dx = expected_out.max_pool2d_grad::<()>(&expected_indices, (3, 3));
+        assert_eq!(dx.read(), expected_dx.read());
+
+        let avg = x.avg_pool2d((3, 3), (2, 2), config);
+        let expected_avg = cpu_x.avg_pool2d::<()>((3, 3), (2, 2), config);
+        assert_eq!(avg.read(), expected_avg.read());
+
+        let avg_dx = avg.avg_pool2d_grad((3, 3), (2, 2), config);
+        let expected_avg_dx = expected_avg.avg_pool2d_grad::<()>((3, 3), (2, 2), config);
+        assert_eq!(avg_dx.read(), expected_avg_dx.read());
+    }
+    Ok(())
+}
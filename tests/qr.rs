@@ -0,0 +1,172 @@
+use custos::CPU;
+use custos_math::{LinalgError, Matrix, QrMode};
+
+#[cfg(feature = "cpu")]
+pub fn roughly_equals(lhs: &[f64], rhs: &[f64], diff: f64) {
+    assert_eq!(lhs.len(), rhs.len());
+    for (a, b) in lhs.iter().zip(rhs) {
+        let abs = (*a - *b).abs();
+        if abs > diff {
+            panic!(
+                "\n left: '{:?}',\n right: '{:?}', \n left elem.: {} != right elem. {}",
+                lhs, rhs, a, b
+            )
+        }
+    }
+}
+
+#[cfg(feature = "cpu")]
+fn identity(n: usize) -> Vec<f64> {
+    (0..n * n)
+        .map(|idx| if idx / n == idx % n { 1. } else { 0. })
+        .collect()
+}
+
+#[cfg(feature = "cpu")]
+fn check_qr(a: &Matrix<f64>, mode: QrMode, q_dims: (usize, usize), r_dims: (usize, usize)) {
+    let (q, r): (Matrix, Matrix) = a.qr(mode);
+    assert_eq!(q.dims(), q_dims);
+    assert_eq!(r.dims(), r_dims);
+
+    let reconstructed: Matrix = q.gemm(&r);
+    roughly_equals(&reconstructed, a, 1e-12);
+
+    let qtq: Matrix = q.T::<()>().gemm(&q);
+    roughly_equals(&qtq, &identity(q.cols()), 1e-12);
+
+    for row in 0..r.rows() {
+        for col in 0..row.min(r.cols()) {
+            assert_eq!(r[row * r.cols() + col], 0.);
+        }
+    }
+}
+
+const TALL: [f64; 12] = [1., 2., 3., 4., 5., 6., 7., 8., 10., 1., 0., 1.];
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_qr_cpu() {
+    let device = CPU::new();
+
+    let tall = Matrix::from((&device, (4, 3), TALL));
+    check_qr(&tall, QrMode::Thin, (4, 3), (3, 3));
+    check_qr(&tall, QrMode::Full, (4, 4), (4, 3));
+
+    let wide = Matrix::from((&device, (2, 3), [1., 2., 3., 4., 5., 6.]));
+    check_qr(&wide, QrMode::Thin, (2, 2), (2, 3));
+    check_qr(&wide, QrMode::Full, (2, 2), (2, 3));
+
+    // the second column is a multiple of the first one
+    let deficient = Matrix::from((&device, (3, 3), [1., 2., 0., 2., 4., 1., 3., 6., 0.]));
+    check_qr(&deficient, QrMode::Thin, (3, 3), (3, 3));
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_lstsq_cpu() -> Result<(), LinalgError> {
+    let device = CPU::new();
+
+    // y = 0.9 + 0.9x and y = 1 - x (exact)
+    let a = Matrix::from((&device, (4, 2), [1., 0., 1., 1., 1., 2., 1., 3.]));
+    let b = Matrix::from((&device, (4, 2), [1., 1., 2., 0., 2., -1., 4., -2.]));
+
+    let (x, residuals, rank): (Matrix, Matrix, usize) = a.lstsq(&b)?;
+    assert_eq!(x.dims(), (2, 2));
+    assert_eq!(residuals.dims(), (1, 2));
+    assert_eq!(rank, 2);
+    roughly_equals(&x, &[0.9, 1., 0.9, -1.], 1e-12);
+    roughly_equals(&residuals, &[0.7, 0.], 1e-12);
+
+    // the residuals are the squared norms of b - a * x
+    let prediction: Matrix = a.gemm(&x);
+    let diff = &b - &prediction;
+    let expected = [0, 1].map(|col| {
+        (0..4)
+            .map(|row| diff[row * 2 + col] * diff[row * 2 + col])
+            .sum::<f64>()
+    });
+    roughly_equals(&residuals, &expected, 1e-12);
+
+    // a square, regular system is solved exactly
+    let square = Matrix::from((&device, (2, 2), [4., 3., 6., 3.]));
+    let rhs = Matrix::from((&device, (2, 1), [10., 12.]));
+    let (x, residuals, rank): (Matrix, Matrix, usize) = square.lstsq(&rhs)?;
+    roughly_equals(&x, &[1., 2.], 1e-12);
+    roughly_equals(&residuals, &[0.], 1e-12);
+    assert_eq!(rank, 2);
+    Ok(())
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_lstsq_rank_deficient_cpu() -> Result<(), LinalgError> {
+    let device = CPU::new();
+
+    // the second column is twice the first one
+    let a = Matrix::from((
+        &device,
+        (4, 3),
+        [1., 2., 1., 2., 4., 0., 3., 6., 1., 4., 8., 2.],
+    ));
+    let b = Matrix::from((&device, (4, 1), [3., 2., 5., 8.]));
+
+    let (x, residuals, rank): (Matrix, Matrix, usize) = a.lstsq(&b)?;
+    assert_eq!(rank, 2);
+    assert_eq!(x[1], 0.);
+    roughly_equals(&x, &[1., 0., 2.], 1e-12);
+    roughly_equals(&residuals, &[0.], 1e-12);
+
+    let zeros = Matrix::from((&device, (2, 2), [0.; 4]));
+    let rhs = Matrix::from((&device, (2, 1), [1., 2.]));
+    let (x, residuals, rank): (Matrix, Matrix, usize) = zeros.lstsq(&rhs)?;
+    assert_eq!(rank, 0);
+    assert_eq!(x.read(), vec![0., 0.]);
+    roughly_equals(&residuals, &[5.], 1e-12);
+
+    let wrong_rows = Matrix::from((&device, (3, 1), [1., 2., 3.]));
+    let err = zeros
+        .lstsq::<(), (), ()>(&wrong_rows)
+        .map(|_| ())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        LinalgError::DimsMismatch {
+            lhs: (2, 2),
+            rhs: (3, 1)
+        }
+    );
+    Ok(())
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_qr_lstsq_stack() -> Result<(), LinalgError> {
+    use custos::{Buffer, Dim1, Stack};
+    use custos_math::{LstsqOp, QrOps};
+
+    let a = Matrix {
+        data: Buffer::<_, _, Dim1<12>>::from((&Stack, TALL)),
+        dims: (4, 3),
+    };
+    let b = Matrix {
+        data: Buffer::<_, _, Dim1<4>>::from((&Stack, [1., 2., 3., 4.])),
+        dims: (4, 1),
+    };
+
+    let device = CPU::new();
+    let cpu_a = Matrix::from((&device, (4, 3), TALL));
+    let cpu_b = Matrix::from((&device, (4, 1), [1., 2., 3., 4.]));
+
+    let (q, r): (Matrix<_, _, Dim1<12>>, Matrix<_, _, Dim1<9>>) = Stack.qr(&a, QrMode::Thin);
+    let (cpu_q, cpu_r): (Matrix, Matrix) = cpu_a.qr(QrMode::Thin);
+    assert_eq!(q.as_slice(), &*cpu_q);
+    assert_eq!(r.as_slice(), &*cpu_r);
+
+    let (x, residuals, rank): (Matrix<_, _, Dim1<3>>, Matrix<_, _, Dim1<1>>, usize) =
+        Stack.lstsq(&a, &b)?;
+    let (cpu_x, cpu_residuals, cpu_rank): (Matrix, Matrix, usize) = cpu_a.lstsq(&cpu_b)?;
+    assert_eq!(x.as_slice(), &*cpu_x);
+    assert_eq!(residuals.as_slice(), &*cpu_residuals);
+    assert_eq!(rank, cpu_rank);
+    Ok(())
+}
@@ -31,3 +31,211 @@ fn test_rand() -> custos::Result<()> {
     println!("{:?}", a);
     Ok(())
 }
+
+#[cfg(feature = "fastrand")]
+#[cfg(feature = "cpu")]
+#[test]
+fn test_rand_seed() {
+    use custos_math::Matrix;
+
+    let device = CPU::new();
+
+    let mut a = Matrix::<f32>::from((&device, (4, 5), [0.; 20]));
+    let mut b = Matrix::<f32>::from((&device, (4, 5), [0.; 20]));
+
+    RandOp::<f32>::seed(&device, 42);
+    a.rand(-1., 1.);
+    RandOp::<f32>::seed(&device, 42);
+    b.rand(-1., 1.);
+
+    assert_eq!(a.read(), b.read());
+    assert!(a.iter().all(|value| (-1. ..1.).contains(value)));
+
+    b.rand(-1., 1.);
+    assert_ne!(a.read(), b.read());
+}
+
+#[cfg(feature = "fastrand")]
+#[cfg(feature = "cpu")]
+#[test]
+fn test_rand_with() {
+    use custos_math::Matrix;
+
+    let device = CPU::new();
+
+    let mut a = Matrix::<f64>::from((&device, (3, 3), [0.; 9]));
+    let mut b = Matrix::<f64>::from((&device, (3, 3), [0.; 9]));
+
+    let mut rng = fastrand::Rng::with_seed(3);
+    a.rand_with(&mut rng, 2., 5.);
+    device.rand_with(&mut b, &mut fastrand::Rng::with_seed(3), 2., 5.);
+
+    assert_eq!(a.read(), b.read());
+    assert!(a.iter().all(|value| (2. ..5.).contains(value)));
+
+    // the generator advances
+    b.rand_with(&mut rng, 2., 5.);
+    assert_ne!(a.read(), b.read());
+}
+
+#[cfg(feature = "fastrand")]
+#[cfg(feature = "opencl")]
+#[test]
+fn test_rand_seed_cl() -> custos::Result<()> {
+    use custos::OpenCL;
+    use custos_math::Matrix;
+
+    let device = OpenCL::new(0)?;
+    let cpu = CPU::new();
+
+    let mut cl = Matrix::<f32, _>::from((&device, (2, 8), [0.; 16]));
+    let mut host = Matrix::<f32>::from((&cpu, (2, 8), [0.; 16]));
+
+    RandOp::<f32>::seed(&device, 7);
+    cl.rand(-3., 3.);
+    RandOp::<f32>::seed(&cpu, 7);
+    host.rand(-3., 3.);
+    assert_eq!(cl.read(), host.read());
+
+    cl.rand_with(&mut fastrand::Rng::with_seed(9), 0., 1.);
+    host.rand_with(&mut fastrand::Rng::with_seed(9), 0., 1.);
+    assert_eq!(cl.read(), host.read());
+    Ok(())
+}
+
+#[cfg(feature = "fastrand")]
+#[cfg(feature = "cuda")]
+#[test]
+fn test_rand_seed_cuda() -> custos::Result<()> {
+    use custos_math::Matrix;
+
+    let device = CUDA::new(0)?;
+    let cpu = CPU::new();
+
+    let mut cu = Matrix::<f32, _>::from((&device, (2, 8), [0.; 16]));
+    let mut host = Matrix::<f32>::from((&cpu, (2, 8), [0.; 16]));
+
+    RandOp::<f32>::seed(&device, 7);
+    cu.rand(-3., 3.);
+    RandOp::<f32>::seed(&cpu, 7);
+    host.rand(-3., 3.);
+    assert_eq!(cu.read(), host.read());
+    Ok(())
+}
+
+#[cfg(feature = "fastrand")]
+#[cfg(feature = "cpu")]
+fn mean_std(values: &[f64]) -> (f64, f64) {
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let var = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / values.len() as f64;
+    (mean, var.sqrt())
+}
+
+#[cfg(feature = "fastrand")]
+#[cfg(feature = "cpu")]
+#[test]
+fn test_randn() {
+    use custos_math::Matrix;
+
+    let device = CPU::new();
+    RandOp::<f64>::seed(&device, 1);
+
+    let mut a = Matrix::<f64>::from((&device, (100, 100), vec![0.; 10000]));
+    a.randn(3., 0.5);
+
+    let (mean, std) = mean_std(&a);
+    assert!((mean - 3.).abs() < 0.03, "mean: {mean}");
+    assert!((std - 0.5).abs() < 0.03, "std: {std}");
+
+    device.truncated_normal(&mut a, -1., 2.);
+    assert!(a.iter().all(|x| (-5. ..=3.).contains(x)));
+
+    // the truncated distribution has a smaller standard deviation
+    let (mean, std) = mean_std(&a);
+    assert!((mean + 1.).abs() < 0.1, "mean: {mean}");
+    assert!(std < 2. && std > 1.6, "std: {std}");
+}
+
+#[cfg(feature = "fastrand")]
+#[cfg(feature = "cpu")]
+#[test]
+fn test_initialisers() {
+    use custos_math::Matrix;
+
+    let device = CPU::new();
+    RandOp::<f64>::seed(&device, 2);
+
+    // fan-in: 200, fan-out: 100
+    let mut w = Matrix::<f64>::from((&device, (200, 100), vec![0.; 20000]));
+
+    w.xavier_uniform();
+    let limit = (6f64 / 300.).sqrt();
+    assert!(w.iter().all(|x| (-limit..limit).contains(x)));
+
+    w.he_uniform();
+    let limit = (6f64 / 200.).sqrt();
+    assert!(w.iter().all(|x| (-limit..limit).contains(x)));
+
+    let check_std = |w: &Matrix<f64>, expected_std: f64| {
+        let (mean, std) = mean_std(w);
+        assert!(mean.abs() < 0.01, "mean: {mean}");
+        assert!((std - expected_std).abs() < 0.01, "std: {std}");
+    };
+
+    w.xavier_normal();
+    check_std(&w, (2f64 / 300.).sqrt());
+
+    w.he_normal();
+    check_std(&w, (2f64 / 200.).sqrt());
+
+    w.lecun();
+    check_std(&w, (1f64 / 200.).sqrt());
+}
+
+#[cfg(feature = "fastrand")]
+#[cfg(feature = "stack")]
+#[test]
+fn test_randn_stack() {
+    use custos::{Dim1, Stack};
+    use custos_math::Matrix;
+
+    let mut a = Matrix {
+        data: Buffer::<f32, _, Dim1<6>>::from((&Stack, [0.; 6])),
+        dims: (2, 3),
+    };
+    let mut b = Matrix::<f32>::from((&CPU::new(), (2, 3), [0.; 6]));
+
+    RandOp::<f32>::seed(&Stack, 5);
+    a.truncated_normal(0., 1.);
+    RandOp::<f32>::seed(&Stack, 5);
+    b.truncated_normal(0., 1.);
+
+    assert_eq!(a.as_slice(), &*b);
+}
+
+#[cfg(feature = "fastrand")]
+#[cfg(feature = "opencl")]
+#[test]
+fn test_randn_cl() -> custos::Result<()> {
+    use custos::OpenCL;
+    use custos_math::Matrix;
+
+    let device = OpenCL::new(0)?;
+    let cpu = CPU::new();
+
+    let mut cl = Matrix::<f32, _>::from((&device, (4, 8), [0.; 32]));
+    let mut host = Matrix::<f32>::from((&cpu, (4, 8), [0.; 32]));
+
+    RandOp::<f32>::seed(&device, 11);
+    cl.he_normal();
+    RandOp::<f32>::seed(&cpu, 11);
+    host.he_normal();
+    assert_eq!(cl.read(), host.read());
+
+    RandOp::<f32>::seed(&device, 12);
+    cl.truncated_normal(1., 0.1);
+    RandOp::<f32>::seed(&cpu, 12);
+    host.truncated_normal(1., 0.1);
+    assert_eq!(cl.read(), host.read());
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use custos::CPU;
+use custos_math::{Axis, Matrix, Reduce, ReduceOps};
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_reduce_cpu() {
+    let device = CPU::new();
+    let x = Matrix::from((&device, (2, 3), [1., 2., 3., 4., 5., 6.]));
+
+    assert_eq!(x.reduce(Axis::Rows, Reduce::Sum).read(), vec![5., 7., 9.]);
+    assert_eq!(x.reduce(Axis::Cols, Reduce::Sum).read(), vec![6., 15.]);
+    assert_eq!(x.reduce(Axis::All, Reduce::Sum).read(), vec![21.]);
+
+    assert_eq!(
+        x.reduce(Axis::Rows, Reduce::Mean).read(),
+        vec![2.5, 3.5, 4.5]
+    );
+    assert_eq!(x.reduce(Axis::Cols, Reduce::Mean).read(), vec![2., 5.]);
+    assert_eq!(x.reduce(Axis::All, Reduce::Mean).read(), vec![3.5]);
+
+    assert_eq!(x.reduce(Axis::Rows, Reduce::Max).read(), vec![4., 5., 6.]);
+    assert_eq!(x.reduce(Axis::Cols, Reduce::Min).read(), vec![1., 4.]);
+    assert_eq!(x.reduce(Axis::All, Reduce::Prod).read(), vec![720.]);
+
+    assert_eq!(x.reduce(Axis::Cols, Reduce::ArgMax).read(), vec![2., 2.]);
+    assert_eq!(x.reduce(Axis::All, Reduce::ArgMax).read(), vec![5.]);
+    assert_eq!(
+        x.reduce(Axis::Rows, Reduce::ArgMin).read(),
+        vec![0., 0., 0.]
+    );
+
+    assert_eq!(
+        x.reduce(Axis::Rows, Reduce::Var).read(),
+        vec![2.25, 2.25, 2.25]
+    );
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_reduce_dims() {
+    let device = CPU::new();
+    let x = Matrix::<f32>::new(&device, (4, 7));
+
+    assert_eq!(device.reduce(&x, Axis::Rows, Reduce::Min).dims(), (1, 7));
+    assert_eq!(device.reduce(&x, Axis::Cols, Reduce::Var).dims(), (4, 1));
+    assert_eq!(device.reduce(&x, Axis::All, Reduce::Prod).dims(), (1, 1));
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_reduce_stack() {
+    use custos::{Buffer, Dim1, Stack};
+
+    let x = Matrix {
+        data: Buffer::<_, _, Dim1<6>>::from((&Stack, [1., 2., 3., 4., 5., 6.])),
+        dims: (2, 3),
+    };
+
+    let out: Matrix<_, _, Dim1<3>> = Stack.reduce(&x, Axis::Rows, Reduce::Max);
+    assert_eq!(out.as_slice(), &[4., 5., 6.]);
+
+    let out: Matrix<_, _, Dim1<2>> = Stack.reduce(&x, Axis::Cols, Reduce::Sum);
+    assert_eq!(out.as_slice(), &[6., 15.]);
+
+    let out: Matrix<_, _, Dim1<1>> = Stack.reduce(&x, Axis::All, Reduce::Min);
+    assert_eq!(out.as_slice(), &[1.]);
+
+    let out: Matrix<_, _, Dim1<2>> = Stack.reduce(&x, Axis::Cols, Reduce::Prod);
+    assert_eq!(out.as_slice(), &[6., 120.]);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_reduce_cl() -> custos::Result<()> {
+    let device = custos::OpenCL::new(0)?;
+    let x = Matrix::from((&device, (2, 3), [1f32, 2., 3., 4., 5., 6.]));
+
+    assert_eq!(x.reduce(Axis::Rows, Reduce::Sum).read(), vec![5., 7., 9.]);
+    assert_eq!(x.reduce(Axis::Cols, Reduce::Max).read(), vec![3., 6.]);
+    assert_eq!(x.reduce(Axis::All, Reduce::Mean).read(), vec![3.5]);
+    assert_eq!(x.reduce(Axis::Cols, Reduce::Min).read(), vec![1., 4.]);
+    assert_eq!(
+        x.reduce(Axis::Rows, Reduce::Var).read(),
+        vec![2.25, 2.25, 2.25]
+    );
+    Ok(())
+}
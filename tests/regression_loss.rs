@@ -0,0 +1,217 @@
+use custos::CPU;
+use custos_math::{
+    nn::{huber, log_cosh, mae, smooth_l1},
+    Matrix,
+};
+
+#[cfg(feature = "cpu")]
+pub fn roughly_equals(lhs: &[f64], rhs: &[f64], diff: f64) {
+    for (a, b) in lhs.iter().zip(rhs) {
+        let abs = (*a - *b).abs();
+        if abs > diff {
+            panic!(
+                "\n left: '{:?}',\n right: '{:?}', \n left elem.: {} != right elem. {}",
+                lhs, rhs, a, b
+            )
+        }
+    }
+}
+
+// preds - targets = [0.5, -0.5, 0., 1., 10., -0.5]
+const PREDS: [f64; 6] = [1.5, -0.2, 3., 0.4, 10., 2.];
+const TARGETS: [f64; 6] = [1., 0.3, 3., -0.6, 0., 2.5];
+
+#[cfg(feature = "cpu")]
+fn numeric_grad(loss: impl Fn(&[f64]) -> f64) -> Vec<f64> {
+    let h = 1e-6;
+    (0..PREDS.len())
+        .map(|idx| {
+            let mut plus = PREDS.to_vec();
+            plus[idx] += h;
+            let mut minus = PREDS.to_vec();
+            minus[idx] -= h;
+            (loss(&plus) - loss(&minus)) / (2. * h)
+        })
+        .collect()
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_mae_cpu() {
+    let device = CPU::new();
+
+    let preds = Matrix::from((&device, (2, 3), PREDS));
+    let targets = Matrix::from((&device, (2, 3), TARGETS));
+
+    let (loss, grad) = mae(&preds, &targets);
+    assert!((loss - 12.5 / 6.).abs() < 1e-12);
+    roughly_equals(
+        &grad,
+        &[1. / 6., -1. / 6., 0., 1. / 6., 1. / 6., -1. / 6.],
+        1e-12,
+    );
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_huber_smooth_l1_cpu() {
+    let device = CPU::new();
+
+    let preds = Matrix::from((&device, (2, 3), PREDS));
+    let targets = Matrix::from((&device, (2, 3), TARGETS));
+
+    let (loss, grad) = huber(&preds, &targets, 1.);
+    assert!((loss - 1.729167).abs() < 1e-6);
+
+    // the gradient of the outlier is limited to delta
+    roughly_equals(
+        &grad,
+        &[0.5 / 6., -0.5 / 6., 0., 1. / 6., 1. / 6., -0.5 / 6.],
+        1e-12,
+    );
+
+    let numeric = numeric_grad(|preds| {
+        let preds = Matrix::from((&device, (2, 3), preds.to_vec()));
+        huber(&preds, &targets, 1.).0
+    });
+    roughly_equals(&grad, &numeric, 1e-6);
+
+    let (loss, grad) = smooth_l1(&preds, &targets, 0.5);
+    assert!((loss - 1.875).abs() < 1e-12);
+
+    let numeric = numeric_grad(|preds| {
+        let preds = Matrix::from((&device, (2, 3), preds.to_vec()));
+        smooth_l1(&preds, &targets, 0.5).0
+    });
+    roughly_equals(&grad, &numeric, 1e-6);
+
+    // with beta = 1, smooth l1 and huber are the same
+    let (huber_loss, huber_grad) = huber(&preds, &targets, 1.);
+    let (smooth_l1_loss, smooth_l1_grad) = smooth_l1(&preds, &targets, 1.);
+    assert_eq!(huber_loss, smooth_l1_loss);
+    assert_eq!(huber_grad.read(), smooth_l1_grad.read());
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_log_cosh_cpu() {
+    let device = CPU::new();
+
+    let preds = Matrix::from((&device, (2, 3), PREDS));
+    let targets = Matrix::from((&device, (2, 3), TARGETS));
+
+    let (loss, grad) = log_cosh(&preds, &targets);
+    assert!((loss - 1.683496).abs() < 1e-6);
+
+    let numeric = numeric_grad(|preds| {
+        let preds = Matrix::from((&device, (2, 3), preds.to_vec()));
+        log_cosh(&preds, &targets).0
+    });
+    roughly_equals(&grad, &numeric, 1e-6);
+
+    // cosh(1000) overflows, the loss does not
+    let preds = Matrix::from((&device, (1, 2), [1000., -1000.]));
+    let targets = Matrix::from((&device, (1, 2), [0., 0.]));
+    let (loss, _) = log_cosh(&preds, &targets);
+    assert!((loss - (1000. - 2f64.ln())).abs() < 1e-9);
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_regression_loss_stack() {
+    use custos::{Buffer, Dim1, Stack};
+
+    let preds = Matrix {
+        data: Buffer::<_, _, Dim1<6>>::from((&Stack, PREDS)),
+        dims: (2, 3),
+    };
+    let targets = Matrix {
+        data: Buffer::<_, _, Dim1<6>>::from((&Stack, TARGETS)),
+        dims: (2, 3),
+    };
+
+    let device = CPU::new();
+    let cpu_preds = Matrix::from((&device, (2, 3), PREDS));
+    let cpu_targets = Matrix::from((&device, (2, 3), TARGETS));
+
+    let (loss, grad) = mae(&preds, &targets);
+    let (expected_loss, expected_grad) = mae(&cpu_preds, &cpu_targets);
+    assert_eq!(loss, expected_loss);
+    assert_eq!(grad.as_slice(), &*expected_grad);
+
+    let (loss, grad) = huber(&preds, &targets, 1.);
+    let (expected_loss, expected_grad) = huber(&cpu_preds, &cpu_targets, 1.);
+    assert_eq!(loss, expected_loss);
+    assert_eq!(grad.as_slice(), &*expected_grad);
+
+    let (loss, grad) = smooth_l1(&preds, &targets, 0.5);
+    let (expected_loss, expected_grad) = smooth_l1(&cpu_preds, &cpu_targets, 0.5);
+    assert_eq!(loss, expected_loss);
+    assert_eq!(grad.as_slice(), &*expected_grad);
+
+    let (loss, grad) = log_cosh(&preds, &targets);
+    let (expected_loss, expected_grad) = log_cosh(&cpu_preds, &cpu_targets);
+    assert_eq!(loss, expected_loss);
+    assert_eq!(grad.as_slice(), &*expected_grad);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_regression_loss_cl() -> custos::Result<()> {
+    use custos::OpenCL;
+    use custos_math::nn::{huber_grad_cl, log_cosh_grad_cl, mae_grad_cl, smooth_l1_grad_cl};
+
+    let to_f32 = |values: &[f64]| values.iter().map(|x| *x as f32).collect::<Vec<_>>();
+
+    let device = OpenCL::new(0)?;
+    let preds = Matrix::from((&device, (2, 3), to_f32(&PREDS)));
+    let targets = Matrix::from((&device, (2, 3), to_f32(&TARGETS)));
+
+    let cpu = CPU::new();
+    let cpu_preds = Matrix::from((&cpu, (2, 3), to_f32(&PREDS)));
+    let cpu_targets = Matrix::from((&cpu, (2, 3), to_f32(&TARGETS)));
+
+    // OpenCL is not required to round exp, ln and tanh correctly
+    let roughly_equals = |lhs: Vec<f32>, rhs: Vec<f32>| {
+        for (a, b) in lhs.iter().zip(&rhs) {
+            assert!((a - b).abs() < 1e-5, "{lhs:?} != {rhs:?}");
+        }
+    };
+
+    let (loss, grad) = mae(&preds, &targets);
+    let (expected_loss, expected_grad) = mae(&cpu_preds, &cpu_targets);
+    roughly_equals(vec![loss], vec![expected_loss]);
+    roughly_equals(grad.read(), expected_grad.read());
+    roughly_equals(
+        mae_grad_cl(&device, &preds, &targets).read(),
+        expected_grad.read(),
+    );
+
+    let (loss, grad) = huber(&preds, &targets, 1.);
+    let (expected_loss, expected_grad) = huber(&cpu_preds, &cpu_targets, 1.);
+    roughly_equals(vec![loss], vec![expected_loss]);
+    roughly_equals(grad.read(), expected_grad.read());
+    roughly_equals(
+        huber_grad_cl(&device, &preds, &targets, 1.).read(),
+        expected_grad.read(),
+    );
+
+    let (loss, grad) = smooth_l1(&preds, &targets, 0.5);
+    let (expected_loss, expected_grad) = smooth_l1(&cpu_preds, &cpu_targets, 0.5);
+    roughly_equals(vec![loss], vec![expected_loss]);
+    roughly_equals(grad.read(), expected_grad.read());
+    roughly_equals(
+        smooth_l1_grad_cl(&device, &preds, &targets, 0.5).read(),
+        expected_grad.read(),
+    );
+
+    let (loss, grad) = log_cosh(&preds, &targets);
+    let (expected_loss, expected_grad) = log_cosh(&cpu_preds, &cpu_targets);
+    roughly_equals(vec![loss], vec![expected_loss]);
+    roughly_equals(grad.read(), expected_grad.read());
+    roughly_equals(
+        log_cosh_grad_cl(&device, &preds, &targets).read(),
+        expected_grad.read(),
+    );
+    Ok(())
+}
@@ -78,3 +78,41 @@ fn test_row_op_mut_cu() -> custos::Result<()> {
     assert_eq!(a.read(), vec![2., 4., 6., 5., 7., 9., 8., 10., 12.]);
     Ok(())
 }
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_sub_mul_row() {
+    let device = CPU::new();
+
+    let mut a = Matrix::from((&device, (2, 3), [1., 2., 3., 4., 5., 6.]));
+    let b = Matrix::from((&device, (1, 3), [1., 2., 3.]));
+
+    assert_eq!(a.sub_row(&b).read(), vec![0., 0., 0., 3., 3., 3.]);
+    assert_eq!(a.mul_row(&b).read(), vec![1., 4., 9., 4., 10., 18.]);
+
+    a.mul_row_mut(&b);
+    assert_eq!(a.as_slice(), &[1., 4., 9., 4., 10., 18.]);
+
+    a.sub_row_mut(&b);
+    assert_eq!(a.as_slice(), &[0., 2., 6., 3., 8., 15.]);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_sub_mul_row_cl() -> custos::Result<()> {
+    let device = custos::OpenCL::new(0)?;
+
+    let mut a = Matrix::from((&device, (2, 3), [1., 2., 3., 4., 5., 6.]));
+    let b = Matrix::from((&device, (1, 3), [1., 2., 3.]));
+
+    assert_eq!(device.sub_row(&a, &b).read(), vec![0., 0., 0., 3., 3., 3.]);
+    assert_eq!(
+        device.mul_row(&a, &b).read(),
+        vec![1., 4., 9., 4., 10., 18.]
+    );
+
+    device.mul_row_mut(&mut a, &b);
+    device.sub_row_mut(&mut a, &b);
+    assert_eq!(a.read(), vec![0., 2., 6., 3., 8., 15.]);
+    Ok(())
+}
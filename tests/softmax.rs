@@ -1,5 +1,35 @@
 use custos::{range, CPU};
-use custos_math::{nn::SoftmaxOps, Matrix};
+use custos_math::{nn::SoftmaxOps, BaseOps, Matrix};
+
+pub fn roughly_equals(lhs: &[f32], rhs: &[f32], diff: f32) {
+    for (a, b) in lhs.iter().zip(rhs) {
+        let abs = (*a - *b).abs();
+        if abs > diff {
+            panic!(
+                "\n left: '{:?}',\n right: '{:?}', \n left elem.: {} != right elem. {}",
+                lhs, rhs, a, b
+            )
+        }
+    }
+}
+
+/// Builds the jacobian `diagflat(s) - s * s^T` for every row and multiplies it with the gradient row.
+fn jacobian_softmax_grad(device: &CPU, activated: &[f32], grads: &[f32], cols: usize) -> Vec<f32> {
+    let mut out = Vec::with_capacity(grads.len());
+
+    for (activated, grads) in activated.chunks(cols).zip(grads.chunks(cols)) {
+        let single_out = Matrix::from((device, (cols, 1), activated));
+        let single_grad = Matrix::from((device, (cols, 1), grads));
+
+        let jacobian = device.sub(
+            &single_out.diagflat(),
+            &single_out.gemm(&single_out.T::<()>()),
+        );
+        let res: Matrix<f32> = jacobian.gemm(&single_grad);
+        out.extend_from_slice(&res);
+    }
+    out
+}
 
 #[cfg(feature = "cpu")]
 #[test]
@@ -17,6 +47,82 @@ fn test_softmax_cpu() {
     }
 }
 
+#[cfg(feature = "cpu")]
+#[test]
+fn test_softmax_grad_matches_jacobian_cpu() {
+    let device = CPU::new();
+
+    let x = Matrix::from((
+        &device,
+        (3, 4),
+        [1., 2., 3., 4., -1., 0.5, 0.25, 2., 3., -2., 1., 0.],
+    ));
+    let activated = x.softmax();
+
+    let grads = Matrix::from((
+        &device,
+        (3, 4),
+        [0.5, -1., 2., 0.1, 1., 1., -3., 0.2, -0.7, 0.3, 1.5, -2.],
+    ));
+
+    let out = device.softmax_grad(&activated, &grads);
+    assert_eq!(out.dims(), (3, 4));
+    assert_eq!(out.read(), grads.softmax_grad(&activated).read());
+
+    let expected = jacobian_softmax_grad(&device, &activated, &grads, 4);
+    // the last row is included as well
+    roughly_equals(&out, &expected, 1e-6);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_log_softmax_cpu() {
+    let device = CPU::new();
+
+    let x = Matrix::from((&device, (2, 3), [1., 2., 3., -1., 0.5, 0.25]));
+    let out = x.log_softmax();
+
+    roughly_equals(
+        &out,
+        &[-2.40761, -1.40761, -0.40761, -2.19411, -0.69411, -0.94411],
+        1e-5,
+    );
+
+    // softmax(x).ln() underflows to -inf
+    let x = Matrix::from((&device, (1, 2), [100., -100.]));
+    let out = device.log_softmax(&x);
+    assert_eq!(out.read(), vec![0., -200.]);
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_softmax_stack() {
+    use custos::{Buffer, Dim1, Stack};
+
+    let device = CPU::new();
+
+    let x = [1., 2., 3., -1., 0.5, 0.25];
+    let grads = [0.5, -1., 2., 1., 1., -3.];
+
+    let activated = Matrix::from((&device, (2, 3), x)).softmax();
+    let expected = device.softmax_grad(&activated, &Matrix::from((&device, (2, 3), grads)));
+
+    let x = Matrix {
+        data: Buffer::<_, _, Dim1<6>>::from((&Stack, x)),
+        dims: (2, 3),
+    };
+    let grads = Matrix {
+        data: Buffer::<_, _, Dim1<6>>::from((&Stack, grads)),
+        dims: (2, 3),
+    };
+
+    let stack_activated = Stack.softmax(&x);
+    roughly_equals(stack_activated.as_slice(), &activated, 1e-6);
+
+    let out = Stack.softmax_grad(&stack_activated, &grads);
+    roughly_equals(out.as_slice(), &expected, 1e-6);
+}
+
 #[cfg(feature = "opencl")]
 #[test]
 fn test_softmax_cl() -> custos::Result<()> {
@@ -34,6 +140,26 @@ fn test_softmax_cl() -> custos::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "opencl")]
+#[test]
+fn test_softmax_grad_matches_jacobian_cl() -> custos::Result<()> {
+    let device = custos::OpenCL::new(0)?;
+    let cpu = CPU::new();
+
+    let x = [1., 2., 3., 4., -1., 0.5, 0.25, 2., 3., -2., 1., 0.];
+    let grads = [0.5, -1., 2., 0.1, 1., 1., -3., 0.2, -0.7, 0.3, 1.5, -2.];
+
+    let activated = Matrix::from((&cpu, (3, 4), x)).softmax();
+    let expected = jacobian_softmax_grad(&cpu, &activated, &grads, 4);
+
+    let cl_activated = Matrix::from((&device, (3, 4), activated.read()));
+    let cl_grads = Matrix::from((&device, (3, 4), grads));
+
+    let out = device.softmax_grad(&cl_activated, &cl_grads);
+    roughly_equals(&out.read(), &expected, 1e-6);
+    Ok(())
+}
+
 #[cfg(feature = "opencl")]
 #[test]
 fn test_softmax_kernel_cl() -> custos::Result<()> {
@@ -0,0 +1,195 @@
+use custos::CPU;
+use custos_math::Matrix;
+
+#[cfg(feature = "cpu")]
+pub fn roughly_equals(lhs: &[f64], rhs: &[f64], diff: f64) {
+    assert_eq!(lhs.len(), rhs.len());
+    for (a, b) in lhs.iter().zip(rhs) {
+        let abs = (*a - *b).abs();
+        if abs > diff {
+            panic!(
+                "\n left: '{:?}',\n right: '{:?}', \n left elem.: {} != right elem. {}",
+                lhs, rhs, a, b
+            )
+        }
+    }
+}
+
+#[cfg(feature = "cpu")]
+fn identity(n: usize) -> Vec<f64> {
+    (0..n * n)
+        .map(|idx| if idx / n == idx % n { 1. } else { 0. })
+        .collect()
+}
+
+#[cfg(feature = "cpu")]
+fn check_svd(a: &Matrix<f64>) -> Vec<f64> {
+    let (m, n) = a.dims();
+    let k = m.min(n);
+
+    let (u, sigma, vt): (Matrix, Matrix, Matrix) = a.svd();
+    assert_eq!(u.dims(), (m, k));
+    assert_eq!(sigma.dims(), (1, k));
+    assert_eq!(vt.dims(), (k, n));
+    assert!(sigma.windows(2).all(|pair| pair[0] >= pair[1]));
+    assert!(sigma.iter().all(|value| *value >= 0.));
+
+    let scaled_u = (0..m * k)
+        .map(|idx| u[idx] * sigma[idx % k])
+        .collect::<Vec<_>>();
+    let scaled_u = Matrix::from((a.device(), (m, k), scaled_u));
+    let reconstructed: Matrix = scaled_u.gemm(&vt);
+    roughly_equals(&reconstructed, a, 1e-12);
+
+    let utu: Matrix = u.T::<()>().gemm(&u);
+    roughly_equals(&utu, &identity(k), 1e-12);
+
+    let vvt: Matrix = vt.gemm(&vt.T::<()>());
+    roughly_equals(&vvt, &identity(k), 1e-12);
+
+    sigma.read()
+}
+
+const TALL: [f64; 12] = [1., 2., 3., 4., 5., 6., 7., 8., 10., 1., 0., 1.];
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_svd_cpu() {
+    let device = CPU::new();
+
+    let tall = Matrix::from((&device, (4, 3), TALL));
+    let sigma = check_svd(&tall);
+
+    // the singular values of a^T are the same
+    let wide: Matrix = tall.T();
+    roughly_equals(&check_svd(&wide), &sigma, 1e-12);
+
+    let a = Matrix::from((&device, (2, 3), [3., 2., 2., 2., 3., -2.]));
+    roughly_equals(&check_svd(&a), &[5., 3.], 1e-12);
+
+    let row = Matrix::from((&device, (1, 3), [2., -1., 2.]));
+    roughly_equals(&check_svd(&row), &[3.], 1e-12);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_svd_rank_deficient_cpu() {
+    let device = CPU::new();
+
+    // the second column is twice the first one
+    let a = Matrix::from((
+        &device,
+        (4, 3),
+        [1., 2., 1., 2., 4., 0., 3., 6., 1., 4., 8., 2.],
+    ));
+    let sigma = check_svd(&a);
+    assert_eq!(sigma[2], 0.);
+    assert_eq!(a.matrix_rank(), 2);
+
+    // the singular vectors of zero singular values are still orthonormal
+    let zeros = Matrix::from((&device, (3, 2), [0.; 6]));
+    assert_eq!(check_svd(&zeros), vec![0., 0.]);
+    assert_eq!(zeros.matrix_rank(), 0);
+
+    assert_eq!(Matrix::from((&device, (4, 3), TALL)).matrix_rank(), 3);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_pinv_cpu() {
+    let device = CPU::new();
+
+    let a = Matrix::from((&device, (3, 2), [1., 2., 3., 4., 5., 6.]));
+    let pinv: Matrix = a.pinv();
+    assert_eq!(pinv.dims(), (2, 3));
+    roughly_equals(
+        &pinv,
+        &[-4. / 3., -1. / 3., 2. / 3., 13. / 12., 1. / 3., -5. / 12.],
+        1e-12,
+    );
+
+    // the pseudo-inverse of a regular matrix is its inverse
+    let square = Matrix::from((&device, (2, 2), [4., 7., 2., 6.]));
+    let pinv: Matrix = square.pinv();
+    roughly_equals(&pinv, &square.inv().unwrap(), 1e-12);
+
+    // moore-penrose conditions of a rank deficient matrix
+    let a = Matrix::from((
+        &device,
+        (4, 3),
+        [1., 2., 1., 2., 4., 0., 3., 6., 1., 4., 8., 2.],
+    ));
+    let pinv: Matrix = a.pinv();
+    assert_eq!(pinv.dims(), (3, 4));
+
+    let a_pinv: Matrix = a.gemm(&pinv);
+    let a_pinv_a: Matrix = a_pinv.gemm(&a);
+    roughly_equals(&a_pinv_a, &a, 1e-12);
+
+    let pinv_a: Matrix = pinv.gemm(&a);
+    let pinv_a_pinv: Matrix = pinv_a.gemm(&pinv);
+    roughly_equals(&pinv_a_pinv, &pinv, 1e-12);
+
+    roughly_equals(&a_pinv, &a_pinv.T::<()>(), 1e-12);
+    roughly_equals(&pinv_a, &pinv_a.T::<()>(), 1e-12);
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_svd_stack() {
+    use custos::{Buffer, Dim1, Stack};
+    use custos_math::{MatrixRankOp, PinvOp, SvdOps};
+
+    let a = Matrix {
+        data: Buffer::<_, _, Dim1<12>>::from((&Stack, TALL)),
+        dims: (4, 3),
+    };
+
+    let device = CPU::new();
+    let cpu_a = Matrix::from((&device, (4, 3), TALL));
+
+    let (u, sigma, vt): (
+        Matrix<_, _, Dim1<12>>,
+        Matrix<_, _, Dim1<3>>,
+        Matrix<_, _, Dim1<9>>,
+    ) = Stack.svd(&a);
+    let (cpu_u, cpu_sigma, cpu_vt): (Matrix, Matrix, Matrix) = cpu_a.svd();
+    assert_eq!(u.as_slice(), &*cpu_u);
+    assert_eq!(sigma.as_slice(), &*cpu_sigma);
+    assert_eq!(vt.as_slice(), &*cpu_vt);
+
+    let pinv: Matrix<_, _, Dim1<12>> = Stack.pinv(&a);
+    let cpu_pinv: Matrix = cpu_a.pinv();
+    assert_eq!(pinv.dims(), (3, 4));
+    assert_eq!(pinv.as_slice(), &*cpu_pinv);
+
+    assert_eq!(Stack.matrix_rank(&a), 3);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_svd_cl() -> custos::Result<()> {
+    use custos::OpenCL;
+
+    let tall = TALL.map(|x| x as f32);
+
+    let device = OpenCL::new(0)?;
+    let a = Matrix::from((&device, (4, 3), tall));
+
+    let cpu = CPU::new();
+    let cpu_a = Matrix::from((&cpu, (4, 3), tall));
+
+    let (u, sigma, vt): (Matrix<f32, _>, Matrix<f32, _>, Matrix<f32, _>) = a.svd();
+    let (cpu_u, cpu_sigma, cpu_vt): (Matrix<f32>, Matrix<f32>, Matrix<f32>) = cpu_a.svd();
+    assert_eq!(u.read(), cpu_u.read());
+    assert_eq!(sigma.read(), cpu_sigma.read());
+    assert_eq!(vt.read(), cpu_vt.read());
+
+    let pinv: Matrix<f32, _> = a.pinv();
+    let cpu_pinv: Matrix<f32> = cpu_a.pinv();
+    assert_eq!(pinv.dims(), (3, 4));
+    assert_eq!(pinv.read(), cpu_pinv.read());
+
+    assert_eq!(a.matrix_rank(), 3);
+    Ok(())
+}